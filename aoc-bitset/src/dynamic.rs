@@ -0,0 +1,238 @@
+use std::ops::{BitAnd, BitOr, RangeInclusive, Sub};
+
+use crate::{count_ones, ranges, word_and_mask, Iter, Ranges};
+
+// Set of integers that grows to fit the largest inserted value.
+#[derive(Clone, Debug, Default)]
+pub struct DynBitSet {
+    words: Vec<u64>,
+}
+
+impl DynBitSet {
+    pub fn new() -> Self {
+        Self { words: Vec::new() }
+    }
+
+    // Create a set able to hold values less than `capacity` without
+    // reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(u64::BITS as usize)],
+        }
+    }
+
+    // Adds `value` to the set, returning whether it was newly inserted.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, mask) = word_and_mask(value);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        inserted
+    }
+
+    // Removes `value` from the set, returning whether it was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, mask) = word_and_mask(value);
+        match self.words.get_mut(word) {
+            Some(word) => {
+                let removed = *word & mask != 0;
+                *word &= !mask;
+                removed
+            }
+            None => false,
+        }
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        let (word, mask) = word_and_mask(value);
+        self.words.get(word).is_some_and(|word| word & mask != 0)
+    }
+
+    // Adds every value in `range`, growing the set if needed.
+    pub fn insert_range(&mut self, range: RangeInclusive<u32>) {
+        if range.is_empty() {
+            return;
+        }
+        let (word, _) = word_and_mask(*range.end() as usize);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        ranges::fill(&mut self.words, &range);
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let (longer, shorter) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.words.clone();
+        for (word, other) in words.iter_mut().zip(&shorter.words) {
+            *word |= other;
+        }
+        Self { words }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .map(|(a, b)| a & b)
+                .collect(),
+        }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut words = self.words.clone();
+        for (word, other) in words.iter_mut().zip(&other.words) {
+            *word &= !other;
+        }
+        Self { words }
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.words)
+    }
+
+    // The set as the minimal list of disjoint ranges, in ascending order.
+    pub fn ranges(&self) -> Ranges<'_> {
+        Ranges::new(&self.words)
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+// Sets are equal if they contain the same values, regardless of how many
+// trailing zero words either one has allocated.
+impl PartialEq for DynBitSet {
+    fn eq(&self, other: &Self) -> bool {
+        let len = self.words.len().max(other.words.len());
+        (0..len).all(|i| self.words.get(i).unwrap_or(&0) == other.words.get(i).unwrap_or(&0))
+    }
+}
+
+impl Eq for DynBitSet {}
+
+impl FromIterator<usize> for DynBitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl FromIterator<RangeInclusive<u32>> for DynBitSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u32>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert_range(range);
+        }
+        set
+    }
+}
+
+impl<'a> IntoIterator for &'a DynBitSet {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl BitAnd for &DynBitSet {
+    type Output = DynBitSet;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for &DynBitSet {
+    type Output = DynBitSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl Sub for &DynBitSet {
+    type Output = DynBitSet;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = DynBitSet::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert(3));
+        assert!(set.insert(1000));
+        assert!(!set.insert(1000));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(1000));
+        assert!(!set.contains(999));
+        assert!(!set.contains(100_000));
+
+        assert!(set.remove(1000));
+        assert!(!set.remove(100_000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn equality_ignores_capacity() {
+        let mut a = DynBitSet::with_capacity(1000);
+        a.insert(5);
+        let b: DynBitSet = [5].into_iter().collect();
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: DynBitSet = [1, 2, 3, 500].into_iter().collect();
+        let b: DynBitSet = [2, 3, 4].into_iter().collect();
+
+        assert_eq!((&a | &b).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 500]);
+        assert_eq!((&b | &a).iter().collect::<Vec<_>>(), vec![1, 2, 3, 4, 500]);
+        assert_eq!((&a & &b).iter().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!((&a - &b).iter().collect::<Vec<_>>(), vec![1, 500]);
+        assert_eq!((&b - &a).iter().collect::<Vec<_>>(), vec![4]);
+    }
+
+    #[test]
+    fn ranges_round_trip() {
+        // Overlapping and adjacent ranges merge.
+        let set: DynBitSet = [10..=20, 15..=30, 31..=40, 1000..=1000]
+            .into_iter()
+            .collect();
+        assert_eq!(set.len(), 31 + 1);
+        assert_eq!(set.ranges().collect::<Vec<_>>(), vec![10..=40, 1000..=1000]);
+    }
+}
@@ -0,0 +1,232 @@
+use std::ops::{BitAnd, BitOr, RangeInclusive, Sub};
+
+use crate::{count_ones, ranges, word_and_mask, Iter, Ranges, WORD_BITS};
+
+// Set of integers in `0..WORDS * 64` stored inline.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BitSet<const WORDS: usize> {
+    words: [u64; WORDS],
+}
+
+pub type BitSet64 = BitSet<1>;
+pub type BitSet128 = BitSet<2>;
+pub type BitSet256 = BitSet<4>;
+
+impl<const WORDS: usize> Default for BitSet<WORDS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WORDS: usize> BitSet<WORDS> {
+    pub const CAPACITY: usize = WORDS * WORD_BITS;
+
+    pub const fn new() -> Self {
+        Self { words: [0; WORDS] }
+    }
+
+    // Adds `value` to the set, returning whether it was newly inserted.
+    //
+    // Panics if `value` is not less than `CAPACITY`.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < Self::CAPACITY, "{value} out of range for BitSet");
+        let (word, mask) = word_and_mask(value);
+        let inserted = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        inserted
+    }
+
+    // Removes `value` from the set, returning whether it was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = word_and_mask(value);
+        let removed = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        removed
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        if value >= Self::CAPACITY {
+            return false;
+        }
+        let (word, mask) = word_and_mask(value);
+        self.words[word] & mask != 0
+    }
+
+    // Adds every value in `range`.
+    //
+    // Panics if the range extends past `CAPACITY`.
+    pub fn insert_range(&mut self, range: RangeInclusive<u32>) {
+        assert!(
+            range.is_empty() || (*range.end() as usize) < Self::CAPACITY,
+            "{range:?} out of range for BitSet"
+        );
+        ranges::fill(&mut self.words, &range);
+    }
+
+    pub fn clear(&mut self) {
+        self.words = [0; WORDS];
+    }
+
+    pub fn len(&self) -> usize {
+        count_ones(&self.words)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&word| word == 0)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn iter(&self) -> Iter<'_> {
+        Iter::new(&self.words)
+    }
+
+    // The set as the minimal list of disjoint ranges, in ascending order.
+    pub fn ranges(&self) -> Ranges<'_> {
+        Ranges::new(&self.words)
+    }
+
+    pub fn words(&self) -> &[u64; WORDS] {
+        &self.words
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let mut words = [0; WORDS];
+        for (i, word) in words.iter_mut().enumerate() {
+            *word = f(self.words[i], other.words[i]);
+        }
+        Self { words }
+    }
+}
+
+impl<const WORDS: usize> FromIterator<usize> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<const WORDS: usize> FromIterator<RangeInclusive<u32>> for BitSet<WORDS> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<u32>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert_range(range);
+        }
+        set
+    }
+}
+
+impl<'a, const WORDS: usize> IntoIterator for &'a BitSet<WORDS> {
+    type Item = usize;
+    type IntoIter = Iter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<const WORDS: usize> BitAnd for BitSet<WORDS> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
+
+impl<const WORDS: usize> BitOr for BitSet<WORDS> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl<const WORDS: usize> Sub for BitSet<WORDS> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut set = BitSet128::new();
+        assert!(set.is_empty());
+
+        assert!(set.insert(3));
+        assert!(set.insert(64));
+        assert!(set.insert(127));
+        assert!(!set.insert(3));
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(64));
+        assert!(!set.contains(65));
+        assert!(!set.contains(1000));
+
+        assert!(set.remove(64));
+        assert!(!set.remove(64));
+        assert!(!set.remove(1000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![3, 127]);
+
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_range() {
+        BitSet64::new().insert(64);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: BitSet256 = [1, 2, 3, 100, 200].into_iter().collect();
+        let b: BitSet256 = [2, 3, 4, 200, 255].into_iter().collect();
+
+        assert_eq!(
+            (a | b).iter().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 100, 200, 255]
+        );
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![2, 3, 200]);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![1, 100]);
+        assert_eq!(a.intersection(&b).len(), 3);
+    }
+
+    #[test]
+    fn ranges_round_trip() {
+        let set: BitSet128 = [2..=4, 60..=70, 6..=6].into_iter().collect();
+        assert_eq!(set.len(), 3 + 11 + 1);
+        assert!(set.contains(64));
+        assert!(!set.contains(5));
+        assert_eq!(
+            set.ranges().collect::<Vec<_>>(),
+            vec![2..=4, 6..=6, 60..=70]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_range_out_of_range() {
+        BitSet64::new().insert_range(60..=64);
+    }
+}
@@ -0,0 +1,57 @@
+// Compact sets of small unsigned integers backed by `u64` words.
+//
+// `BitSet` has a fixed capacity chosen at compile time and lives entirely on
+// the stack, making it suitable for hot loops.  `DynBitSet` grows on demand.
+
+mod dynamic;
+mod fixed;
+mod ranges;
+
+pub use dynamic::DynBitSet;
+pub use fixed::{BitSet, BitSet128, BitSet256, BitSet64};
+pub use ranges::Ranges;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+// Iterator over the indices of set bits in a slice of words.
+pub struct Iter<'a> {
+    words: &'a [u64],
+    // Index of the word currently held in `current`.
+    index: usize,
+    current: u64,
+}
+
+impl<'a> Iter<'a> {
+    fn new(words: &'a [u64]) -> Self {
+        Self {
+            words,
+            index: 0,
+            current: words.first().copied().unwrap_or(0),
+        }
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current == 0 {
+            self.index += 1;
+            self.current = *self.words.get(self.index)?;
+        }
+
+        let bit = self.current.trailing_zeros() as usize;
+        // Clear the lowest set bit.
+        self.current &= self.current - 1;
+
+        Some(self.index * WORD_BITS + bit)
+    }
+}
+
+fn word_and_mask(value: usize) -> (usize, u64) {
+    (value / WORD_BITS, 1 << (value % WORD_BITS))
+}
+
+fn count_ones(words: &[u64]) -> usize {
+    words.iter().map(|word| word.count_ones() as usize).sum()
+}
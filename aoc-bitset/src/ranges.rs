@@ -0,0 +1,137 @@
+// Conversions between bitsets and lists of inclusive ranges.
+//
+// Range lists are compact when values form long runs, bitsets are fast to
+// combine.  These let a problem switch representation (or check one against
+// the other) without walking every value by hand.
+
+use std::ops::RangeInclusive;
+
+use crate::WORD_BITS;
+
+// Iterator over the maximal runs of set bits in a slice of words, in
+// ascending order.
+pub struct Ranges<'a> {
+    words: &'a [u64],
+    // First bit not yet examined.
+    position: usize,
+}
+
+impl<'a> Ranges<'a> {
+    pub(crate) fn new(words: &'a [u64]) -> Self {
+        Self { words, position: 0 }
+    }
+
+    // Index of the first bit at or after `from` which is set (or clear when
+    // `set` is false).  Clear bits continue forever past the last word.
+    fn next_bit(&self, from: usize, set: bool) -> Option<usize> {
+        let mut index = from / WORD_BITS;
+        let word = |index: usize| {
+            let word = self.words[index];
+            if set {
+                word
+            } else {
+                !word
+            }
+        };
+
+        if index >= self.words.len() {
+            return (!set).then_some(from);
+        }
+        let mut current = word(index) & (!0 << (from % WORD_BITS));
+        while current == 0 {
+            index += 1;
+            if index >= self.words.len() {
+                return (!set).then_some(index * WORD_BITS);
+            }
+            current = word(index);
+        }
+
+        Some(index * WORD_BITS + current.trailing_zeros() as usize)
+    }
+}
+
+impl Iterator for Ranges<'_> {
+    type Item = RangeInclusive<u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.next_bit(self.position, true)?;
+        // There is always a clear bit eventually.
+        let end = self.next_bit(start, false)?;
+        self.position = end;
+
+        Some(to_u32(start)..=to_u32(end - 1))
+    }
+}
+
+fn to_u32(value: usize) -> u32 {
+    u32::try_from(value).expect("bitset value does not fit in a u32")
+}
+
+// Set every bit of `range` in `words`, which must be long enough to hold it.
+pub(crate) fn fill(words: &mut [u64], range: &RangeInclusive<u32>) {
+    if range.is_empty() {
+        return;
+    }
+    let start = *range.start() as usize;
+    let end = *range.end() as usize;
+    let (first, last) = (start / WORD_BITS, end / WORD_BITS);
+
+    // Masks covering `start..` within the first word and `..=end` within the
+    // last.
+    let head = !0u64 << (start % WORD_BITS);
+    let tail = !0u64 >> (WORD_BITS - 1 - end % WORD_BITS);
+
+    if first == last {
+        words[first] |= head & tail;
+        return;
+    }
+    words[first] |= head;
+    for word in &mut words[first + 1..last] {
+        *word = !0;
+    }
+    words[last] |= tail;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_within_word() {
+        let mut words = [0u64; 2];
+        fill(&mut words, &(3..=5));
+        assert_eq!(words, [0b111000, 0]);
+    }
+
+    #[test]
+    fn fill_across_words() {
+        let mut words = [0u64; 3];
+        fill(&mut words, &(62..=129));
+        assert_eq!(words, [0b11 << 62, !0, 0b11]);
+    }
+
+    #[test]
+    fn fill_empty() {
+        let mut words = [0u64; 1];
+        #[allow(clippy::reversed_empty_ranges)]
+        fill(&mut words, &(5..=4));
+        assert_eq!(words, [0]);
+    }
+
+    #[test]
+    fn ranges() {
+        let words = [0b1110_0001u64 | 1 << 63, !0, 1];
+        assert_eq!(
+            Ranges::new(&words).collect::<Vec<_>>(),
+            vec![0..=0, 5..=7, 63..=128]
+        );
+        assert_eq!(Ranges::new(&[0, 0]).next(), None);
+        assert_eq!(Ranges::new(&[]).next(), None);
+    }
+
+    #[test]
+    fn full_final_word() {
+        let words = [0, !0];
+        assert_eq!(Ranges::new(&words).collect::<Vec<_>>(), vec![64..=127]);
+    }
+}
@@ -0,0 +1,139 @@
+use std::{fs, path::PathBuf, time::Instant};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Args;
+use env_logger::Env;
+use log::info;
+
+// Command line arguments understood by every day.  Embed in a day's own
+// arguments with `#[command(flatten)]`.
+#[derive(Debug, Args)]
+pub struct CommonArgs {
+    /// Puzzle input file
+    #[arg(required_unless_present = "example")]
+    pub input: Option<PathBuf>,
+
+    /// Only run the given part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Use the example from the puzzle description instead of an input file
+    #[arg(long)]
+    pub example: bool,
+
+    /// Log how long each part takes
+    #[arg(long)]
+    pub time: bool,
+
+    /// Log filter, e.g. "debug" (defaults to $RUST_LOG, then "info")
+    #[arg(long)]
+    pub log_level: Option<String>,
+}
+
+impl CommonArgs {
+    pub fn init_logging(&self) {
+        let mut builder = match &self.log_level {
+            Some(filter) => {
+                let mut builder = env_logger::Builder::new();
+                builder.parse_filters(filter);
+                builder
+            }
+            None => env_logger::Builder::from_env(Env::default().default_filter_or("info")),
+        };
+        builder.init();
+    }
+
+    // Read the input file, or return `example` when `--example` was given.
+    pub fn read_input(&self, example: &str) -> Result<String> {
+        match (&self.input, self.example) {
+            (_, true) => Ok(example.to_owned()),
+            (Some(path), false) => fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display())),
+            (None, false) => Err(anyhow!("No input file given")),
+        }
+    }
+
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    // Run `solve` if `part` was selected, timing it when `--time` was given.
+    //
+    // Returns `None` when the part was skipped.
+    pub fn run_part<T>(&self, part: u8, solve: impl FnOnce() -> Result<T>) -> Result<Option<T>> {
+        if !self.runs_part(part) {
+            return Ok(None);
+        }
+
+        let start = Instant::now();
+        let answer = solve()?;
+        if self.time {
+            info!("[Part {part}] Took {:?}", start.elapsed());
+        }
+
+        Ok(Some(answer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct TestArgs {
+        #[command(flatten)]
+        common: CommonArgs,
+    }
+
+    fn parse(args: &[&str]) -> Result<CommonArgs, clap::Error> {
+        TestArgs::try_parse_from(["test"].iter().chain(args)).map(|args| args.common)
+    }
+
+    #[test]
+    fn defaults() {
+        let args = parse(&["input.txt"]).unwrap();
+        assert_eq!(args.input, Some(PathBuf::from("input.txt")));
+        assert_eq!(args.part, None);
+        assert!(!args.example);
+        assert!(!args.time);
+        assert_eq!(args.log_level, None);
+        assert!(args.runs_part(1));
+        assert!(args.runs_part(2));
+    }
+
+    #[test]
+    fn flags() {
+        let args = parse(&["--part", "2", "--time", "--log-level", "debug", "in"]).unwrap();
+        assert_eq!(args.part, Some(2));
+        assert!(args.time);
+        assert_eq!(args.log_level.as_deref(), Some("debug"));
+        assert!(!args.runs_part(1));
+        assert!(args.runs_part(2));
+    }
+
+    #[test]
+    fn input_required_without_example() {
+        assert!(parse(&[]).is_err());
+
+        let args = parse(&["--example"]).unwrap();
+        assert_eq!(args.read_input("example").unwrap(), "example");
+    }
+
+    #[test]
+    fn part_out_of_range() {
+        assert!(parse(&["--part", "3", "input.txt"]).is_err());
+    }
+
+    #[test]
+    fn run_part() {
+        let args = parse(&["--part", "1", "input.txt"]).unwrap();
+        assert_eq!(args.run_part(1, || Ok(5)).unwrap(), Some(5));
+        assert_eq!(
+            args.run_part(2, || -> Result<()> { panic!("part 2 ran") })
+                .unwrap(),
+            None
+        );
+    }
+}
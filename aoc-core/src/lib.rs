@@ -0,0 +1,5 @@
+// Pieces shared by every day's binary.
+
+mod args;
+
+pub use args::CommonArgs;
@@ -0,0 +1,7 @@
+// Geometry types shared between days.
+
+mod point3;
+mod voxel;
+
+pub use point3::{Point3, Vec3};
+pub use voxel::{BoundingBox, VoxelSet};
@@ -0,0 +1,182 @@
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Error, Result};
+
+// Integer point (or displacement) in 3D space.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Point3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+// Points and displacements share a representation.  The alias documents
+// which one is meant.
+pub type Vec3 = Point3;
+
+impl Point3 {
+    pub const ORIGIN: Self = Self::new(0, 0, 0);
+
+    // Unit vectors along each axis in both directions.
+    pub const FACE_DIRECTIONS: [Vec3; 6] = [
+        Self::new(1, 0, 0),
+        Self::new(-1, 0, 0),
+        Self::new(0, 1, 0),
+        Self::new(0, -1, 0),
+        Self::new(0, 0, 1),
+        Self::new(0, 0, -1),
+    ];
+
+    pub const fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    // The six points sharing a face with this one.
+    pub fn neighbors(self) -> impl Iterator<Item = Point3> {
+        Self::FACE_DIRECTIONS
+            .into_iter()
+            .map(move |direction| self + direction)
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    pub fn min(self, other: Self) -> Self {
+        Self::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    pub fn max(self, other: Self) -> Self {
+        Self::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl Add for Point3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i32> for Point3 {
+    type Output = Self;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+// Parses the `x,y,z` format used by puzzle inputs.
+impl FromStr for Point3 {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords = s
+            .split(',')
+            .map(|coord| {
+                coord
+                    .trim()
+                    .parse()
+                    .map_err(|e| anyhow!("invalid coordinate '{}' in '{}': {}", coord, s, e))
+            })
+            .collect::<Result<Vec<i32>>>()?;
+        match coords[..] {
+            [x, y, z] => Ok(Self::new(x, y, z)),
+            _ => Err(anyhow!(
+                "'{}' does not contain exactly three coordinates",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("2,2,2".parse::<Point3>().unwrap(), Point3::new(2, 2, 2));
+        assert_eq!(
+            "-1, 0,13".parse::<Point3>().unwrap(),
+            Point3::new(-1, 0, 13)
+        );
+        assert!("1,2".parse::<Point3>().is_err());
+        assert!("1,2,3,4".parse::<Point3>().is_err());
+        assert!("1,a,3".parse::<Point3>().is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Point3::new(1, -2, 3).to_string(), "1,-2,3");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(-1, 5, 0);
+        assert_eq!(a + b, Point3::new(0, 7, 3));
+        assert_eq!(a - b, Point3::new(2, -3, 3));
+        assert_eq!(-a, Point3::new(-1, -2, -3));
+        assert_eq!(a * 2, Point3::new(2, 4, 6));
+        assert_eq!(a.manhattan_distance(b), 8);
+        assert_eq!(a.min(b), Point3::new(-1, 2, 0));
+        assert_eq!(a.max(b), Point3::new(1, 5, 3));
+    }
+
+    #[test]
+    fn neighbors() {
+        let neighbors: Vec<_> = Point3::ORIGIN.neighbors().collect();
+        assert_eq!(neighbors.len(), 6);
+        assert!(neighbors
+            .iter()
+            .all(|neighbor| neighbor.manhattan_distance(Point3::ORIGIN) == 1));
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use crate::Point3;
+
+// Axis aligned box containing every point from `min` to `max` inclusive.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BoundingBox {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl BoundingBox {
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    // Grow the box by `amount` in every direction.
+    pub fn expand(&self, amount: i32) -> Self {
+        let delta = Point3::new(amount, amount, amount);
+        Self {
+            min: self.min - delta,
+            max: self.max + delta,
+        }
+    }
+
+    pub fn volume(&self) -> u64 {
+        let size = self.max - self.min;
+        (size.x as u64 + 1) * (size.y as u64 + 1) * (size.z as u64 + 1)
+    }
+}
+
+// Sparse set of unit cubes identified by their integer coordinates.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VoxelSet {
+    voxels: HashSet<Point3>,
+}
+
+impl VoxelSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, voxel: Point3) -> bool {
+        self.voxels.insert(voxel)
+    }
+
+    pub fn remove(&mut self, voxel: Point3) -> bool {
+        self.voxels.remove(&voxel)
+    }
+
+    pub fn contains(&self, voxel: Point3) -> bool {
+        self.voxels.contains(&voxel)
+    }
+
+    pub fn len(&self) -> usize {
+        self.voxels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.voxels.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Point3> + '_ {
+        self.voxels.iter().copied()
+    }
+
+    // Face neighbors of `voxel` that are in the set.
+    pub fn occupied_neighbors(&self, voxel: Point3) -> impl Iterator<Item = Point3> + '_ {
+        voxel
+            .neighbors()
+            .filter(move |neighbor| self.contains(*neighbor))
+    }
+
+    // Face neighbors of `voxel` that are not in the set.
+    pub fn empty_neighbors(&self, voxel: Point3) -> impl Iterator<Item = Point3> + '_ {
+        voxel
+            .neighbors()
+            .filter(move |neighbor| !self.contains(*neighbor))
+    }
+
+    // Smallest box containing every voxel, or `None` if the set is empty.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut voxels = self.iter();
+        let first = voxels.next()?;
+        Some(voxels.fold(
+            BoundingBox {
+                min: first,
+                max: first,
+            },
+            |bounds, voxel| BoundingBox {
+                min: bounds.min.min(voxel),
+                max: bounds.max.max(voxel),
+            },
+        ))
+    }
+
+    // Number of voxel faces not shared with another voxel in the set.
+    pub fn exposed_faces(&self) -> usize {
+        self.iter()
+            .map(|voxel| self.empty_neighbors(voxel).count())
+            .sum()
+    }
+}
+
+impl FromIterator<Point3> for VoxelSet {
+    fn from_iter<I: IntoIterator<Item = Point3>>(iter: I) -> Self {
+        Self {
+            voxels: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The larger example from day 18.
+    const EXAMPLE: &str =
+        "2,2,2\n1,2,2\n3,2,2\n2,1,2\n2,3,2\n2,2,1\n2,2,3\n2,2,4\n2,2,6\n1,2,5\n3,2,5\n2,1,5\n2,3,5";
+
+    fn example() -> VoxelSet {
+        EXAMPLE.lines().map(|line| line.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn exposed_faces() {
+        let voxels: VoxelSet = [Point3::new(1, 1, 1), Point3::new(2, 1, 1)]
+            .into_iter()
+            .collect();
+        assert_eq!(voxels.exposed_faces(), 10);
+        assert_eq!(example().exposed_faces(), 64);
+    }
+
+    #[test]
+    fn neighbors() {
+        let voxels = example();
+        assert_eq!(voxels.occupied_neighbors(Point3::new(2, 2, 2)).count(), 6);
+        assert_eq!(voxels.empty_neighbors(Point3::new(2, 2, 6)).count(), 6);
+        assert_eq!(voxels.occupied_neighbors(Point3::new(2, 2, 4)).count(), 1);
+    }
+
+    #[test]
+    fn bounding_box() {
+        assert_eq!(VoxelSet::new().bounding_box(), None);
+
+        let bounds = example().bounding_box().unwrap();
+        assert_eq!(
+            bounds,
+            BoundingBox {
+                min: Point3::new(1, 1, 1),
+                max: Point3::new(3, 3, 6),
+            }
+        );
+        assert_eq!(bounds.volume(), 54);
+        assert!(bounds.contains(Point3::new(3, 1, 6)));
+        assert!(!bounds.contains(Point3::new(0, 1, 6)));
+        assert!(bounds.expand(1).contains(Point3::new(0, 0, 7)));
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut voxels = VoxelSet::new();
+        assert!(voxels.is_empty());
+        assert!(voxels.insert(Point3::ORIGIN));
+        assert!(!voxels.insert(Point3::ORIGIN));
+        assert_eq!(voxels.len(), 1);
+        assert!(voxels.remove(Point3::ORIGIN));
+        assert!(!voxels.contains(Point3::ORIGIN));
+    }
+}
@@ -0,0 +1,302 @@
+// Operations on inclusive integer ranges.
+//
+// `Overlap` and `ContainsRange` work for any ordered type.  The remaining
+// operations need to step to the next or previous value and are therefore
+// limited to `Discrete` types.
+
+use std::{cmp, ops::RangeInclusive};
+
+mod merge;
+mod set;
+
+pub use merge::{gaps, merge, merge_sorted};
+pub use set::IntervalSet;
+
+pub trait Overlap<T: PartialOrd + Ord + Clone> {
+    fn overlap(&self, range: &RangeInclusive<T>) -> RangeInclusive<T>;
+}
+
+impl<T: PartialOrd + Ord + Clone> Overlap<T> for RangeInclusive<T> {
+    fn overlap(&self, range: &RangeInclusive<T>) -> RangeInclusive<T> {
+        let start = cmp::max(self.start(), range.start());
+        let end = cmp::min(self.end(), range.end());
+
+        start.clone()..=end.clone()
+    }
+}
+
+pub trait ContainsRange<T: PartialOrd> {
+    fn contains_range(&self, range: &RangeInclusive<T>) -> bool;
+}
+
+impl<T: PartialOrd> ContainsRange<T> for RangeInclusive<T> {
+    fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
+        self.contains(range.start()) && self.contains(range.end())
+    }
+}
+
+// Integer-like types with well defined neighboring values.
+pub trait Discrete: Ord + Copy {
+    fn succ(self) -> Self;
+    fn pred(self) -> Self;
+
+    // Number of values in `start..=end`, which must not be empty.
+    fn span(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_discrete {
+    ($($t:ty),*) => {
+        $(
+            impl Discrete for $t {
+                fn succ(self) -> Self {
+                    self + 1
+                }
+
+                fn pred(self) -> Self {
+                    self - 1
+                }
+
+                fn span(start: Self, end: Self) -> u128 {
+                    (end as i128 - start as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_discrete!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+// Set operations on discrete inclusive ranges.
+//
+// Empty ranges (where start > end) are treated as containing no values and
+// are never returned.
+pub trait Interval<T: Discrete> {
+    fn intersection(&self, range: &RangeInclusive<T>) -> Option<RangeInclusive<T>>;
+
+    // Returns a single range if the two ranges overlap or are adjacent,
+    // otherwise both ranges in sorted order.
+    fn union(&self, range: &RangeInclusive<T>) -> Vec<RangeInclusive<T>>;
+
+    // Values in `self` that are not in `range`.  This may split `self` in
+    // two.
+    fn subtract(&self, range: &RangeInclusive<T>) -> Vec<RangeInclusive<T>>;
+
+    // Values strictly between the two ranges, if any.
+    fn gap(&self, range: &RangeInclusive<T>) -> Option<RangeInclusive<T>>;
+
+    // Whether the ranges overlap or are adjacent, so that their union is a
+    // single range.
+    fn touches(&self, range: &RangeInclusive<T>) -> bool;
+}
+
+impl<T: Discrete> Interval<T> for RangeInclusive<T> {
+    fn intersection(&self, range: &RangeInclusive<T>) -> Option<RangeInclusive<T>> {
+        let overlap = self.overlap(range);
+        (!overlap.is_empty()).then_some(overlap)
+    }
+
+    fn union(&self, range: &RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        match (self.is_empty(), range.is_empty()) {
+            (true, true) => return vec![],
+            (true, false) => return vec![range.clone()],
+            (false, true) => return vec![self.clone()],
+            (false, false) => (),
+        }
+
+        let (first, second) = if self.start() <= range.start() {
+            (self, range)
+        } else {
+            (range, self)
+        };
+
+        // `first.end() < T::MAX` is implied when the ranges are disjoint, so
+        // `succ()` can't overflow here.
+        if first.end() >= second.start() || first.end().succ() == *second.start() {
+            vec![*first.start()..=*cmp::max(first.end(), second.end())]
+        } else {
+            vec![first.clone(), second.clone()]
+        }
+    }
+
+    fn subtract(&self, range: &RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        if self.intersection(range).is_none() {
+            return if self.is_empty() {
+                vec![]
+            } else {
+                vec![self.clone()]
+            };
+        }
+
+        let mut ranges = Vec::new();
+        if self.start() < range.start() {
+            ranges.push(*self.start()..=range.start().pred());
+        }
+        if self.end() > range.end() {
+            ranges.push(range.end().succ()..=*self.end());
+        }
+
+        ranges
+    }
+
+    fn gap(&self, range: &RangeInclusive<T>) -> Option<RangeInclusive<T>> {
+        if self.is_empty() || range.is_empty() {
+            return None;
+        }
+
+        let (first, second) = if self.start() <= range.start() {
+            (self, range)
+        } else {
+            (range, self)
+        };
+
+        if first.end() >= second.start() {
+            return None;
+        }
+        let gap = first.end().succ()..=second.start().pred();
+        (!gap.is_empty()).then_some(gap)
+    }
+
+    fn touches(&self, range: &RangeInclusive<T>) -> bool {
+        !self.is_empty() && !range.is_empty() && self.gap(range).is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    #[test]
+    fn overlap() {
+        assert_eq!((2..=4).overlap(&(3..=8)), 3..=4);
+        assert!((2..=4).overlap(&(6..=8)).is_empty());
+    }
+
+    #[test]
+    fn contains_range() {
+        assert!((2..=8).contains_range(&(3..=7)));
+        assert!((4..=6).contains_range(&(4..=6)));
+        assert!(!(2..=6).contains_range(&(4..=8)));
+    }
+
+    #[test]
+    fn intersection() {
+        assert_eq!((2..=4).intersection(&(3..=8)), Some(3..=4));
+        assert_eq!((5..=7).intersection(&(7..=9)), Some(7..=7));
+        assert_eq!((2..=4).intersection(&(5..=8)), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 5..=4;
+        assert_eq!((2..=8).intersection(&empty), None);
+    }
+
+    #[test]
+    fn union() {
+        assert_eq!((2..=4).union(&(3..=8)), vec![2..=8]);
+        assert_eq!((6..=8).union(&(2..=4)), vec![2..=4, 6..=8]);
+        // Adjacent ranges merge.
+        assert_eq!((5..=8).union(&(2..=4)), vec![2..=8]);
+        assert_eq!((2..=8).union(&(3..=4)), vec![2..=8]);
+        assert_eq!((0..=u32::MAX).union(&(3..=4)), vec![0..=u32::MAX]);
+    }
+
+    #[test]
+    fn subtract() {
+        assert_eq!((2..=8).subtract(&(4..=5)), vec![2..=3, 6..=8]);
+        assert_eq!((2..=8).subtract(&(0..=5)), vec![6..=8]);
+        assert_eq!((2..=8).subtract(&(5..=10)), vec![2..=4]);
+        assert_eq!((2..=8).subtract(&(2..=8)), vec![]);
+        assert_eq!((2..=8).subtract(&(10..=12)), vec![2..=8]);
+        assert_eq!((0..=u32::MAX).subtract(&(0..=u32::MAX)), vec![]);
+    }
+
+    #[test]
+    fn gap() {
+        assert_eq!((2..=4).gap(&(8..=9)), Some(5..=7));
+        assert_eq!((8..=9).gap(&(2..=4)), Some(5..=7));
+        assert_eq!((2..=4).gap(&(5..=9)), None);
+        assert_eq!((2..=4).gap(&(3..=9)), None);
+        assert_eq!((-4..=-2).gap(&(2..=4)), Some(-1..=1));
+    }
+
+    #[test]
+    fn touches() {
+        assert!((2..=4).touches(&(3..=8)));
+        assert!((2..=4).touches(&(5..=8)));
+        assert!((5..=8).touches(&(2..=4)));
+        assert!(!(2..=4).touches(&(6..=8)));
+        assert!((0..=u32::MAX).touches(&(u32::MAX..=u32::MAX)));
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 5..=4;
+        assert!(!(2..=8).touches(&empty));
+    }
+
+    // Values of `range` within the tested domain.
+    fn values(range: &RangeInclusive<i8>) -> BTreeSet<i8> {
+        range.clone().collect()
+    }
+
+    fn values_of(ranges: &[RangeInclusive<i8>]) -> BTreeSet<i8> {
+        ranges.iter().flat_map(values).collect()
+    }
+
+    // Check every operation against set operations on the values of every
+    // pair of ranges, including empty ones, over a small domain.
+    #[test]
+    fn exhaustive() {
+        const DOMAIN: RangeInclusive<i8> = -3..=4;
+        let ranges: Vec<_> = DOMAIN
+            .flat_map(|start| DOMAIN.map(move |end| start..=end))
+            .collect();
+
+        for a in &ranges {
+            for b in &ranges {
+                let (a_values, b_values) = (values(a), values(b));
+                let context = format!("{a:?} and {b:?}");
+
+                let overlap = a.overlap(b);
+                assert_eq!(values(&overlap), &a_values & &b_values, "{context}");
+                let intersection = a.intersection(b);
+                assert_eq!(
+                    intersection.as_ref().map(values).unwrap_or_default(),
+                    &a_values & &b_values,
+                    "{context}"
+                );
+                if !b.is_empty() {
+                    assert_eq!(
+                        a.contains_range(b),
+                        b_values.is_subset(&a_values),
+                        "{context}"
+                    );
+                }
+
+                let union = a.union(b);
+                assert_eq!(values_of(&union), &a_values | &b_values, "{context}");
+                assert!(union.iter().all(|range| !range.is_empty()), "{context}");
+                assert!(union.windows(2).all(|pair| pair[0].gap(&pair[1]).is_some()));
+
+                let difference = a.subtract(b);
+                assert_eq!(values_of(&difference), &a_values - &b_values, "{context}");
+                assert!(difference.len() <= 2, "{context}");
+                assert!(difference.iter().all(|range| !range.is_empty()));
+
+                // The gap is every value between the two non-empty ranges.
+                let between: BTreeSet<_> = if a.is_empty() || b.is_empty() {
+                    BTreeSet::new()
+                } else {
+                    (cmp::min(a.end(), b.end()) + 1..*cmp::max(a.start(), b.start())).collect()
+                };
+                assert_eq!(
+                    a.gap(b).as_ref().map(values).unwrap_or_default(),
+                    between,
+                    "{context}"
+                );
+                assert_eq!(
+                    a.touches(b),
+                    union.len() == 1 && !a.is_empty() && !b.is_empty(),
+                    "{context}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::{cmp, ops::RangeInclusive};
+
+use crate::{Discrete, Interval};
+
+// Merge ranges that are already sorted by start into a list of disjoint,
+// non-adjacent ranges.  Empty ranges are dropped.
+pub fn merge_sorted<T: Discrete>(
+    ranges: impl IntoIterator<Item = RangeInclusive<T>>,
+) -> Vec<RangeInclusive<T>> {
+    let mut merged: Vec<RangeInclusive<T>> = Vec::new();
+    for range in ranges.into_iter().filter(|range| !range.is_empty()) {
+        match merged.last_mut() {
+            Some(last) if last.gap(&range).is_none() => {
+                debug_assert!(last.start() <= range.start(), "ranges are not sorted");
+                *last = *last.start()..=*cmp::max(last.end(), range.end());
+            }
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+// Merge ranges in any order into a sorted list of disjoint, non-adjacent
+// ranges.
+pub fn merge<T: Discrete>(
+    ranges: impl IntoIterator<Item = RangeInclusive<T>>,
+) -> Vec<RangeInclusive<T>> {
+    let mut ranges: Vec<_> = ranges.into_iter().collect();
+    ranges.sort_by_key(|range| *range.start());
+    merge_sorted(ranges)
+}
+
+// Gaps between consecutive ranges of a merged list.
+pub fn gaps<T: Discrete>(merged: &[RangeInclusive<T>]) -> Vec<RangeInclusive<T>> {
+    merged
+        .windows(2)
+        .filter_map(|pair| pair[0].gap(&pair[1]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_sorted() {
+        assert_eq!(
+            merge_sorted([1..=3, 2..=5, 6..=7, 9..=10, 10..=12]),
+            vec![1..=7, 9..=12]
+        );
+        assert_eq!(merge_sorted([1..=10, 2..=3, 4..=5]), vec![1..=10]);
+        assert_eq!(merge_sorted(Vec::<RangeInclusive<u32>>::new()), vec![]);
+    }
+
+    #[test]
+    fn test_merge() {
+        assert_eq!(
+            merge([9..=10, 2..=5, 1..=3, 10..=12, 6..=7]),
+            vec![1..=7, 9..=12]
+        );
+    }
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(gaps(&merge([1..=3, 9..=10, 5..=6])), vec![4..=4, 7..=8]);
+        assert_eq!(gaps(&[1..=3]), vec![]);
+    }
+}
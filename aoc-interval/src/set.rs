@@ -0,0 +1,223 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use crate::{Discrete, Interval};
+
+// A set of values stored as sorted, disjoint, non-adjacent ranges.
+//
+// Ranges are keyed by their start in a `BTreeMap` so insertion, removal and
+// overlap queries cost O(log n) plus the number of ranges they touch.  The
+// total number of covered values is kept up to date incrementally.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet<T: Discrete> {
+    ranges: BTreeMap<T, T>,
+    covered: u128,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+            covered: 0,
+        }
+    }
+
+    // Number of disjoint ranges in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // Total number of values covered by the set.
+    pub fn covered_len(&self) -> u128 {
+        self.covered
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value <= end)
+    }
+
+    // Ranges in the set that share at least one value with `range`.
+    pub fn overlapping(
+        &self,
+        range: &RangeInclusive<T>,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        let (start, end) = (*range.start(), *range.end());
+        let empty = range.is_empty();
+
+        // The only range starting before `start` that can overlap is the
+        // last one.
+        let before = self
+            .ranges
+            .range(..start)
+            .next_back()
+            .filter(move |(_, &prev_end)| !empty && prev_end >= start);
+        let within = (!empty)
+            .then(|| self.ranges.range(start..=end))
+            .into_iter()
+            .flatten();
+
+        before
+            .into_iter()
+            .chain(within)
+            .map(|(&start, &end)| start..=end)
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Collect every range that overlaps or is adjacent to the new one.
+        // Those are the last range starting before it plus every range
+        // starting within or immediately after it.
+        let first = *range.start();
+        let mut merged = range;
+        let mut absorbed = Vec::new();
+        if let Some((&start, &end)) = self.ranges.range(..first).next_back() {
+            if (start..=end).gap(&merged).is_none() {
+                absorbed.push(start);
+                merged = start..=end.max(*merged.end());
+            }
+        }
+        for (&start, &end) in self.ranges.range(first..) {
+            if (start..=end).gap(&merged).is_some() {
+                break;
+            }
+            absorbed.push(start);
+            merged = *merged.start()..=end.max(*merged.end());
+        }
+
+        for start in absorbed {
+            let end = self.ranges.remove(&start).unwrap();
+            self.covered -= T::span(start, end);
+        }
+        self.covered += T::span(*merged.start(), *merged.end());
+        self.ranges.insert(*merged.start(), *merged.end());
+    }
+
+    pub fn remove(&mut self, range: &RangeInclusive<T>) {
+        let overlapping: Vec<_> = self.overlapping(range).collect();
+        for existing in overlapping {
+            self.ranges.remove(existing.start());
+            self.covered -= T::span(*existing.start(), *existing.end());
+            for remaining in existing.subtract(range) {
+                self.covered += T::span(*remaining.start(), *remaining.end());
+                self.ranges.insert(*remaining.start(), *remaining.end());
+            }
+        }
+    }
+}
+
+impl<T: Discrete> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet<i32>) -> Vec<RangeInclusive<i32>> {
+        set.iter().collect()
+    }
+
+    #[test]
+    fn insert() {
+        let mut set = IntervalSet::new();
+        assert!(set.is_empty());
+
+        set.insert(10..=20);
+        set.insert(30..=40);
+        assert_eq!(ranges(&set), vec![10..=20, 30..=40]);
+        assert_eq!(set.covered_len(), 22);
+
+        // Adjacent ranges are merged.
+        set.insert(21..=22);
+        assert_eq!(ranges(&set), vec![10..=22, 30..=40]);
+
+        // A range spanning several existing ranges absorbs them all.
+        set.insert(0..=35);
+        assert_eq!(ranges(&set), vec![0..=40]);
+        assert_eq!(set.covered_len(), 41);
+        assert_eq!(set.len(), 1);
+
+        // Ranges already covered change nothing.
+        set.insert(5..=6);
+        assert_eq!(ranges(&set), vec![0..=40]);
+        assert_eq!(set.covered_len(), 41);
+    }
+
+    #[test]
+    fn insert_extends_previous() {
+        let mut set: IntervalSet<i32> = [0..=5, 10..=15].into_iter().collect();
+        set.insert(3..=8);
+        assert_eq!(ranges(&set), vec![0..=8, 10..=15]);
+        assert_eq!(set.covered_len(), 15);
+    }
+
+    #[test]
+    fn contains() {
+        let set: IntervalSet<i32> = [0..=5, 10..=15].into_iter().collect();
+        assert!(set.contains(0));
+        assert!(set.contains(5));
+        assert!(!set.contains(6));
+        assert!(set.contains(12));
+        assert!(!set.contains(16));
+        assert!(!set.contains(-1));
+    }
+
+    #[test]
+    fn overlapping() {
+        let set: IntervalSet<i32> = [0..=5, 10..=15, 20..=25].into_iter().collect();
+        assert_eq!(
+            set.overlapping(&(3..=12)).collect::<Vec<_>>(),
+            vec![0..=5, 10..=15]
+        );
+        assert_eq!(set.overlapping(&(6..=9)).collect::<Vec<_>>(), vec![]);
+        assert_eq!(
+            set.overlapping(&(15..=20)).collect::<Vec<_>>(),
+            vec![10..=15, 20..=25]
+        );
+        assert_eq!(set.overlapping(&(-10..=100)).count(), 3);
+    }
+
+    #[test]
+    fn remove() {
+        let mut set: IntervalSet<i32> = [0..=10, 20..=30].into_iter().collect();
+        set.remove(&(5..=25));
+        assert_eq!(ranges(&set), vec![0..=4, 26..=30]);
+        assert_eq!(set.covered_len(), 10);
+
+        set.remove(&(2..=2));
+        assert_eq!(ranges(&set), vec![0..=1, 3..=4, 26..=30]);
+        assert_eq!(set.covered_len(), 9);
+    }
+
+    #[test]
+    fn full_range() {
+        let set: IntervalSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(set.covered_len(), u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn many_ranges() {
+        let set: IntervalSet<u32> = (0..100_000).map(|i| i * 3..=i * 3 + 1).collect();
+        assert_eq!(set.len(), 100_000);
+        assert_eq!(set.covered_len(), 200_000);
+    }
+}
@@ -0,0 +1,99 @@
+// Number theory helpers.
+
+mod modular;
+
+pub use modular::{crt, extended_gcd, mod_inverse, mod_pow};
+
+use std::ops::{Div, Mul, Rem};
+
+// Primitive integer types usable with `gcd` and `lcm`.
+pub trait Integer:
+    Copy + Ord + Rem<Output = Self> + Div<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+
+    fn abs(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    (unsigned: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+
+                fn abs(self) -> Self {
+                    self
+                }
+            }
+        )*
+    };
+    (signed: $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(unsigned: u8, u16, u32, u64, u128, usize);
+impl_integer!(signed: i8, i16, i32, i64, i128, isize);
+
+// Greatest common divisor.  The result is always non-negative and
+// `gcd(0, 0)` is 0.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+// Least common multiple.  `lcm(x, 0)` is 0.
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    // Divide before multiplying to reduce the chance of overflow.
+    (a / gcd(a, b) * b).abs()
+}
+
+// Least common multiple of every value, or `None` if there are none.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values.into_iter().reduce(lcm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(17u64, 5), 1);
+        assert_eq!(gcd(0u8, 5), 5);
+        assert_eq!(gcd(5u8, 0), 5);
+        assert_eq!(gcd(0i32, 0), 0);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(12i64, -18), 6);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4u32, 6), 12);
+        assert_eq!(lcm(7u64, 13), 91);
+        assert_eq!(lcm(0u32, 6), 0);
+        assert_eq!(lcm(-4i32, 6), 12);
+    }
+
+    #[test]
+    fn test_lcm_all() {
+        // The divisibility tests from the day 11 example.
+        assert_eq!(lcm_all([23u64, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm_all(Vec::<u64>::new()), None);
+    }
+}
@@ -0,0 +1,122 @@
+// Modular arithmetic on 64 bit integers.
+//
+// Intermediate products are computed in 128 bits so any 64 bit modulus is
+// supported without overflow.
+
+// `base.pow(exp) % modulus` computed by repeated squaring.
+pub fn mod_pow(base: u64, mut exp: u64, modulus: u64) -> u64 {
+    assert!(modulus > 0, "modulus must be positive");
+    let modulus = modulus as u128;
+    let mut base = base as u128 % modulus;
+    let mut result = 1 % modulus;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+// Returns `(g, x, y)` such that `a * x + b * y == g == gcd(a, b)`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (g, x, y) = extended_gcd_wide(a as i128, b as i128);
+    (g as i64, x as i64, y as i64)
+}
+
+fn extended_gcd_wide(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    (old_r, old_x, old_y)
+}
+
+// Multiplicative inverse of `a` modulo `modulus`, if `a` and `modulus` are
+// coprime.  The result is in `0..modulus`.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    assert!(modulus > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+// Solve the system of congruences `x ≡ residue (mod modulus)` for each
+// `(residue, modulus)` pair using the Chinese Remainder Theorem.
+//
+// Moduli need not be pairwise coprime.  Returns `(x, m)` where `x` is the
+// smallest non-negative solution and `m` the combined modulus (every
+// solution is `x + k * m`), or `None` if the congruences are inconsistent.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i128 = 0;
+    let mut m: i128 = 1;
+    for &(residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+        let (residue, modulus) = (residue as i128, modulus as i128);
+
+        // Find k such that x + k * m ≡ residue (mod modulus).
+        let (g, inverse, _) = extended_gcd_wide(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (diff / g % step * inverse).rem_euclid(step);
+        x += k * m;
+        m *= step;
+        x = x.rem_euclid(m);
+    }
+
+    Some((x.try_into().ok()?, m.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(3, 0, 7), 1);
+        assert_eq!(mod_pow(3, 0, 1), 0);
+        assert_eq!(mod_pow(u64::MAX, 2, u64::MAX - 1), 1);
+        // Fermat's little theorem.
+        assert_eq!(mod_pow(123456789, 1_000_000_006, 1_000_000_007), 1);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        let (g, x, y) = extended_gcd(-240, 46);
+        assert_eq!(g, 2);
+        assert_eq!(-240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(10, 17), Some(12));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(2, 4), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt(&[(0, 4), (2, 6)]), Some((8, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
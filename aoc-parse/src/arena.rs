@@ -0,0 +1,158 @@
+// Adapters for allocating nom parser output in a `Bump` arena.
+//
+// Parsers that build owned trees (e.g. day 7's per-entry `String` names)
+// spend much of their time in the global allocator.  Wrapping the pieces of
+// such a parser with these adapters moves those allocations into an arena
+// that is freed all at once when the parse result is dropped.
+//
+//     let bump = Bump::new();
+//     let (_, name) = alloc_str(&bump, alpha1)("abc def")?;
+//
+// The resulting values borrow from the arena rather than the input, so the
+// input can be discarded as soon as parsing finishes.
+
+pub use bumpalo::{collections::Vec as BumpVec, Bump};
+use nom::{IResult, Parser};
+
+// Run `parser` and move its output into `bump`.
+pub fn alloc<'b, I, O, E, P>(
+    bump: &'b Bump,
+    mut parser: P,
+) -> impl FnMut(I) -> IResult<I, &'b mut O, E>
+where
+    P: Parser<I, O, E>,
+{
+    move |input| {
+        let (input, value) = parser.parse(input)?;
+        Ok((input, bump.alloc(value)))
+    }
+}
+
+// Run a parser that recognizes a string slice and copy the slice into
+// `bump`, detaching it from the input's lifetime.
+pub fn alloc_str<'b, 'i, E, P>(
+    bump: &'b Bump,
+    mut parser: P,
+) -> impl FnMut(&'i str) -> IResult<&'i str, &'b str, E>
+where
+    P: Parser<&'i str, &'i str, E>,
+{
+    move |input| {
+        let (input, value) = parser.parse(input)?;
+        Ok((input, &*bump.alloc_str(value)))
+    }
+}
+
+// Like nom's `many0` but collects results into an arena backed `Vec`.
+//
+// Stops at the first error or when `parser` stops consuming input.
+pub fn many0_in<'b, I, O, E, P>(
+    bump: &'b Bump,
+    mut parser: P,
+) -> impl FnMut(I) -> IResult<I, BumpVec<'b, O>, E>
+where
+    I: Clone + nom::InputLength,
+    P: Parser<I, O, E>,
+{
+    move |mut input: I| {
+        let mut values = BumpVec::new_in(bump);
+        loop {
+            let len = input.input_len();
+            match parser.parse(input.clone()) {
+                Ok((rest, value)) => {
+                    if rest.input_len() == len {
+                        return Ok((input, values));
+                    }
+                    values.push(value);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((input, values)),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        character::complete::{alpha1, digit1, line_ending, space1},
+        combinator::{map_res, opt},
+        sequence::{separated_pair, terminated},
+    };
+
+    use super::*;
+
+    // A day 7 style directory listing entry with an arena allocated name.
+    #[derive(Debug, PartialEq)]
+    struct File<'b> {
+        name: &'b str,
+        size: u64,
+    }
+
+    fn parse_files<'b, 'i>(
+        bump: &'b Bump,
+        input: &'i str,
+    ) -> IResult<&'i str, BumpVec<'b, File<'b>>> {
+        many0_in(
+            bump,
+            terminated(
+                separated_pair(
+                    map_res(digit1, str::parse::<u64>),
+                    space1,
+                    alloc_str(bump, alpha1),
+                ),
+                opt(line_ending),
+            )
+            .map(|(size, name)| File { name, size }),
+        )(input)
+    }
+
+    #[test]
+    fn test_alloc_str() {
+        let bump = Bump::new();
+        let name = {
+            let input = String::from("abc def");
+            let (rest, name) = alloc_str::<nom::error::Error<_>, _>(&bump, alpha1)(&input).unwrap();
+            assert_eq!(rest, " def");
+            name
+        };
+        // `name` outlives the input it was parsed from.
+        assert_eq!(name, "abc");
+    }
+
+    #[test]
+    fn test_alloc() {
+        let bump = Bump::new();
+        let (_, value) = alloc::<_, _, nom::error::Error<_>, _>(
+            &bump,
+            map_res(digit1, str::parse::<u32>),
+        )("123")
+        .unwrap();
+        *value += 1;
+        assert_eq!(*value, 124);
+    }
+
+    #[test]
+    fn test_many0_in() {
+        let bump = Bump::new();
+        let input = String::from("14848514 b\n8504156 c\ndir d");
+        let (rest, files) = parse_files(&bump, &input).unwrap();
+        assert_eq!(rest, "dir d");
+        drop(input);
+        assert_eq!(
+            &files[..],
+            &[
+                File {
+                    name: "b",
+                    size: 14848514
+                },
+                File {
+                    name: "c",
+                    size: 8504156
+                },
+            ]
+        );
+        assert!(bump.allocated_bytes() > 0);
+    }
+}
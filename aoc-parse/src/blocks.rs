@@ -0,0 +1,99 @@
+// Iterator over groups of lines separated by blank lines.
+//
+// Each block is yielded as a slice of the input without its trailing line
+// ending.  Runs of several blank lines count as a single separator and
+// blank lines at the start or end of the input are ignored, so no empty
+// blocks are produced.  Both `\n` and `\r\n` line endings are supported.
+pub struct Blocks<'a> {
+    rest: &'a str,
+}
+
+pub fn blocks(input: &str) -> Blocks<'_> {
+    Blocks { rest: input }
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim_end_matches(['\r', '\n']).is_empty()
+}
+
+fn trim_line_ending(line: &str) -> &str {
+    line.strip_suffix('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .unwrap_or(line)
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Skip separator lines preceding the block.
+        let mut start = 0;
+        for line in self.rest.split_inclusive('\n') {
+            if !is_blank(line) {
+                break;
+            }
+            start += line.len();
+        }
+        let rest = &self.rest[start..];
+        if rest.is_empty() {
+            self.rest = rest;
+            return None;
+        }
+
+        // The block extends up to the next blank line.
+        let mut end = 0;
+        for line in rest.split_inclusive('\n') {
+            if is_blank(line) {
+                break;
+            }
+            end += line.len();
+        }
+
+        self.rest = &rest[end..];
+        Some(trim_line_ending(&rest[..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(input: &str) -> Vec<&str> {
+        blocks(input).collect()
+    }
+
+    #[test]
+    fn single_block() {
+        assert_eq!(collect("1\n2\n3"), vec!["1\n2\n3"]);
+        assert_eq!(collect("1\n2\n3\n"), vec!["1\n2\n3"]);
+    }
+
+    #[test]
+    fn multiple_blocks() {
+        assert_eq!(
+            collect("1000\n2000\n\n4000\n\n5000\n6000\n"),
+            vec!["1000\n2000", "4000", "5000\n6000"]
+        );
+    }
+
+    #[test]
+    fn extra_blank_lines() {
+        assert_eq!(collect("\n\na\n\n\n\nb\n\n"), vec!["a", "b"]);
+        assert_eq!(collect(""), Vec::<&str>::new());
+        assert_eq!(collect("\n\n\n"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn crlf() {
+        assert_eq!(collect("a\r\nb\r\n\r\nc\r\n"), vec!["a\r\nb", "c"]);
+        assert_eq!(collect("\r\n\r\na\r\n\r\n"), vec!["a"]);
+    }
+
+    #[test]
+    fn lines_within_blocks() {
+        let lines: Vec<Vec<_>> = blocks("a\r\nb\r\n\r\nc")
+            .map(|block| block.lines().collect())
+            .collect();
+        assert_eq!(lines, vec![vec!["a", "b"], vec!["c"]]);
+    }
+}
@@ -0,0 +1,9 @@
+// Parsing helpers shared between days.
+
+#[cfg(feature = "bump")]
+pub mod arena;
+mod blocks;
+mod report;
+
+pub use blocks::{blocks, Blocks};
+pub use report::{finish, parse_complete, IResult, ParseError};
@@ -0,0 +1,208 @@
+// Location-annotated reporting of nom parse failures.
+//
+// Parsers return `aoc_parse::IResult`, which uses nom's `VerboseError` to
+// record where parsing failed and what was being parsed at the time.
+// `parse_complete` and `finish` turn those errors into a `ParseError`
+// that displays as:
+//
+//     line 2, column 6: expected '-' while parsing range in pair
+//       6-8,2x4
+//            ^
+
+use std::{error::Error, fmt};
+
+use nom::{
+    error::{VerboseError, VerboseErrorKind},
+    Offset,
+};
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    // One based line and column (in characters) of the failure.
+    pub line: usize,
+    pub column: usize,
+    // Text of the line containing the failure, without its line ending.
+    pub line_text: String,
+    // Description of what the parser expected to find.
+    pub expected: String,
+    // Innermost first list of `context` labels active at the failure.
+    pub contexts: Vec<&'static str>,
+}
+
+impl ParseError {
+    // Build an error pointing at `remaining`, which must be a suffix of
+    // `input`.
+    pub fn at(input: &str, remaining: &str, expected: impl Into<String>) -> Self {
+        let offset = input.offset(remaining).min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_text = input[line_start..]
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            line_text,
+            expected: expected.into(),
+            contexts: Vec::new(),
+        }
+    }
+
+    fn from_verbose(input: &str, error: VerboseError<&str>) -> Self {
+        // The first entry is where parsing actually failed.  Later entries
+        // are the contexts it was nested in.
+        let Some((remaining, kind)) = error.errors.first() else {
+            return Self::at(input, input, "valid input");
+        };
+        let mut contexts: Vec<&'static str> = error
+            .errors
+            .iter()
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(context) => Some(*context),
+                _ => None,
+            })
+            .collect();
+        let expected = match kind {
+            VerboseErrorKind::Char(c) => format!("expected '{}'", c.escape_default()),
+            // Combinator names like `OneOf` mean little to the reader, so
+            // prefer the innermost context label when there is one.
+            VerboseErrorKind::Nom(_) if !contexts.is_empty() => {
+                format!("expected {}", contexts.remove(0))
+            }
+            VerboseErrorKind::Nom(kind) => format!("expected {}", kind.description()),
+            VerboseErrorKind::Context(context) => format!("invalid {}", context),
+        };
+
+        let mut report = Self::at(input, remaining, expected);
+        report.contexts = contexts;
+        report
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.expected
+        )?;
+        if !self.contexts.is_empty() {
+            write!(f, " while parsing {}", self.contexts.join(" in "))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "  {}", self.line_text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl Error for ParseError {}
+
+// Convert the result of running a parser over `input` into one with a
+// location-annotated error.  Unlike `parse_complete`, unconsumed input is
+// returned rather than treated as an error.
+pub fn finish<'a, O>(input: &'a str, result: IResult<'a, O>) -> Result<(&'a str, O), ParseError> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(nom::Err::Error(e)) | Err(nom::Err::Failure(e)) => {
+            Err(ParseError::from_verbose(input, e))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at(input, "", "more input")),
+    }
+}
+
+// Run `parser` over `input`, requiring it to consume everything.
+pub fn parse_complete<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    let (remaining, value) = finish(input, parser(input))?;
+    if !remaining.is_empty() {
+        return Err(ParseError::at(input, remaining, "end of input"));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{
+        bytes::complete::tag,
+        character::complete::{char, digit1, line_ending},
+        error::context,
+        multi::separated_list1,
+        sequence::separated_pair,
+    };
+
+    use super::*;
+
+    type Range<'a> = (&'a str, &'a str);
+
+    fn range(input: &str) -> IResult<'_, Range<'_>> {
+        context("range", separated_pair(digit1, char('-'), digit1))(input)
+    }
+
+    fn pairs(input: &str) -> IResult<'_, Vec<(Range<'_>, Range<'_>)>> {
+        separated_list1(
+            line_ending,
+            context("pair", separated_pair(range, tag(","), range)),
+        )(input)
+    }
+
+    #[test]
+    fn success() {
+        assert_eq!(
+            parse_complete("2-4,6-8", pairs).unwrap(),
+            vec![(("2", "4"), ("6", "8"))]
+        );
+        assert_eq!(finish("1-2x", range("1-2x")).unwrap(), ("x", ("1", "2")));
+    }
+
+    #[test]
+    fn location() {
+        let error = parse_complete("2-4\n16x8", |input| {
+            let (input, first) = range(input)?;
+            let (input, _) = line_ending(input)?;
+            let (input, second) = range(input)?;
+            Ok((input, (first, second)))
+        })
+        .unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 3);
+        assert_eq!(error.line_text, "16x8");
+        assert_eq!(error.expected, "expected '-'");
+        assert_eq!(error.contexts, vec!["range"]);
+    }
+
+    #[test]
+    fn display() {
+        let error = parse_complete("2-4,x-8", pairs).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: expected range while parsing pair\n  2-4,x-8\n      ^"
+        );
+    }
+
+    #[test]
+    fn unlabelled_failure() {
+        let error = parse_complete("x", digit1).unwrap_err();
+        assert_eq!(error.expected, "expected Digit");
+        assert!(error.contexts.is_empty());
+    }
+
+    #[test]
+    fn trailing_input() {
+        let error = parse_complete("2-4,6-8\n\n", pairs).unwrap_err();
+        assert_eq!((error.line, error.column), (1, 8));
+        assert_eq!(error.expected, "end of input");
+    }
+
+    #[test]
+    fn multibyte_columns() {
+        let input = "🎄🎄x";
+        let error = ParseError::at(input, &input[8..], "digit");
+        assert_eq!(error.column, 3);
+    }
+}
@@ -0,0 +1,11 @@
+// Generic graph search algorithms shared between days.
+//
+// Graphs are never materialized: callers describe them with a `neighbors`
+// closure that returns the nodes reachable from a given node (and, for the
+// weighted searches, the cost of reaching them).
+
+mod shortest_path;
+mod traversal;
+
+pub use shortest_path::{astar, dijkstra, dijkstra_all, ShortestPath};
+pub use traversal::{bfs, dfs, Traversal};
@@ -0,0 +1,262 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap},
+    hash::Hash,
+    ops::Add,
+};
+
+// Lowest cost path found by `dijkstra` or `astar`.  `path` includes both the
+// start and goal nodes.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ShortestPath<N, C> {
+    pub cost: C,
+    pub path: Vec<N>,
+}
+
+// Bookkeeping shared by the weighted searches.
+//
+// Nodes are interned into `nodes` so the heap only has to order
+// `(priority, index)` pairs and `N` does not need to implement `Ord`.
+struct Search<N, C> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    costs: Vec<C>,
+    parents: Vec<Option<usize>>,
+    heap: BinaryHeap<Reverse<(C, usize)>>,
+}
+
+impl<N, C> Search<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+{
+    fn new(start: N, priority: C) -> Self {
+        let mut search = Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+            costs: Vec::new(),
+            parents: Vec::new(),
+            heap: BinaryHeap::new(),
+        };
+        search.relax(start, C::default(), None, priority);
+        search
+    }
+
+    // Record that `node` can be reached with `cost`, keeping it only if it
+    // is cheaper than any previously known route.
+    fn relax(&mut self, node: N, cost: C, parent: Option<usize>, priority: C) {
+        let index = match self.indices.entry(node) {
+            Entry::Occupied(entry) => {
+                let index = *entry.get();
+                if cost >= self.costs[index] {
+                    return;
+                }
+                self.costs[index] = cost;
+                self.parents[index] = parent;
+                index
+            }
+            Entry::Vacant(entry) => {
+                let index = self.nodes.len();
+                self.nodes.push(entry.key().clone());
+                entry.insert(index);
+                self.costs.push(cost);
+                self.parents.push(parent);
+                index
+            }
+        };
+        self.heap.push(Reverse((priority, index)));
+    }
+
+    fn path_to(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].clone()];
+        while let Some(parent) = self.parents[index] {
+            path.push(self.nodes[parent].clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+// A* search from `start` to the first node satisfying `is_goal`.
+//
+// `neighbors` returns each reachable node along with the cost of the edge to
+// it.  `heuristic` estimates the remaining cost to a goal and must never
+// overestimate it for the returned path to be optimal.
+pub fn astar<N, C, F, I, H, G>(
+    start: N,
+    mut neighbors: F,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> Option<ShortestPath<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    H: FnMut(&N) -> C,
+    G: FnMut(&N) -> bool,
+{
+    let priority = heuristic(&start);
+    let mut search = Search::new(start, priority);
+
+    while let Some(Reverse((priority, index))) = search.heap.pop() {
+        let cost = search.costs[index];
+
+        // Skip stale heap entries for nodes that have since been reached
+        // more cheaply.
+        let node = search.nodes[index].clone();
+        if priority > cost + heuristic(&node) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some(ShortestPath {
+                cost,
+                path: search.path_to(index),
+            });
+        }
+
+        for (next, edge_cost) in neighbors(&node) {
+            let next_cost = cost + edge_cost;
+            let next_priority = next_cost + heuristic(&next);
+            search.relax(next, next_cost, Some(index), next_priority);
+        }
+    }
+
+    None
+}
+
+// Dijkstra's algorithm from `start` to the first node satisfying `is_goal`.
+pub fn dijkstra<N, C, F, I, G>(start: N, neighbors: F, is_goal: G) -> Option<ShortestPath<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+    G: FnMut(&N) -> bool,
+{
+    // Dijkstra is A* with a heuristic that provides no information.
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+// Dijkstra's algorithm from `start` computing the lowest cost to every
+// reachable node.
+pub fn dijkstra_all<N, C, F, I>(start: N, mut neighbors: F) -> HashMap<N, C>
+where
+    N: Eq + Hash + Clone,
+    C: Ord + Copy + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start, C::default());
+
+    while let Some(Reverse((cost, index))) = search.heap.pop() {
+        if cost > search.costs[index] {
+            continue;
+        }
+
+        let node = search.nodes[index].clone();
+        for (next, edge_cost) in neighbors(&node) {
+            let next_cost = cost + edge_cost;
+            search.relax(next, next_cost, Some(index), next_cost);
+        }
+    }
+
+    search.nodes.into_iter().zip(search.costs).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example graph from the Wikipedia article on Dijkstra's algorithm
+    // (undirected, nodes renumbered from 1).
+    fn wikipedia_neighbors(node: &u32) -> Vec<(u32, u32)> {
+        const EDGES: [(u32, u32, u32); 9] = [
+            (1, 2, 7),
+            (1, 3, 9),
+            (1, 6, 14),
+            (2, 3, 10),
+            (2, 4, 15),
+            (3, 4, 11),
+            (3, 6, 2),
+            (4, 5, 6),
+            (5, 6, 9),
+        ];
+        EDGES
+            .iter()
+            .filter_map(|&(a, b, cost)| {
+                if a == *node {
+                    Some((b, cost))
+                } else if b == *node {
+                    Some((a, cost))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // A 5x5 grid where each cell costs its digit to enter.
+    const GRID: [&str; 5] = ["11637", "13813", "21365", "36949", "74634"];
+
+    fn grid_neighbors(&(x, y): &(i32, i32)) -> Vec<((i32, i32), u32)> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+            .map(|(x, y)| {
+                let cost = GRID[y as usize].as_bytes()[x as usize] - b'0';
+                ((x, y), cost as u32)
+            })
+            .collect()
+    }
+
+    fn manhattan(&(x, y): &(i32, i32)) -> u32 {
+        ((4 - x) + (4 - y)) as u32
+    }
+
+    #[test]
+    fn dijkstra_wikipedia() {
+        let result = dijkstra(1, wikipedia_neighbors, |&node| node == 5).unwrap();
+        assert_eq!(result.cost, 20);
+        assert_eq!(result.path, vec![1, 3, 6, 5]);
+    }
+
+    #[test]
+    fn dijkstra_all_wikipedia() {
+        let costs = dijkstra_all(1, wikipedia_neighbors);
+        assert_eq!(
+            costs,
+            HashMap::from([(1, 0), (2, 7), (3, 9), (4, 20), (5, 20), (6, 11)])
+        );
+    }
+
+    #[test]
+    fn dijkstra_unreachable() {
+        assert_eq!(dijkstra(1, wikipedia_neighbors, |&node| node == 7), None);
+    }
+
+    #[test]
+    fn dijkstra_start_is_goal() {
+        let result = dijkstra(1, wikipedia_neighbors, |&node| node == 1).unwrap();
+        assert_eq!(
+            result,
+            ShortestPath {
+                cost: 0,
+                path: vec![1]
+            }
+        );
+    }
+
+    #[test]
+    fn astar_grid() {
+        let result = astar((0, 0), grid_neighbors, manhattan, |&node| node == (4, 4)).unwrap();
+        let expected = dijkstra((0, 0), grid_neighbors, |&node| node == (4, 4)).unwrap();
+        assert_eq!(result.cost, expected.cost);
+        assert_eq!(result.cost, 24);
+        assert_eq!(result.path.first(), Some(&(0, 0)));
+        assert_eq!(result.path.last(), Some(&(4, 4)));
+    }
+}
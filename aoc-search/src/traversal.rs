@@ -0,0 +1,194 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    hash::Hash,
+};
+
+// Result of a breadth-first or depth-first traversal.
+//
+// `distances` holds the number of edges followed to reach each visited node
+// and `parents` the node it was first discovered from.  The start node has a
+// distance of 0 and no parent.
+#[derive(Debug)]
+pub struct Traversal<N> {
+    pub start: N,
+    pub distances: HashMap<N, usize>,
+    pub parents: HashMap<N, N>,
+    // Nodes in the order they were visited.
+    pub order: Vec<N>,
+}
+
+impl<N: Eq + Hash + Clone> Traversal<N> {
+    fn new(start: N) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), 0)]),
+            parents: HashMap::new(),
+            order: Vec::new(),
+            start,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<usize> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    // Reconstruct the path from the start node to `goal` by following the
+    // parent map backwards.  Returns `None` if `goal` was never reached.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.contains(goal) {
+            return None;
+        }
+
+        let mut path = vec![goal.clone()];
+        let mut current = goal;
+        while let Some(parent) = self.parents.get(current) {
+            path.push(parent.clone());
+            current = parent;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    fn discover(&mut self, node: &N, parent: &N, distance: usize) -> bool {
+        if self.distances.contains_key(node) {
+            return false;
+        }
+        self.distances.insert(node.clone(), distance);
+        self.parents.insert(node.clone(), parent.clone());
+        true
+    }
+}
+
+// Breadth-first traversal of every node reachable from `start`.
+//
+// Because nodes are visited in order of increasing distance, the recorded
+// distances are shortest path lengths in an unweighted graph.
+pub fn bfs<N, F, I>(start: N, mut neighbors: F) -> Traversal<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut traversal = Traversal::new(start.clone());
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = traversal.distances[&node];
+        for next in neighbors(&node) {
+            if traversal.discover(&next, &node, distance + 1) {
+                queue.push_back(next);
+            }
+        }
+        traversal.order.push(node);
+    }
+
+    traversal
+}
+
+// Depth-first traversal of every node reachable from `start`.
+//
+// Distances are the depth at which each node was discovered, which is not
+// necessarily the shortest path length.
+pub fn dfs<N, F, I>(start: N, mut neighbors: F) -> Traversal<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut traversal = Traversal::new(start.clone());
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        let distance = traversal.distances[&node];
+        traversal.order.push(node.clone());
+
+        // Push in reverse so that neighbors are explored in the order the
+        // closure returned them.
+        let next: Vec<_> = neighbors(&node).into_iter().collect();
+        for next in next.into_iter().rev() {
+            if traversal.discover(&next, &node, distance + 1) {
+                stack.push(next);
+            }
+        }
+    }
+
+    traversal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small directed graph:
+    //
+    //   0 -> 1 -> 3 -> 4
+    //   |         ^
+    //   +--> 2 ---+
+    //
+    //   5 (unreachable)
+    fn neighbors(node: &u32) -> Vec<u32> {
+        match node {
+            0 => vec![1, 2],
+            1 => vec![3],
+            2 => vec![3],
+            3 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    // Four connected neighbors on a 5x5 grid.
+    fn grid_neighbors(&(x, y): &(i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .map(move |(dx, dy)| (x + dx, y + dy))
+            .filter(|(x, y)| (0..5).contains(x) && (0..5).contains(y))
+    }
+
+    #[test]
+    fn bfs_distances() {
+        let traversal = bfs(0, neighbors);
+        assert_eq!(traversal.distance(&0), Some(0));
+        assert_eq!(traversal.distance(&1), Some(1));
+        assert_eq!(traversal.distance(&2), Some(1));
+        assert_eq!(traversal.distance(&3), Some(2));
+        assert_eq!(traversal.distance(&4), Some(3));
+        assert_eq!(traversal.distance(&5), None);
+        assert_eq!(traversal.order, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn bfs_path() {
+        let traversal = bfs(0, neighbors);
+        assert_eq!(traversal.path_to(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(traversal.path_to(&0), Some(vec![0]));
+        assert_eq!(traversal.path_to(&5), None);
+    }
+
+    #[test]
+    fn bfs_grid() {
+        let traversal = bfs((0, 0), grid_neighbors);
+        assert_eq!(traversal.distances.len(), 25);
+        assert_eq!(traversal.distance(&(4, 4)), Some(8));
+        assert_eq!(traversal.path_to(&(4, 4)).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn dfs_order() {
+        let traversal = dfs(0, neighbors);
+        assert_eq!(traversal.order, vec![0, 1, 3, 4, 2]);
+        assert_eq!(traversal.distance(&4), Some(3));
+        assert_eq!(traversal.distance(&5), None);
+        assert_eq!(traversal.path_to(&2), Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn dfs_visits_everything_once() {
+        let traversal = dfs((0, 0), grid_neighbors);
+        assert_eq!(traversal.order.len(), 25);
+        assert_eq!(traversal.distances.len(), 25);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{NodeId, Tree};
+
+// Per-node values computed bottom up by `Tree::aggregate`.
+pub struct Aggregates<T, A> {
+    values: Vec<Option<A>>,
+    _tree: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T, A> Aggregates<T, A> {
+    pub(crate) fn compute(tree: &Tree<T>, mut f: impl FnMut(&T, &[&A]) -> A) -> Self {
+        let mut values: Vec<Option<A>> = Vec::new();
+        values.resize_with(tree.len(), || None);
+
+        // Post-order guarantees every child is computed before its parent.
+        for id in tree.post_order(tree.root()) {
+            let children: Vec<_> = tree
+                .children(id)
+                .map(|child| values[child.index()].as_ref().unwrap())
+                .collect();
+            let value = f(tree.get(id), &children);
+            values[id.index()] = Some(value);
+        }
+
+        Self {
+            values,
+            _tree: std::marker::PhantomData,
+        }
+    }
+
+    pub fn get(&self, id: NodeId<T>) -> &A {
+        self.values[id.index()]
+            .as_ref()
+            .expect("node was added after the aggregates were computed")
+    }
+
+    // Every node's aggregate in arena (insertion) order.
+    pub fn iter(&self) -> impl Iterator<Item = &A> {
+        self.values.iter().flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subtree_sums() {
+        let mut tree = Tree::new(1);
+        let a = tree.append(tree.root(), 2);
+        let b = tree.append(tree.root(), 3);
+        let c = tree.append(a, 4);
+
+        let sums = tree.aggregate(|value, children| value + children.iter().copied().sum::<i32>());
+        assert_eq!(*sums.get(tree.root()), 10);
+        assert_eq!(*sums.get(a), 6);
+        assert_eq!(*sums.get(b), 3);
+        assert_eq!(*sums.get(c), 4);
+        assert_eq!(sums.iter().copied().collect::<Vec<_>>(), vec![10, 6, 3, 4]);
+    }
+
+    #[test]
+    fn visits_each_node_once() {
+        let mut tree = Tree::new(());
+        for _ in 0..10 {
+            let child = tree.append(tree.root(), ());
+            tree.append(child, ());
+        }
+        let mut calls = 0;
+        let heights = tree.aggregate(|_, children| {
+            calls += 1;
+            children.iter().map(|&&h| h + 1).max().unwrap_or(0)
+        });
+        assert_eq!(calls, 21);
+        assert_eq!(*heights.get(tree.root()), 2);
+    }
+}
@@ -0,0 +1,214 @@
+// Rooted trees stored in an arena.
+//
+// This is a thin layer over `indextree` that hides its arena-passing API
+// behind a single `Tree` value and adds the operations hierarchical puzzles
+// keep needing: finding children by name, walking the tree in pre or post
+// order, and computing per-subtree aggregates once.
+
+mod aggregate;
+mod node_id;
+
+use indextree::{Arena, NodeEdge};
+
+pub use aggregate::Aggregates;
+pub use node_id::NodeId;
+
+// Values that can be looked up by name among their siblings.
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+// Event produced by `Tree::traverse` when entering or leaving a node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Edge<T> {
+    Enter(NodeId<T>),
+    Exit(NodeId<T>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Tree<T> {
+    arena: Arena<T>,
+    root: NodeId<T>,
+}
+
+impl<T> Tree<T> {
+    pub fn new(root: T) -> Self {
+        let mut arena = Arena::new();
+        let root = NodeId::new(arena.new_node(root));
+        Self { arena, root }
+    }
+
+    pub fn root(&self) -> NodeId<T> {
+        self.root
+    }
+
+    // Total number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+
+    // A tree always has a root, so it is never empty.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    pub fn get(&self, id: NodeId<T>) -> &T {
+        self.arena[id.raw()].get()
+    }
+
+    pub fn get_mut(&mut self, id: NodeId<T>) -> &mut T {
+        self.arena[id.raw()].get_mut()
+    }
+
+    // Add `value` as the last child of `parent`.
+    pub fn append(&mut self, parent: NodeId<T>, value: T) -> NodeId<T> {
+        NodeId::new(parent.raw().append_value(value, &mut self.arena))
+    }
+
+    pub fn parent(&self, id: NodeId<T>) -> Option<NodeId<T>> {
+        id.raw().parent(&self.arena).map(NodeId::new)
+    }
+
+    pub fn children(&self, id: NodeId<T>) -> impl Iterator<Item = NodeId<T>> + '_ {
+        id.raw().children(&self.arena).map(NodeId::new)
+    }
+
+    // First child of `parent` whose value satisfies `predicate`.
+    pub fn find_child(
+        &self,
+        parent: NodeId<T>,
+        mut predicate: impl FnMut(&T) -> bool,
+    ) -> Option<NodeId<T>> {
+        self.children(parent)
+            .find(|&child| predicate(self.get(child)))
+    }
+
+    // Number of edges between `id` and the root.
+    pub fn depth(&self, id: NodeId<T>) -> usize {
+        id.raw().ancestors(&self.arena).count() - 1
+    }
+
+    // `id` and all of its descendants, parents before children.
+    pub fn pre_order(&self, id: NodeId<T>) -> impl Iterator<Item = NodeId<T>> + '_ {
+        id.raw().descendants(&self.arena).map(NodeId::new)
+    }
+
+    // `id` and all of its descendants, children before parents.
+    pub fn post_order(&self, id: NodeId<T>) -> impl Iterator<Item = NodeId<T>> + '_ {
+        self.traverse(id).filter_map(|edge| match edge {
+            Edge::Enter(_) => None,
+            Edge::Exit(id) => Some(id),
+        })
+    }
+
+    // Depth first walk of the subtree at `id` reporting both when each node
+    // is entered and when it is left, which is handy for printing indented
+    // trees.
+    pub fn traverse(&self, id: NodeId<T>) -> impl Iterator<Item = Edge<T>> + '_ {
+        id.raw().traverse(&self.arena).map(|edge| match edge {
+            NodeEdge::Start(id) => Edge::Enter(NodeId::new(id)),
+            NodeEdge::End(id) => Edge::Exit(NodeId::new(id)),
+        })
+    }
+
+    // Compute a value for every node from its own value and the values
+    // already computed for its children.  Each node is visited once.
+    pub fn aggregate<A>(&self, f: impl FnMut(&T, &[&A]) -> A) -> Aggregates<T, A> {
+        Aggregates::compute(self, f)
+    }
+}
+
+impl<T: Named> Tree<T> {
+    // Child of `parent` with the given name.
+    pub fn child_by_name(&self, parent: NodeId<T>, name: &str) -> Option<NodeId<T>> {
+        self.find_child(parent, |value| value.name() == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl Named for &str {
+        fn name(&self) -> &str {
+            self
+        }
+    }
+
+    // /
+    // ├── a
+    // │   ├── c
+    // │   └── d
+    // └── b
+    fn example() -> Tree<&'static str> {
+        let mut tree = Tree::new("/");
+        let a = tree.append(tree.root(), "a");
+        tree.append(tree.root(), "b");
+        tree.append(a, "c");
+        tree.append(a, "d");
+        tree
+    }
+
+    fn values<'a>(
+        tree: &'a Tree<&str>,
+        ids: impl Iterator<Item = NodeId<&'a str>>,
+    ) -> Vec<&'a str> {
+        ids.map(|id| *tree.get(id)).collect()
+    }
+
+    #[test]
+    fn structure() {
+        let tree = example();
+        assert_eq!(tree.len(), 5);
+        assert!(!tree.is_empty());
+
+        let a = tree.child_by_name(tree.root(), "a").unwrap();
+        let d = tree.child_by_name(a, "d").unwrap();
+        assert_eq!(tree.child_by_name(tree.root(), "d"), None);
+        assert_eq!(tree.parent(d), Some(a));
+        assert_eq!(tree.parent(tree.root()), None);
+        assert_eq!(tree.depth(d), 2);
+        assert_eq!(values(&tree, tree.children(a)), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn orders() {
+        let tree = example();
+        assert_eq!(
+            values(&tree, tree.pre_order(tree.root())),
+            vec!["/", "a", "c", "d", "b"]
+        );
+        assert_eq!(
+            values(&tree, tree.post_order(tree.root())),
+            vec!["c", "d", "a", "b", "/"]
+        );
+    }
+
+    #[test]
+    fn traverse() {
+        let tree = example();
+        let a = tree.child_by_name(tree.root(), "a").unwrap();
+        let c = tree.child_by_name(a, "c").unwrap();
+        let d = tree.child_by_name(a, "d").unwrap();
+        assert_eq!(
+            tree.traverse(a).collect::<Vec<_>>(),
+            vec![
+                Edge::Enter(a),
+                Edge::Enter(c),
+                Edge::Exit(c),
+                Edge::Enter(d),
+                Edge::Exit(d),
+                Edge::Exit(a),
+            ]
+        );
+    }
+
+    #[test]
+    fn get_mut() {
+        let mut tree = example();
+        let b = tree.child_by_name(tree.root(), "b").unwrap();
+        *tree.get_mut(b) = "e";
+        assert_eq!(tree.child_by_name(tree.root(), "b"), None);
+        assert_eq!(tree.child_by_name(tree.root(), "e"), Some(b));
+    }
+}
@@ -0,0 +1,59 @@
+use std::{fmt, hash::Hash, marker::PhantomData};
+
+// Handle to a node in a `Tree<T>`.
+//
+// The value type is part of the handle's type so handles from trees of
+// different types can't be mixed up.  Handles from different trees of the
+// same type are not distinguished.
+pub struct NodeId<T> {
+    id: indextree::NodeId,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> NodeId<T> {
+    pub(crate) fn new(id: indextree::NodeId) -> Self {
+        Self {
+            id,
+            _marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn raw(self) -> indextree::NodeId {
+        self.id
+    }
+
+    // Zero based index of the node in its tree's arena.
+    pub(crate) fn index(self) -> usize {
+        usize::from(self.id) - 1
+    }
+}
+
+// Implemented by hand since deriving would require `T` to implement these
+// traits too.
+impl<T> Clone for NodeId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for NodeId<T> {}
+
+impl<T> PartialEq for NodeId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for NodeId<T> {}
+
+impl<T> Hash for NodeId<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for NodeId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "NodeId({})", usize::from(self.id))
+    }
+}
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+// Maps characters to scores, e.g. day 3's a-z => 1-26, A-Z => 27-52.
+//
+// Scores for ASCII characters are kept in a byte-indexed table so that
+// lookups in hot loops are a single array access.  Any other characters
+// fall back to a `HashMap`.
+#[derive(Clone, Debug)]
+pub struct CharScorer {
+    ascii: [Option<u32>; 128],
+    other: HashMap<char, u32>,
+}
+
+impl Default for CharScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CharScorer {
+    // An empty scorer with no characters assigned.
+    pub fn new() -> Self {
+        Self {
+            ascii: [None; 128],
+            other: HashMap::new(),
+        }
+    }
+
+    // Assign consecutive scores starting at `first_score` to each character
+    // of `alphabet` in order.  Later assignments override earlier ones.
+    pub fn with_alphabet(
+        mut self,
+        alphabet: impl IntoIterator<Item = char>,
+        first_score: u32,
+    ) -> Self {
+        for (score, c) in (first_score..).zip(alphabet) {
+            self.set(c, score);
+        }
+        self
+    }
+
+    // Assign consecutive scores starting at `first_score` to each character
+    // in `range`.
+    pub fn with_range(self, range: RangeInclusive<char>, first_score: u32) -> Self {
+        self.with_alphabet(range, first_score)
+    }
+
+    pub fn set(&mut self, c: char, score: u32) {
+        if c.is_ascii() {
+            self.ascii[c as usize] = Some(score);
+        } else {
+            self.other.insert(c, score);
+        }
+    }
+
+    pub fn score(&self, c: char) -> Option<u32> {
+        if c.is_ascii() {
+            self.ascii[c as usize]
+        } else {
+            self.other.get(&c).copied()
+        }
+    }
+
+    // Score a raw byte.  Only ASCII characters can be looked up this way.
+    pub fn score_byte(&self, byte: u8) -> Option<u32> {
+        self.ascii.get(byte as usize).copied().flatten()
+    }
+
+    // Sum of the scores of every character in `s`, or `None` if any
+    // character has no score.
+    pub fn score_str(&self, s: &str) -> Option<u32> {
+        s.chars().map(|c| self.score(c)).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn priorities() -> CharScorer {
+        CharScorer::new()
+            .with_range('a'..='z', 1)
+            .with_range('A'..='Z', 27)
+    }
+
+    #[test]
+    fn ranges() {
+        let scorer = priorities();
+        assert_eq!(scorer.score('a'), Some(1));
+        assert_eq!(scorer.score('z'), Some(26));
+        assert_eq!(scorer.score('A'), Some(27));
+        assert_eq!(scorer.score('Z'), Some(52));
+        assert_eq!(scorer.score('`'), None);
+        assert_eq!(scorer.score('0'), None);
+        assert_eq!(scorer.score('🎄'), None);
+    }
+
+    #[test]
+    fn bytes() {
+        let scorer = priorities();
+        assert_eq!(scorer.score_byte(b'p'), Some(16));
+        assert_eq!(scorer.score_byte(b'L'), Some(38));
+        assert_eq!(scorer.score_byte(0xff), None);
+    }
+
+    #[test]
+    fn custom_alphabet() {
+        let scorer = CharScorer::new().with_alphabet("🎄🎁⭐".chars(), 10);
+        assert_eq!(scorer.score('🎄'), Some(10));
+        assert_eq!(scorer.score('⭐'), Some(12));
+        assert_eq!(scorer.score('a'), None);
+
+        // Later assignments override earlier ones.
+        let scorer = priorities().with_alphabet("a".chars(), 100);
+        assert_eq!(scorer.score('a'), Some(100));
+        assert_eq!(scorer.score('b'), Some(2));
+    }
+
+    #[test]
+    fn strings() {
+        let scorer = priorities();
+        assert_eq!(
+            scorer.score_str("pLPvts"),
+            Some(16 + 38 + 42 + 22 + 20 + 19)
+        );
+        assert_eq!(scorer.score_str("ab1"), None);
+        assert_eq!(scorer.score_str(""), Some(0));
+    }
+}
@@ -0,0 +1,243 @@
+// Iterators over permutations and subsets of a slice.
+//
+// Items are yielded as `Vec`s of cloned elements.  Each iterator reports an
+// exact size hint (saturating at `usize::MAX`) so callers can preallocate or
+// sanity check how much work a brute force search will do.
+
+fn factorial(n: usize) -> usize {
+    (1..=n)
+        .try_fold(1usize, |acc, i| acc.checked_mul(i))
+        .unwrap_or(usize::MAX)
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > usize::MAX as u128 {
+            return usize::MAX;
+        }
+    }
+    result as usize
+}
+
+// Every ordering of the elements, in lexicographic order of their indices.
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    remaining: usize,
+}
+
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: (0..items.len()).collect(),
+        remaining: factorial(items.len()),
+    }
+}
+
+// Advance `indices` to the next lexicographic permutation.  Returns false if
+// it was already the last one.
+fn next_permutation(indices: &mut [usize]) -> bool {
+    let Some(i) = (1..indices.len())
+        .rev()
+        .find(|&i| indices[i - 1] < indices[i])
+    else {
+        return false;
+    };
+    let j = (i..indices.len())
+        .rev()
+        .find(|&j| indices[j] > indices[i - 1])
+        .unwrap();
+    indices.swap(i - 1, j);
+    indices[i..].reverse();
+    true
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let permutation = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        self.remaining -= 1;
+        if !next_permutation(&mut self.indices) {
+            self.remaining = 0;
+        }
+        Some(permutation)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+// Every subset of exactly `k` elements, preserving the slice's order within
+// each subset.
+pub struct KSubsets<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    remaining: usize,
+}
+
+pub fn k_subsets<T: Clone>(items: &[T], k: usize) -> KSubsets<'_, T> {
+    KSubsets {
+        items,
+        indices: (0..k).collect(),
+        remaining: binomial(items.len(), k),
+    }
+}
+
+impl<T: Clone> Iterator for KSubsets<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let subset = self
+            .indices
+            .iter()
+            .map(|&i| self.items[i].clone())
+            .collect();
+        self.remaining -= 1;
+
+        // Advance the rightmost index that still has room to move, then
+        // reset every index after it.
+        let (n, k) = (self.items.len(), self.indices.len());
+        match (0..k).rev().find(|&i| self.indices[i] < n - k + i) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.remaining = 0,
+        }
+
+        Some(subset)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+// Every subset of the elements (the power set), produced by counting a
+// bitmask from 0 to 2^n - 1 where bit `i` selects element `i`.
+//
+// Panics if the slice has 64 or more elements.
+pub fn subsets<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    assert!(items.len() < 64, "too many elements for a u64 bitmask");
+    (0..1u64 << items.len()).map(move |mask| {
+        items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, item)| item.clone())
+            .collect()
+    })
+}
+
+// Every submask of `mask`, from `mask` itself down to 0.
+pub fn submasks(mask: u64) -> impl Iterator<Item = u64> {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || {
+        let current = next?;
+        next = (current != 0).then(|| (current - 1) & mask);
+        Some(current)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutations() {
+        let perms: Vec<_> = permutations(&['a', 'b', 'c']).collect();
+        assert_eq!(
+            perms,
+            vec![
+                vec!['a', 'b', 'c'],
+                vec!['a', 'c', 'b'],
+                vec!['b', 'a', 'c'],
+                vec!['b', 'c', 'a'],
+                vec!['c', 'a', 'b'],
+                vec!['c', 'b', 'a'],
+            ]
+        );
+        assert_eq!(permutations(&[0; 0]).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(permutations(&[1, 2, 3, 4, 5]).count(), 120);
+    }
+
+    #[test]
+    fn permutations_of_duplicates() {
+        // Elements are permuted by position so duplicates are not merged.
+        assert_eq!(permutations(&[1, 1]).count(), 2);
+    }
+
+    #[test]
+    fn permutations_size_hint() {
+        let mut perms = permutations(&[1, 2, 3, 4]);
+        assert_eq!(perms.size_hint(), (24, Some(24)));
+        perms.next();
+        assert_eq!(perms.size_hint(), (23, Some(23)));
+        assert_eq!(permutations(&[0; 30]).size_hint().0, usize::MAX);
+    }
+
+    #[test]
+    fn test_k_subsets() {
+        let subsets: Vec<_> = k_subsets(&[1, 2, 3, 4], 2).collect();
+        assert_eq!(
+            subsets,
+            vec![
+                vec![1, 2],
+                vec![1, 3],
+                vec![1, 4],
+                vec![2, 3],
+                vec![2, 4],
+                vec![3, 4],
+            ]
+        );
+        assert_eq!(k_subsets(&[1, 2, 3], 0).collect::<Vec<_>>(), vec![vec![]]);
+        assert_eq!(
+            k_subsets(&[1, 2, 3], 3).collect::<Vec<_>>(),
+            vec![vec![1, 2, 3]]
+        );
+        assert_eq!(k_subsets(&[1, 2, 3], 4).count(), 0);
+        assert_eq!(k_subsets(&[0; 15], 5).size_hint(), (3003, Some(3003)));
+        assert_eq!(k_subsets(&[0; 15], 5).count(), 3003);
+    }
+
+    #[test]
+    fn test_subsets() {
+        let all: Vec<_> = subsets(&['a', 'b', 'c']).collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], Vec::<char>::new());
+        assert_eq!(all[5], vec!['a', 'c']);
+        assert_eq!(all[7], vec!['a', 'b', 'c']);
+        assert_eq!(subsets(&[0; 10]).size_hint(), (1024, Some(1024)));
+        assert_eq!(subsets(&[0; 10]).count(), 1024);
+    }
+
+    #[test]
+    fn test_submasks() {
+        assert_eq!(
+            submasks(0b101).collect::<Vec<_>>(),
+            vec![0b101, 0b100, 0b001, 0]
+        );
+        assert_eq!(submasks(0).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(submasks(0b1111).count(), 16);
+    }
+}
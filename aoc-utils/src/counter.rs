@@ -0,0 +1,175 @@
+use std::{collections::HashMap, hash::Hash};
+
+// Multiset that tracks how many times each value has been inserted.
+//
+// Values whose count drops to zero are forgotten, so `distinct_count` is
+// always the number of values currently present.
+#[derive(Clone, Debug)]
+pub struct Counter<T> {
+    counts: HashMap<T, usize>,
+    total: usize,
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::new(),
+            total: 0,
+        }
+    }
+
+    // Increment the count of `value`, returning the new count.
+    pub fn insert(&mut self, value: T) -> usize {
+        self.insert_n(value, 1)
+    }
+
+    // Add `n` to the count of `value`, returning the new count.  Inserting
+    // zero of a value that isn't present leaves it absent.
+    pub fn insert_n(&mut self, value: T, n: usize) -> usize {
+        if n == 0 {
+            return self.count(&value);
+        }
+        self.total += n;
+        let count = self.counts.entry(value).or_insert(0);
+        *count += n;
+        *count
+    }
+
+    // Decrement the count of `value`, returning the new count.  Removing a
+    // value that isn't present does nothing.
+    pub fn remove(&mut self, value: &T) -> usize {
+        let Some(count) = self.counts.get_mut(value) else {
+            return 0;
+        };
+        self.total -= 1;
+        *count -= 1;
+        let count = *count;
+        if count == 0 {
+            self.counts.remove(value);
+        }
+        count
+    }
+
+    pub fn count(&self, value: &T) -> usize {
+        self.counts.get(value).copied().unwrap_or(0)
+    }
+
+    // Number of different values present.
+    pub fn distinct_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    // Sum of every value's count.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(value, &count)| (value, count))
+    }
+
+    // The `n` most common values with their counts, most common first.  Ties
+    // are broken by the values' ordering so the result is deterministic.
+    pub fn most_common(&self, n: usize) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut counts: Vec<_> = self.iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then_with(|| a.cmp(b)));
+        counts.truncate(n);
+        counts
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove() {
+        let mut counter = Counter::new();
+        assert!(counter.is_empty());
+
+        assert_eq!(counter.insert('a'), 1);
+        assert_eq!(counter.insert('a'), 2);
+        assert_eq!(counter.insert('b'), 1);
+        assert_eq!(counter.insert_n('c', 3), 3);
+        assert_eq!(counter.total(), 6);
+        assert_eq!(counter.distinct_count(), 3);
+
+        assert_eq!(counter.remove(&'a'), 1);
+        assert_eq!(counter.remove(&'b'), 0);
+        assert_eq!(counter.remove(&'z'), 0);
+        assert_eq!(counter.count(&'a'), 1);
+        assert_eq!(counter.count(&'b'), 0);
+        assert_eq!(counter.distinct_count(), 2);
+        assert_eq!(counter.total(), 4);
+    }
+
+    #[test]
+    fn insert_zero() {
+        let mut counter = Counter::new();
+        assert_eq!(counter.insert_n('a', 0), 0);
+        assert_eq!(counter.distinct_count(), 0);
+        assert!(counter.is_empty());
+        assert_eq!(counter.iter().count(), 0);
+
+        counter.insert('b');
+        assert_eq!(counter.insert_n('b', 0), 1);
+        assert_eq!(counter.distinct_count(), 1);
+    }
+
+    #[test]
+    fn most_common() {
+        let counter: Counter<_> = "mississippi".chars().collect();
+        assert_eq!(counter.most_common(2), vec![(&'i', 4), (&'s', 4)]);
+        assert_eq!(counter.most_common(10).len(), 4);
+        assert_eq!(counter.most_common(0), vec![]);
+    }
+
+    #[test]
+    fn sliding_window() {
+        // Track distinct values in a window of 4 to find the first marker
+        // from the day 6 example.
+        let input = b"mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        let mut counter = Counter::new();
+        let mut marker = None;
+        for (i, &byte) in input.iter().enumerate() {
+            counter.insert(byte);
+            if i >= 4 {
+                counter.remove(&input[i - 4]);
+            }
+            if counter.distinct_count() == 4 {
+                marker = Some(i + 1);
+                break;
+            }
+        }
+        assert_eq!(marker, Some(7));
+    }
+}
@@ -0,0 +1,258 @@
+use std::{collections::HashMap, hash::Hash};
+
+// A cycle in the sequence `x0, f(x0), f(f(x0)), ...`.
+//
+// `start` is the index of the first state that is part of the cycle and
+// `length` the number of steps before the sequence repeats.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    // Map step `n` onto the equivalent step within the first pass through the
+    // cycle.  Returns that index along with the number of whole cycles that
+    // were skipped to get there, which callers can use to extrapolate
+    // quantities that accumulate over each cycle.
+    pub fn reduce(&self, n: u64) -> (usize, u64) {
+        let start = self.start as u64;
+        if n < start {
+            return (n as usize, 0);
+        }
+        let length = self.length as u64;
+        let offset = (n - start) % length;
+        ((start + offset) as usize, (n - start) / length)
+    }
+}
+
+// Floyd's "tortoise and hare" cycle detection.
+//
+// Uses constant memory but evaluates `step` roughly three times per state.
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // Find the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    // Find the length of the cycle.
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Brent's cycle detection.
+//
+// Also uses constant memory but typically needs fewer `step` evaluations
+// than Floyd's algorithm.
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Find the length of the cycle by searching successive powers of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Find the start of the cycle with two pointers `length` steps apart.
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+// Run `step` until a state's `key` repeats.
+//
+// Returns every state visited before the repeat, so callers can look up
+// quantities at any index, along with the detected cycle.  Keying allows
+// states that carry accumulated data (e.g. a tower's total height) to be
+// compared only on the parts that determine future behavior.
+pub fn find_cycle_by_key<S, K>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> (Vec<S>, Cycle)
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+
+    loop {
+        let index = states.len();
+        if let Some(start) = seen.insert(key(&state), index) {
+            return (
+                states,
+                Cycle {
+                    start,
+                    length: index - start,
+                },
+            );
+        }
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+}
+
+// Compute the state after `n` applications of `step`, fast forwarding over
+// whole cycles once one is detected.
+pub fn nth_state<S>(initial: S, n: u64, mut step: impl FnMut(&S) -> S) -> S
+where
+    S: Eq + Hash + Clone,
+{
+    let mut seen = HashMap::new();
+    let mut states: Vec<S> = Vec::new();
+    let mut state = initial;
+
+    for i in 0..n {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle {
+                start,
+                length: i as usize - start,
+            };
+            let (index, _) = cycle.reduce(n);
+            return states.swap_remove(index);
+        }
+        seen.insert(state.clone(), i as usize);
+        let next = step(&state);
+        states.push(state);
+        state = next;
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sequence with a tail of 3 states followed by a cycle of length 5:
+    // 0, 1, 2, 3, 4, 5, 6, 7, 3, 4, ...
+    fn step(x: &u32) -> u32 {
+        if *x == 7 {
+            3
+        } else {
+            x + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 3,
+        length: 5,
+    };
+
+    #[test]
+    fn test_floyd() {
+        assert_eq!(floyd(0, step), CYCLE);
+        assert_eq!(
+            floyd(3, step),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(brent(0, step), CYCLE);
+        assert_eq!(
+            brent(3, step),
+            Cycle {
+                start: 0,
+                length: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_find_cycle_by_key() {
+        let (states, cycle) = find_cycle_by_key(0, step, |x| *x);
+        assert_eq!(cycle, CYCLE);
+        assert_eq!(states, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+
+        // Track a running total alongside the state, keyed only on the
+        // state itself.
+        let (states, cycle) = find_cycle_by_key(
+            (0, 0u64),
+            |(x, total)| (step(x), total + *x as u64),
+            |s| s.0,
+        );
+        assert_eq!(cycle, CYCLE);
+
+        // Extrapolate the total at any step from the amount it grows by
+        // over one pass through the cycle.
+        let (last_x, last_total) = states[cycle.start + cycle.length - 1];
+        let per_cycle = last_total + last_x as u64 - states[cycle.start].1;
+        assert_eq!(per_cycle, 3 + 4 + 5 + 6 + 7);
+
+        let brute_force = |n: u64| {
+            let mut state = (0, 0u64);
+            for _ in 0..n {
+                state = (step(&state.0), state.1 + state.0 as u64);
+            }
+            state.1
+        };
+        for n in [0, 2, 5, 8, 1003] {
+            let (index, cycles) = cycle.reduce(n);
+            assert_eq!(states[index].1 + cycles * per_cycle, brute_force(n));
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(CYCLE.reduce(0), (0, 0));
+        assert_eq!(CYCLE.reduce(2), (2, 0));
+        assert_eq!(CYCLE.reduce(3), (3, 0));
+        assert_eq!(CYCLE.reduce(7), (7, 0));
+        assert_eq!(CYCLE.reduce(8), (3, 1));
+        assert_eq!(CYCLE.reduce(14), (4, 2));
+    }
+
+    #[test]
+    fn test_nth_state() {
+        for n in 0..50 {
+            let mut expected = 0;
+            for _ in 0..n {
+                expected = step(&expected);
+            }
+            assert_eq!(nth_state(0, n, step), expected, "n = {n}");
+        }
+        assert_eq!(nth_state(0, 1_000_000_000_000, step), 5);
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt::Debug;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+// Points awarded for a round: one score per move (in cycle order) plus one
+// per outcome.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Scoring {
+    pub moves: Vec<i32>,
+    pub loss: i32,
+    pub draw: i32,
+    pub win: i32,
+}
+
+impl Scoring {
+    // Day 2's scoring: moves are worth 1, 2, 3, ... in cycle order and a
+    // loss, draw, or win are worth 0, 3, or 6.
+    pub fn standard(num_moves: usize) -> Self {
+        Self {
+            moves: (1..=num_moves as i32).collect(),
+            loss: 0,
+            draw: 3,
+            win: 6,
+        }
+    }
+
+    pub fn outcome(&self, outcome: Outcome) -> i32 {
+        match outcome {
+            Outcome::Loss => self.loss,
+            Outcome::Draw => self.draw,
+            Outcome::Win => self.win,
+        }
+    }
+}
+
+// A game like rock-paper-scissors where every move beats exactly half of
+// the other moves.
+//
+// The moves are arranged in a cycle where each move beats the one before
+// it.  With more than three moves, a move beats every move an odd number of
+// steps behind it in the cycle, which for Rock, Paper, Scissors, Spock,
+// Lizard gives the rules of rock-paper-scissors-lizard-Spock.  This requires
+// an odd number of moves.
+#[derive(Clone, Debug)]
+pub struct CyclicGame<M> {
+    cycle: Vec<M>,
+    scoring: Scoring,
+}
+
+impl<M: Copy + Eq + Debug> CyclicGame<M> {
+    // Panics if `cycle` has an even number of moves.
+    pub fn new(cycle: Vec<M>) -> Self {
+        assert!(
+            cycle.len() % 2 == 1,
+            "cyclic games require an odd number of moves, got {:?}",
+            cycle
+        );
+        let scoring = Scoring::standard(cycle.len());
+        Self { cycle, scoring }
+    }
+
+    // Replace the standard scoring.  Panics if `scoring` doesn't have a
+    // score for each move.
+    pub fn with_scoring(mut self, scoring: Scoring) -> Self {
+        assert_eq!(scoring.moves.len(), self.cycle.len());
+        self.scoring = scoring;
+        self
+    }
+
+    pub fn moves(&self) -> &[M] {
+        &self.cycle
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        &self.scoring
+    }
+
+    fn index(&self, m: M) -> usize {
+        self.cycle
+            .iter()
+            .position(|&other| other == m)
+            .unwrap_or_else(|| panic!("{:?} is not part of this game", m))
+    }
+
+    // Number of steps `ours` is ahead of `theirs` in the cycle.
+    fn distance(&self, ours: M, theirs: M) -> usize {
+        let n = self.cycle.len();
+        (self.index(ours) + n - self.index(theirs)) % n
+    }
+
+    pub fn outcome(&self, ours: M, theirs: M) -> Outcome {
+        match self.distance(ours, theirs) {
+            0 => Outcome::Draw,
+            d if d % 2 == 1 => Outcome::Win,
+            _ => Outcome::Loss,
+        }
+    }
+
+    // Every move that produces `outcome` against `theirs`, in cycle order.
+    pub fn moves_for_outcome(&self, theirs: M, outcome: Outcome) -> impl Iterator<Item = M> + '_ {
+        self.cycle
+            .iter()
+            .copied()
+            .filter(move |&ours| self.outcome(ours, theirs) == outcome)
+    }
+
+    // The move producing `outcome` against `theirs` that is closest to it in
+    // the cycle.  For three move games this is the only such move.
+    pub fn move_for_outcome(&self, theirs: M, outcome: Outcome) -> M {
+        let n = self.cycle.len();
+        let offset = match outcome {
+            Outcome::Draw => 0,
+            Outcome::Win => 1,
+            Outcome::Loss => n - 1,
+        };
+        self.cycle[(self.index(theirs) + offset) % n]
+    }
+
+    pub fn score(&self, ours: M, theirs: M) -> i32 {
+        self.scoring.moves[self.index(ours)] + self.scoring.outcome(self.outcome(ours, theirs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    enum Move {
+        Rock,
+        Paper,
+        Scissors,
+        Spock,
+        Lizard,
+    }
+
+    use Move::*;
+
+    fn rps() -> CyclicGame<Move> {
+        CyclicGame::new(vec![Rock, Paper, Scissors])
+    }
+
+    fn rpsls() -> CyclicGame<Move> {
+        CyclicGame::new(vec![Rock, Paper, Scissors, Spock, Lizard])
+    }
+
+    #[test]
+    fn rps_outcomes() {
+        let game = rps();
+        assert_eq!(game.outcome(Paper, Rock), Outcome::Win);
+        assert_eq!(game.outcome(Scissors, Paper), Outcome::Win);
+        assert_eq!(game.outcome(Rock, Scissors), Outcome::Win);
+        assert_eq!(game.outcome(Rock, Paper), Outcome::Loss);
+        assert_eq!(game.outcome(Scissors, Scissors), Outcome::Draw);
+    }
+
+    #[test]
+    fn rps_moves_for_outcome() {
+        let game = rps();
+        assert_eq!(game.move_for_outcome(Rock, Outcome::Win), Paper);
+        assert_eq!(game.move_for_outcome(Rock, Outcome::Loss), Scissors);
+        assert_eq!(game.move_for_outcome(Rock, Outcome::Draw), Rock);
+        for &theirs in game.moves() {
+            for outcome in [Outcome::Loss, Outcome::Draw, Outcome::Win] {
+                let ours = game.move_for_outcome(theirs, outcome);
+                assert_eq!(game.outcome(ours, theirs), outcome);
+                assert_eq!(game.moves_for_outcome(theirs, outcome).count(), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn rps_score() {
+        // The rounds from the day 2 example.
+        let game = rps();
+        assert_eq!(game.score(Paper, Rock), 8);
+        assert_eq!(game.score(Rock, Paper), 1);
+        assert_eq!(game.score(Scissors, Scissors), 6);
+    }
+
+    #[test]
+    fn rpsls_outcomes() {
+        let game = rpsls();
+        let wins = [
+            (Scissors, Paper),
+            (Paper, Rock),
+            (Rock, Lizard),
+            (Lizard, Spock),
+            (Spock, Scissors),
+            (Scissors, Lizard),
+            (Lizard, Paper),
+            (Paper, Spock),
+            (Spock, Rock),
+            (Rock, Scissors),
+        ];
+        for (a, b) in wins {
+            assert_eq!(game.outcome(a, b), Outcome::Win, "{:?} vs {:?}", a, b);
+            assert_eq!(game.outcome(b, a), Outcome::Loss, "{:?} vs {:?}", b, a);
+        }
+        for &m in game.moves() {
+            assert_eq!(game.moves_for_outcome(m, Outcome::Win).count(), 2);
+            assert_eq!(
+                game.outcome(game.move_for_outcome(m, Outcome::Loss), m),
+                Outcome::Loss
+            );
+        }
+    }
+
+    #[test]
+    fn custom_scoring() {
+        let game = rps().with_scoring(Scoring {
+            moves: vec![0, 0, 0],
+            loss: -1,
+            draw: 0,
+            win: 1,
+        });
+        assert_eq!(game.score(Paper, Rock), 1);
+        assert_eq!(game.score(Rock, Paper), -1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn even_number_of_moves() {
+        CyclicGame::new(vec![Rock, Paper]);
+    }
+}
@@ -0,0 +1,23 @@
+// Assorted data structures and helpers shared between days.
+
+mod char_scorer;
+mod combinatorics;
+mod counter;
+mod cycle;
+mod cyclic_game;
+mod memo;
+mod steady_state;
+mod top_n;
+mod union_find;
+mod windows;
+
+pub use char_scorer::CharScorer;
+pub use combinatorics::{k_subsets, permutations, submasks, subsets, KSubsets, Permutations};
+pub use counter::Counter;
+pub use cycle::{brent, find_cycle_by_key, floyd, nth_state, Cycle};
+pub use cyclic_game::{CyclicGame, Outcome, Scoring};
+pub use memo::Memo;
+pub use steady_state::{run_until_stable, simulate_until_stable, MaxRoundsExceeded};
+pub use top_n::{top_n, top_n_by_key};
+pub use union_find::{Handle, UnionFind};
+pub use windows::{windows_distinct, WindowsDistinct, WindowsDistinctExt};
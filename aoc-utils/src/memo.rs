@@ -0,0 +1,149 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+// Cache for recursive solvers.
+//
+// `get_or_compute` takes a closure that receives the `Memo` itself, letting
+// the computation recurse through the cache:
+//
+//     let mut memo = Memo::new();
+//     fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+//         memo.get_or_compute(n, |memo| {
+//             if n < 2 { n } else { fib(memo, n - 1) + fib(memo, n - 2) }
+//         })
+//     }
+//
+// The hasher is pluggable since the default SipHash is often the bottleneck
+// for DP over small integer keys.
+pub struct Memo<K, V, S = RandomState> {
+    cache: HashMap<K, V, S>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone, S: BuildHasher> Memo<K, V, S> {
+    pub fn with_hasher(hasher: S) -> Self {
+        Self {
+            cache: HashMap::with_hasher(hasher),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    // Return the cached value for `key`, computing and storing it with
+    // `compute` if it isn't present.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            return value.clone();
+        }
+
+        self.misses += 1;
+        let value = compute(self);
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    // Number of lookups answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    // Number of lookups that required computing a value.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, hash::BuildHasherDefault, hash::DefaultHasher};
+
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fib(memo, n - 1) + fib(memo, n - 2)
+            }
+        })
+    }
+
+    #[test]
+    fn recursive() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+
+        // Every value from 0 to 90 is computed exactly once and every other
+        // lookup is a hit.
+        assert_eq!(memo.misses(), 91);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(memo.hits(), 88);
+
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.hits(), 89);
+        assert_eq!(memo.misses(), 91);
+    }
+
+    #[test]
+    fn compute_called_once() {
+        let calls = Cell::new(0);
+        let mut memo = Memo::new();
+        for _ in 0..3 {
+            let value = memo.get_or_compute("key", |_| {
+                calls.set(calls.get() + 1);
+                42
+            });
+            assert_eq!(value, 42);
+        }
+        assert_eq!(calls.get(), 1);
+        assert_eq!(memo.get(&"key"), Some(&42));
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(memo.hits(), 0);
+    }
+
+    #[test]
+    fn custom_hasher() {
+        let mut memo: Memo<u32, u32, BuildHasherDefault<DefaultHasher>> =
+            Memo::with_hasher(Default::default());
+        assert_eq!(memo.get_or_compute(1, |_| 2), 2);
+        assert_eq!(memo.get_or_compute(1, |_| 3), 2);
+        assert_eq!(memo.hits(), 1);
+    }
+}
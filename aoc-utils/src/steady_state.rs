@@ -0,0 +1,115 @@
+use std::{error::Error, fmt};
+
+// Returned when a simulation is still changing after the maximum number of
+// rounds.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MaxRoundsExceeded {
+    pub max_rounds: usize,
+}
+
+impl fmt::Display for MaxRoundsExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "simulation did not reach a steady state within {} rounds",
+            self.max_rounds
+        )
+    }
+}
+
+impl Error for MaxRoundsExceeded {}
+
+// Apply `step` to `state` in place until it reports that nothing changed.
+//
+// `step` returns whether the round changed the state.  On success, returns
+// the number of the first round (counting from 1) that made no change.
+pub fn run_until_stable<S>(
+    state: &mut S,
+    max_rounds: usize,
+    mut step: impl FnMut(&mut S) -> bool,
+) -> Result<usize, MaxRoundsExceeded> {
+    for round in 1..=max_rounds {
+        if !step(state) {
+            return Ok(round);
+        }
+    }
+
+    Err(MaxRoundsExceeded { max_rounds })
+}
+
+// Repeatedly replace `state` with `step(&state)` until the result equals the
+// previous state.
+//
+// Returns the steady state along with the number of the first round
+// (counting from 1) that produced no change.
+pub fn simulate_until_stable<S: PartialEq>(
+    mut state: S,
+    max_rounds: usize,
+    mut step: impl FnMut(&S) -> S,
+) -> Result<(S, usize), MaxRoundsExceeded> {
+    for round in 1..=max_rounds {
+        let next = step(&state);
+        if next == state {
+            return Ok((state, round));
+        }
+        state = next;
+    }
+
+    Err(MaxRoundsExceeded { max_rounds })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_place() {
+        // Halve until reaching zero: 100, 50, 25, 12, 6, 3, 1, 0.
+        let mut value = 100u32;
+        let rounds = run_until_stable(&mut value, 100, |value| {
+            let changed = *value != 0;
+            *value /= 2;
+            changed
+        });
+        assert_eq!(rounds, Ok(8));
+        assert_eq!(value, 0);
+    }
+
+    #[test]
+    fn by_value() {
+        // Sorting by adjacent swaps settles once the list is sorted.
+        let (sorted, rounds) = simulate_until_stable(vec![3, 1, 2], 10, |list| {
+            let mut list = list.clone();
+            if let Some(i) = (0..list.len() - 1).find(|&i| list[i] > list[i + 1]) {
+                list.swap(i, i + 1);
+            }
+            list
+        })
+        .unwrap();
+        assert_eq!(sorted, vec![1, 2, 3]);
+        assert_eq!(rounds, 3);
+    }
+
+    #[test]
+    fn already_stable() {
+        assert_eq!(simulate_until_stable(5, 10, |&x| x), Ok((5, 1)));
+    }
+
+    #[test]
+    fn max_rounds() {
+        let result = simulate_until_stable(0u64, 1000, |x| x + 1);
+        assert_eq!(result, Err(MaxRoundsExceeded { max_rounds: 1000 }));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "simulation did not reach a steady state within 1000 rounds"
+        );
+
+        let mut value = 0;
+        assert!(run_until_stable(&mut value, 5, |value| {
+            *value += 1;
+            true
+        })
+        .is_err());
+        assert_eq!(value, 5);
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+// Heap entry ordered by key, then by arrival order so that earlier items
+// win ties.
+struct Entry<K, T> {
+    key: K,
+    seq: usize,
+    item: T,
+}
+
+impl<K: Ord, T> Ord for Entry<K, T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key
+            .cmp(&other.key)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<K: Ord, T> PartialOrd for Entry<K, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, T> PartialEq for Entry<K, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<K: Ord, T> Eq for Entry<K, T> {}
+
+// The `n` items with the largest keys, in descending order.  Items with
+// equal keys are returned in the order they were produced.
+//
+// Uses a min-heap bounded to `n` entries so this runs in O(len * log n) time
+// and O(n) memory.  Fewer than `n` items are returned if the iterator is
+// shorter than that.
+pub fn top_n_by_key<T, K: Ord>(
+    iter: impl IntoIterator<Item = T>,
+    n: usize,
+    mut key: impl FnMut(&T) -> K,
+) -> Vec<T> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(n + 1);
+    for (seq, item) in iter.into_iter().enumerate() {
+        let entry = Entry {
+            key: key(&item),
+            seq,
+            item,
+        };
+        if heap.len() < n {
+            heap.push(Reverse(entry));
+        } else if let Some(mut smallest) = heap.peek_mut() {
+            if entry > smallest.0 {
+                *smallest = Reverse(entry);
+            }
+        }
+    }
+
+    // `into_sorted_vec` sorts ascending by `Reverse`, which is descending by
+    // key.
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| entry.item)
+        .collect()
+}
+
+// The `n` largest items, in descending order.
+pub fn top_n<T: Ord + Clone>(iter: impl IntoIterator<Item = T>, n: usize) -> Vec<T> {
+    top_n_by_key(iter, n, T::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_n() {
+        assert_eq!(
+            top_n([6000, 4000, 11000, 24000, 10000], 3),
+            vec![24000, 11000, 10000]
+        );
+        assert_eq!(top_n([1, 5, 3], 0), Vec::<i32>::new());
+        assert_eq!(top_n([1, 5, 5, 3], 2), vec![5, 5]);
+    }
+
+    #[test]
+    fn test_top_n_short_input() {
+        // Unlike sort + resize, missing items are not padded.
+        assert_eq!(top_n([2, 1], 5), vec![2, 1]);
+        assert_eq!(top_n(Vec::<i32>::new(), 5), vec![]);
+    }
+
+    #[test]
+    fn test_top_n_by_key() {
+        let elves = [("a", 3), ("b", 7), ("c", 5), ("d", 7)];
+        assert_eq!(
+            top_n_by_key(elves, 3, |(_, calories)| *calories),
+            vec![("b", 7), ("d", 7), ("c", 5)]
+        );
+    }
+
+    #[test]
+    fn test_top_n_matches_sort() {
+        let values: Vec<u32> = (0..1000u32)
+            .map(|i| i.wrapping_mul(2654435761) % 997)
+            .collect();
+        let mut sorted = values.clone();
+        sorted.sort_by(|a, b| b.cmp(a));
+        for n in [1, 10, 100, 1000, 2000] {
+            let expected: Vec<_> = sorted.iter().copied().take(n).collect();
+            assert_eq!(top_n(values.iter().copied(), n), expected);
+        }
+    }
+}
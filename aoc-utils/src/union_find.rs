@@ -0,0 +1,197 @@
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
+
+// Opaque handle to an element of a `UnionFind`.
+//
+// Handles are only meaningful for the `UnionFind` that created them.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Handle(usize);
+
+// Disjoint set forest with path compression and union by rank.
+#[derive(Debug)]
+pub struct UnionFind<T> {
+    values: Vec<T>,
+    handles: HashMap<T, Handle>,
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+    sizes: Vec<usize>,
+    num_sets: usize,
+}
+
+impl<T: Eq + Hash + Clone> Default for UnionFind<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone> UnionFind<T> {
+    pub fn new() -> Self {
+        Self {
+            values: Vec::new(),
+            handles: HashMap::new(),
+            parents: Vec::new(),
+            ranks: Vec::new(),
+            sizes: Vec::new(),
+            num_sets: 0,
+        }
+    }
+
+    // Add `value` as a new singleton set.  If `value` is already present its
+    // existing handle is returned and no new set is created.
+    pub fn insert(&mut self, value: T) -> Handle {
+        if let Some(&handle) = self.handles.get(&value) {
+            return handle;
+        }
+
+        let index = self.values.len();
+        self.values.push(value.clone());
+        self.handles.insert(value, Handle(index));
+        self.parents.push(index);
+        self.ranks.push(0);
+        self.sizes.push(1);
+        self.num_sets += 1;
+
+        Handle(index)
+    }
+
+    pub fn handle(&self, value: &T) -> Option<Handle> {
+        self.handles.get(value).copied()
+    }
+
+    pub fn get(&self, handle: Handle) -> &T {
+        &self.values[handle.0]
+    }
+
+    // Number of elements across all sets.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn num_sets(&self) -> usize {
+        self.num_sets
+    }
+
+    // Find the representative of the set containing `handle`.
+    pub fn find(&mut self, handle: Handle) -> Handle {
+        let mut root = handle.0;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        // Point every node on the path directly at the root.
+        let mut current = handle.0;
+        while self.parents[current] != root {
+            let next = self.parents[current];
+            self.parents[current] = root;
+            current = next;
+        }
+
+        Handle(root)
+    }
+
+    // Merge the sets containing `a` and `b`.  Returns `false` if they were
+    // already in the same set.
+    pub fn union(&mut self, a: Handle, b: Handle) -> bool {
+        let a = self.find(a).0;
+        let b = self.find(b).0;
+        if a == b {
+            return false;
+        }
+
+        let (root, child) = match self.ranks[a].cmp(&self.ranks[b]) {
+            Ordering::Less => (b, a),
+            Ordering::Greater => (a, b),
+            Ordering::Equal => {
+                self.ranks[a] += 1;
+                (a, b)
+            }
+        };
+        self.parents[child] = root;
+        self.sizes[root] += self.sizes[child];
+        self.num_sets -= 1;
+
+        true
+    }
+
+    pub fn same_set(&mut self, a: Handle, b: Handle) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Number of elements in the set containing `handle`.
+    pub fn set_size(&mut self, handle: Handle) -> usize {
+        let root = self.find(handle);
+        self.sizes[root.0]
+    }
+
+    // Group every element by the set it belongs to.
+    pub fn sets(&mut self) -> Vec<Vec<&T>> {
+        let mut sets: HashMap<usize, Vec<usize>> = HashMap::new();
+        for index in 0..self.values.len() {
+            let root = self.find(Handle(index)).0;
+            sets.entry(root).or_default().push(index);
+        }
+
+        let mut sets: Vec<_> = sets.into_values().collect();
+        sets.sort();
+        sets.into_iter()
+            .map(|set| set.into_iter().map(|index| &self.values[index]).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert() {
+        let mut sets = UnionFind::new();
+        assert!(sets.is_empty());
+
+        let a = sets.insert('a');
+        let b = sets.insert('b');
+        assert_ne!(a, b);
+        assert_eq!(sets.insert('a'), a);
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets.num_sets(), 2);
+        assert_eq!(sets.handle(&'b'), Some(b));
+        assert_eq!(sets.handle(&'c'), None);
+        assert_eq!(*sets.get(b), 'b');
+    }
+
+    #[test]
+    fn union() {
+        let mut sets = UnionFind::new();
+        let handles: Vec<_> = (0..6).map(|i| sets.insert(i)).collect();
+
+        assert!(sets.union(handles[0], handles[1]));
+        assert!(sets.union(handles[2], handles[3]));
+        assert!(sets.union(handles[1], handles[3]));
+        assert!(!sets.union(handles[0], handles[2]));
+
+        assert_eq!(sets.num_sets(), 3);
+        assert!(sets.same_set(handles[0], handles[3]));
+        assert!(!sets.same_set(handles[0], handles[4]));
+        assert_eq!(sets.set_size(handles[2]), 4);
+        assert_eq!(sets.set_size(handles[5]), 1);
+        assert_eq!(sets.sets(), vec![vec![&0, &1, &2, &3], vec![&4], vec![&5]]);
+    }
+
+    #[test]
+    fn long_chain() {
+        // Union a long chain one element at a time to exercise path
+        // compression.
+        let mut sets = UnionFind::new();
+        let handles: Vec<_> = (0..1000).map(|i| sets.insert(i)).collect();
+        for pair in handles.windows(2) {
+            sets.union(pair[1], pair[0]);
+        }
+
+        assert_eq!(sets.num_sets(), 1);
+        assert_eq!(sets.set_size(handles[500]), 1000);
+        assert!(sets.same_set(handles[0], handles[999]));
+    }
+}
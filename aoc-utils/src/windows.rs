@@ -0,0 +1,122 @@
+use std::{collections::VecDeque, hash::Hash};
+
+use crate::Counter;
+
+// Iterator adapter returned by `windows_distinct`.
+pub struct WindowsDistinct<I: Iterator> {
+    iter: I,
+    size: usize,
+    window: VecDeque<I::Item>,
+    counter: Counter<I::Item>,
+}
+
+// For each item of `iter`, yield whether it and the `size - 1` items
+// before it are all distinct.  Positions before the window has filled
+// yield `false`.
+//
+// Each step costs O(1) expected time regardless of `size`, and only the
+// current window is buffered so this works on unbounded streams.
+pub fn windows_distinct<I>(iter: I, size: usize) -> WindowsDistinct<I::IntoIter>
+where
+    I: IntoIterator,
+    I::Item: Eq + Hash + Clone,
+{
+    assert!(size > 0, "window size must be positive");
+    WindowsDistinct {
+        iter: iter.into_iter(),
+        size,
+        window: VecDeque::with_capacity(size + 1),
+        counter: Counter::new(),
+    }
+}
+
+impl<I> Iterator for WindowsDistinct<I>
+where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone,
+{
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        self.counter.insert(item.clone());
+        self.window.push_back(item);
+        if self.window.len() > self.size {
+            let oldest = self.window.pop_front().unwrap();
+            self.counter.remove(&oldest);
+        }
+
+        Some(self.window.len() == self.size && self.counter.distinct_count() == self.size)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+// Method syntax for `windows_distinct`.
+pub trait WindowsDistinctExt: Iterator + Sized
+where
+    Self::Item: Eq + Hash + Clone,
+{
+    fn windows_distinct(self, size: usize) -> WindowsDistinct<Self> {
+        windows_distinct(self, size)
+    }
+}
+
+impl<I> WindowsDistinctExt for I
+where
+    I: Iterator,
+    I::Item: Eq + Hash + Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Position after the end of the first distinct window, as in day 6.
+    fn marker(input: &str, size: usize) -> Option<usize> {
+        input
+            .bytes()
+            .windows_distinct(size)
+            .position(|distinct| distinct)
+            .map(|i| i + 1)
+    }
+
+    #[test]
+    fn distinct_flags() {
+        assert_eq!(
+            windows_distinct("abcab".chars(), 3).collect::<Vec<_>>(),
+            vec![false, false, true, true, true]
+        );
+        assert_eq!(
+            windows_distinct("aabba".chars(), 2).collect::<Vec<_>>(),
+            vec![false, false, true, false, true]
+        );
+        assert_eq!(
+            windows_distinct("aaa".chars(), 1).collect::<Vec<_>>(),
+            vec![true, true, true]
+        );
+        assert_eq!(windows_distinct("ab".chars(), 3).count(), 2);
+    }
+
+    #[test]
+    fn day_6_examples() {
+        assert_eq!(marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+        assert_eq!(marker("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+        assert_eq!(marker("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Some(19));
+        assert_eq!(marker("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), Some(26));
+        assert_eq!(marker("aaaa", 2), None);
+    }
+
+    #[test]
+    fn unbounded_stream() {
+        // Only the window is buffered, so infinite iterators are fine.
+        let position = (0u32..)
+            .map(|i| i % 7)
+            .windows_distinct(7)
+            .position(|distinct| distinct);
+        assert_eq!(position, Some(6));
+    }
+}
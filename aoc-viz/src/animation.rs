@@ -0,0 +1,46 @@
+use std::{
+    io::{self, Write},
+    thread,
+    time::Duration,
+};
+
+// ANSI escapes moving the cursor to the top left and clearing the screen.
+const CLEAR: &str = "\x1b[H\x1b[2J";
+
+// Frames of text drawn one after another in the same place in a terminal,
+// pausing between them.
+pub struct Animation<W> {
+    out: W,
+    delay: Duration,
+}
+
+impl<W: Write> Animation<W> {
+    pub fn new(out: W, delay: Duration) -> Self {
+        Self { out, delay }
+    }
+
+    // Replace the previous frame with `frame`, then wait for the delay.
+    pub fn frame(&mut self, frame: &str) -> io::Result<()> {
+        write!(self.out, "{CLEAR}{frame}")?;
+        self.out.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let mut out = Vec::new();
+        let mut animation = Animation::new(&mut out, Duration::ZERO);
+        animation.frame("one\n").unwrap();
+        animation.frame("two\n").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[H\x1b[2Jone\n\x1b[H\x1b[2Jtwo\n"
+        );
+    }
+}
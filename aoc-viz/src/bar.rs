@@ -0,0 +1,43 @@
+// Blocks filling 1/8 to 8/8 of a character cell.
+const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+// Render `value` as a horizontal bar scaled so that `max` fills `width`
+// characters.  Partial cells are drawn with eighth blocks.
+pub fn bar(value: f64, max: f64, width: usize) -> String {
+    if max <= 0.0 || value <= 0.0 {
+        return String::new();
+    }
+    let eighths = ((value.min(max) / max) * (width * 8) as f64).round() as usize;
+
+    let (full, partial) = (eighths / 8, eighths % 8);
+
+    let mut bar = EIGHTHS[7].to_string().repeat(full);
+    if partial > 0 {
+        bar.push(EIGHTHS[partial - 1]);
+    }
+    bar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_and_empty() {
+        assert_eq!(bar(10.0, 10.0, 4), "████");
+        assert_eq!(bar(0.0, 10.0, 4), "");
+        assert_eq!(bar(5.0, 0.0, 4), "");
+    }
+
+    #[test]
+    fn partial() {
+        assert_eq!(bar(5.0, 10.0, 4), "██");
+        assert_eq!(bar(1.0, 16.0, 4), "▎");
+        assert_eq!(bar(9.0, 16.0, 4), "██▎");
+    }
+
+    #[test]
+    fn clamped_to_width() {
+        assert_eq!(bar(20.0, 10.0, 2), "██");
+    }
+}
@@ -0,0 +1,110 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+use crate::bar;
+
+// Counts of values falling into equal width bins between the smallest and
+// largest value seen.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Histogram {
+    min: i64,
+    bin_width: i64,
+    counts: Vec<usize>,
+}
+
+impl Histogram {
+    // Panics if `bins` is zero.
+    pub fn new(values: impl IntoIterator<Item = i64>, bins: usize) -> Self {
+        assert!(bins > 0, "a histogram needs at least one bin");
+
+        let values: Vec<_> = values.into_iter().collect();
+        let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+            return Self {
+                min: 0,
+                bin_width: 1,
+                counts: Vec::new(),
+            };
+        };
+
+        // Round up so the last bin reaches `max`, dropping bins which would
+        // lie entirely past it.
+        let span = max - min + 1;
+        let bin_width = (span + bins as i64 - 1) / bins as i64;
+        let bins = ((span + bin_width - 1) / bin_width) as usize;
+
+        let mut counts = vec![0; bins];
+        for value in values {
+            counts[((value - min) / bin_width) as usize] += 1;
+        }
+
+        Self {
+            min,
+            bin_width,
+            counts,
+        }
+    }
+
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    // Values counted by bin `index`.
+    pub fn bin_range(&self, index: usize) -> RangeInclusive<i64> {
+        let start = self.min + index as i64 * self.bin_width;
+        start..=start + self.bin_width - 1
+    }
+
+    // Render one line per bin: its range, a bar scaled so the fullest bin
+    // is `width` characters wide, and its count.
+    pub fn render(&self, width: usize) -> String {
+        let max_count = self.counts.iter().copied().max().unwrap_or(0);
+        let labels: Vec<_> = (0..self.counts.len())
+            .map(|index| {
+                let range = self.bin_range(index);
+                format!("{}..={}", range.start(), range.end())
+            })
+            .collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for (label, &count) in labels.iter().zip(&self.counts) {
+            let bar = bar(count as f64, max_count as f64, width);
+            // Writing to a String can't fail.
+            writeln!(out, "{label:>label_width$} | {bar:<width$} {count}").unwrap();
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn binning() {
+        let histogram = Histogram::new([1, 2, 3, 4, 10], 3);
+        // Spans 1..=10, so bins are 4 wide.
+        assert_eq!(histogram.counts(), &[4, 0, 1]);
+        assert_eq!(histogram.bin_range(0), 1..=4);
+        assert_eq!(histogram.bin_range(2), 9..=12);
+    }
+
+    #[test]
+    fn fewer_distinct_values_than_bins() {
+        let histogram = Histogram::new([5, 5, 6], 10);
+        assert_eq!(histogram.counts(), &[2, 1]);
+        assert_eq!(histogram.bin_range(1), 6..=6);
+    }
+
+    #[test]
+    fn empty() {
+        let histogram = Histogram::new([], 4);
+        assert!(histogram.counts().is_empty());
+        assert_eq!(histogram.render(10), "");
+    }
+
+    #[test]
+    fn render() {
+        let histogram = Histogram::new([0, 1, 1, 2, 3, 3, 3, 3], 2);
+        assert_eq!(histogram.render(4), "0..=1 | ██▍  3\n2..=3 | ████ 5\n");
+    }
+}
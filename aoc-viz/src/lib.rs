@@ -0,0 +1,11 @@
+// Plain text charts for exploring puzzle inputs in the terminal.
+
+mod animation;
+mod bar;
+mod histogram;
+mod spans;
+
+pub use animation::Animation;
+pub use bar::bar;
+pub use histogram::Histogram;
+pub use spans::SpanChart;
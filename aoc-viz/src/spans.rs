@@ -0,0 +1,231 @@
+use std::{fmt::Write, ops::RangeInclusive};
+
+// Characters for values outside a row's span, inside it, and highlighted.
+const EMPTY: char = '.';
+const SPAN: char = '=';
+const HIGHLIGHT: char = '#';
+
+// Pixel sizes used by `render_svg`.
+const SVG_LABEL_WIDTH: usize = 120;
+const SVG_ROW_HEIGHT: usize = 14;
+const SVG_BAR_HEIGHT: usize = 10;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Row {
+    label: String,
+    span: RangeInclusive<i64>,
+    highlights: Vec<RangeInclusive<i64>>,
+}
+
+// Ranges drawn as bars against a shared axis, each with parts highlighted,
+// e.g. day 4's section assignments with their overlaps.  Rows can be split
+// into groups with `gap`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SpanChart {
+    // `None` is a gap between groups.
+    rows: Vec<Option<Row>>,
+}
+
+impl SpanChart {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Add a bar for `span` with the parts within `highlights` picked out.
+    pub fn row(
+        &mut self,
+        label: impl Into<String>,
+        span: RangeInclusive<i64>,
+        highlights: Vec<RangeInclusive<i64>>,
+    ) {
+        self.rows.push(Some(Row {
+            label: label.into(),
+            span,
+            highlights,
+        }));
+    }
+
+    // Leave a blank row before the next one.
+    pub fn gap(&mut self) {
+        self.rows.push(None);
+    }
+
+    fn bars(&self) -> impl Iterator<Item = &Row> {
+        self.rows.iter().flatten()
+    }
+
+    // The smallest and largest value of any span.
+    fn axis(&self) -> Option<(i64, i64)> {
+        self.bars()
+            .map(|row| &row.span)
+            .filter(|span| !span.is_empty())
+            .map(|span| (*span.start(), *span.end()))
+            .reduce(|(min, max), (start, end)| (min.min(start), max.max(end)))
+    }
+
+    // Render each bar as a line of ASCII at most `width` characters wide,
+    // one character per value when they fit.
+    pub fn render(&self, width: usize) -> String {
+        let Some((min, max)) = self.axis() else {
+            return String::new();
+        };
+        // Work in `i128` so spans reaching the ends of `i64` can't overflow.
+        let values = i128::from(max) - i128::from(min) + 1;
+        let cells = values.min(width.max(1) as i128);
+        // Values covered by `cell`, spreading them as evenly as possible.
+        let cell_values = |cell: i128| {
+            let start = i128::from(min) + cell * values / cells;
+            let end = i128::from(min) + (cell + 1) * values / cells - 1;
+            start as i64..=end as i64
+        };
+        let label_width = self.bars().map(|row| row.label.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        for row in &self.rows {
+            let Some(row) = row else {
+                out.push('\n');
+                continue;
+            };
+            let bar: String = (0..cells)
+                .map(|cell| {
+                    let values = cell_values(cell);
+                    if row.highlights.iter().any(|range| overlaps(range, &values)) {
+                        HIGHLIGHT
+                    } else if overlaps(&row.span, &values) {
+                        SPAN
+                    } else {
+                        EMPTY
+                    }
+                })
+                .collect();
+            // Writing to a String can't fail.
+            writeln!(out, "{:>label_width$} {bar}", row.label).unwrap();
+        }
+        out
+    }
+
+    // Render the chart as an SVG image `width` pixels wide.
+    pub fn render_svg(&self, width: usize) -> String {
+        let height = self.rows.len() * SVG_ROW_HEIGHT;
+        let mut out = String::new();
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" font-family="monospace" font-size="{SVG_BAR_HEIGHT}">"#
+        )
+        .unwrap();
+
+        if let Some((min, max)) = self.axis() {
+            let values = (i128::from(max) - i128::from(min) + 1) as f64;
+            let scale = width.saturating_sub(SVG_LABEL_WIDTH) as f64 / values;
+            let x = |value: i64| {
+                SVG_LABEL_WIDTH as f64 + (i128::from(value) - i128::from(min)) as f64 * scale
+            };
+            let rect = |out: &mut String, range: &RangeInclusive<i64>, y: usize, color: &str| {
+                if !range.is_empty() {
+                    let (start, end) = (x(*range.start()), x(*range.end()) + scale);
+                    writeln!(
+                        out,
+                        r#"  <rect x="{start:.1}" y="{y}" width="{:.1}" height="{SVG_BAR_HEIGHT}" fill="{color}"/>"#,
+                        end - start
+                    )
+                    .unwrap();
+                }
+            };
+
+            for (i, row) in self.rows.iter().enumerate() {
+                let Some(row) = row else {
+                    continue;
+                };
+                let y = i * SVG_ROW_HEIGHT;
+                writeln!(
+                    out,
+                    r#"  <text x="0" y="{}">{}</text>"#,
+                    y + SVG_BAR_HEIGHT,
+                    escape(&row.label)
+                )
+                .unwrap();
+                rect(&mut out, &row.span, y, "steelblue");
+                for highlight in &row.highlights {
+                    let start = *highlight.start().max(row.span.start());
+                    let end = *highlight.end().min(row.span.end());
+                    rect(&mut out, &(start..=end), y, "orangered");
+                }
+            }
+        }
+
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn overlaps(a: &RangeInclusive<i64>, b: &RangeInclusive<i64>) -> bool {
+    !a.is_empty() && !b.is_empty() && a.start() <= b.end() && b.start() <= a.end()
+}
+
+// Escape text for use in SVG.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> SpanChart {
+        let mut chart = SpanChart::new();
+        chart.row("2-6", 2..=6, vec![4..=6]);
+        chart.row("4-8", 4..=8, vec![4..=6]);
+        chart.gap();
+        chart.row("10-10", 10..=10, vec![]);
+        chart
+    }
+
+    #[test]
+    fn render() {
+        assert_eq!(
+            example().render(80),
+            "  2-6 ==###....\n  4-8 ..###==..\n\n10-10 ........=\n"
+        );
+        assert_eq!(SpanChart::new().render(80), "");
+    }
+
+    #[test]
+    fn render_scaled() {
+        // Nine values squeezed into three cells of three values each.
+        assert_eq!(example().render(3), "  2-6 ##.\n  4-8 ##=\n\n10-10 ..=\n");
+    }
+
+    #[test]
+    fn render_svg() {
+        let svg = example().render_svg(210);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        // One bar per row plus one per highlight.
+        assert_eq!(svg.matches("<rect").count(), 5);
+        // 90 pixels for 9 values.
+        assert!(
+            svg.contains(r#"<rect x="140.0" y="14" width="50.0""#),
+            "{svg}"
+        );
+        assert!(
+            svg.contains(r#"<rect x="140.0" y="0" width="30.0" height="10" fill="orangered"/>"#)
+        );
+    }
+
+    #[test]
+    fn extreme_values() {
+        let mut chart = SpanChart::new();
+        chart.row("all", i64::MIN..=i64::MAX, vec![0..=i64::MAX]);
+        assert_eq!(chart.render(4), "all ==##\n");
+        assert_eq!(chart.render_svg(200).matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn escaping() {
+        let mut chart = SpanChart::new();
+        chart.row("<a & b>", 0..=1, vec![]);
+        assert!(chart.render_svg(200).contains(">&lt;a &amp; b&gt;</text>"));
+    }
+}
@@ -1,4 +1,4 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
 
 const INPUT: &str = include_str!("../../day-01/input.txt");
 
@@ -10,6 +10,12 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("parse_input_fancy", |b| {
         b.iter(|| day_01_lib::parse_input_fancy(INPUT).unwrap())
     });
+    c.bench_function("parse_input_bytes", |b| {
+        b.iter(|| day_01_lib::parse_input_bytes(INPUT.as_bytes()).unwrap())
+    });
+    c.bench_function("part1_iter", |b| {
+        b.iter(|| day_01_lib::part1_iter(INPUT).unwrap())
+    });
     c.bench_function("find_max_calories", |b| {
         b.iter(|| day_01_lib::find_max_calories(&elves))
     });
@@ -18,5 +24,44 @@ fn criterion_benchmark(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, criterion_benchmark);
+fn top_n_benchmark(c: &mut Criterion) {
+    let elves = day_01_lib::parse_input(INPUT).unwrap();
+    let mut group = c.benchmark_group("top_n_calories");
+    for n in [3, 10, 100] {
+        group.bench_with_input(BenchmarkId::new("sort", n), &n, |b, &n| {
+            b.iter(|| day_01_lib::find_top_n_calories(&elves, n))
+        });
+        group.bench_with_input(BenchmarkId::new("heap", n), &n, |b, &n| {
+            b.iter(|| day_01_lib::find_top_n_calories_heap(&elves, n))
+        });
+    }
+    group.finish();
+}
+
+fn parallel_benchmark(c: &mut Criterion) {
+    // Large enough that splitting the work across threads pays off.
+    let large = [INPUT; 200].join("\n");
+    let mut group = c.benchmark_group("large_input");
+    group.bench_function("part1", |b| b.iter(|| day_01_lib::part1(&large).unwrap()));
+    group.bench_function("part1_iter", |b| {
+        b.iter(|| day_01_lib::part1_iter(&large).unwrap())
+    });
+    group.bench_function("part1_parallel", |b| {
+        b.iter(|| day_01_lib::part1_parallel(&large).unwrap())
+    });
+    group.bench_function("part2_top_n", |b| {
+        b.iter(|| day_01_lib::part2_top_n(&large, 3).unwrap())
+    });
+    group.bench_function("part2_parallel", |b| {
+        b.iter(|| day_01_lib::part2_parallel(&large, 3).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    top_n_benchmark,
+    parallel_benchmark
+);
 criterion_main!(benches);
@@ -0,0 +1,140 @@
+use std::str::FromStr;
+
+use anyhow::{Error, Result};
+use aoc_utils::top_n_by_key;
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_input, Stats};
+
+// An elf along with where it appears in the input and what it carries.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Elf {
+    // Zero based position of the elf in the input.
+    pub index: usize,
+    pub items: Vec<i32>,
+    pub total: i32,
+}
+
+impl Elf {
+    pub fn new(index: usize, items: Vec<i32>) -> Self {
+        let total = items.iter().sum();
+        Self {
+            index,
+            items,
+            total,
+        }
+    }
+}
+
+// Every elf from the challenge input, in input order.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Inventory {
+    pub elves: Vec<Elf>,
+}
+
+impl Inventory {
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn totals(&self) -> impl Iterator<Item = i32> + '_ {
+        self.elves.iter().map(|elf| elf.total)
+    }
+
+    // The elf carrying the most calories.  The first such elf wins ties.
+    pub fn max_elf(&self) -> Option<&Elf> {
+        self.top_n(1).pop()
+    }
+
+    // The `n` elves carrying the most calories, in descending order.
+    pub fn top_n(&self, n: usize) -> Vec<&Elf> {
+        top_n_by_key(&self.elves, n, |elf| elf.total)
+    }
+
+    pub fn stats(&self) -> Option<Stats> {
+        Stats::from_totals(self.totals())
+    }
+}
+
+impl FromStr for Inventory {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_input(s)
+    }
+}
+
+impl From<Vec<Vec<i32>>> for Inventory {
+    fn from(elves: Vec<Vec<i32>>) -> Self {
+        Self {
+            elves: elves
+                .into_iter()
+                .enumerate()
+                .map(|(index, items)| Elf::new(index, items))
+                .collect(),
+        }
+    }
+}
+
+impl From<Inventory> for Vec<Vec<i32>> {
+    fn from(inventory: Inventory) -> Self {
+        inventory.elves.into_iter().map(|elf| elf.items).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT_1: &str = include_str!("example-input-1.txt");
+
+    #[test]
+    fn parse() {
+        let inventory: Inventory = EXAMPLE_INPUT_1.parse().unwrap();
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.elves[2], Elf::new(2, vec![5000, 6000]));
+        assert_eq!(inventory.elves[2].total, 11000);
+        assert_eq!(
+            inventory.totals().collect::<Vec<_>>(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+    }
+
+    #[test]
+    fn max_and_top_n() {
+        let inventory: Inventory = EXAMPLE_INPUT_1.parse().unwrap();
+        assert_eq!(
+            inventory.max_elf(),
+            Some(&Elf::new(3, vec![7000, 8000, 9000]))
+        );
+        let top: Vec<_> = inventory.top_n(3).iter().map(|elf| elf.index).collect();
+        assert_eq!(top, vec![3, 2, 4]);
+        assert_eq!(Inventory::default().max_elf(), None);
+    }
+
+    #[test]
+    fn stats() {
+        let inventory: Inventory = EXAMPLE_INPUT_1.parse().unwrap();
+        assert_eq!(inventory.stats().unwrap().median, 10000.0);
+    }
+
+    #[test]
+    fn conversions() {
+        let raw = vec![vec![1, 2], vec![3]];
+        let inventory = Inventory::from(raw.clone());
+        assert_eq!(inventory.elves[1], Elf::new(1, vec![3]));
+        assert_eq!(Vec::<Vec<i32>>::from(inventory), raw);
+    }
+
+    #[test]
+    fn json() {
+        let inventory = Inventory::from(vec![vec![1, 2]]);
+        let json = serde_json::to_string(&inventory).unwrap();
+        assert_eq!(json, r#"{"elves":[{"index":0,"items":[1,2],"total":3}]}"#);
+        assert_eq!(serde_json::from_str::<Inventory>(&json).unwrap(), inventory);
+    }
+}
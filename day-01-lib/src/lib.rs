@@ -1,56 +1,249 @@
-use std::cmp;
+use std::{
+    cmp,
+    error::Error,
+    fmt,
+    iter::Enumerate,
+    mem,
+    str::{FromStr, Lines},
+};
 
 use anyhow::{anyhow, Result};
+use aoc_parse::blocks;
+use aoc_utils::top_n;
+use rayon::prelude::*;
 
-// Parse challenge input into a Vec of Vecs.
+mod inventory;
+
+pub use inventory::{Elf, Inventory};
+
+// Parse a single line of calories.  `index` is the zero based line number,
+// reported along with the line's text on failure.
+fn parse_calories<T>(index: usize, line: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    line.parse()
+        .map_err(|e| anyhow!("Error parsing line {} '{}': {}", index + 1, line, e))
+}
+
+// Zero based line number at which `block`, a slice of `text`, starts.
+fn first_line_index(text: &str, block: &str) -> usize {
+    let offset = block.as_ptr() as usize - text.as_ptr() as usize;
+    text[..offset].matches('\n').count()
+}
+
+// Parse challenge input into an `Inventory`.
 //
 // This implementation uses a straight forward imperative approach.
-pub fn parse_input(text: &str) -> Result<Vec<Vec<i32>>> {
+pub fn parse_input(text: &str) -> Result<Inventory> {
     let mut elves = Vec::new();
-    let mut elf = Vec::new();
-    for line in text.lines() {
-        if line.is_empty() {
-            elves.push(elf);
-            elf = Vec::new();
-        } else {
-            let calories: i32 = line
-                .parse()
-                .map_err(|e| anyhow!("Error parsing '{}': {}", text, e))?;
+    for block in blocks(text) {
+        let first_line = first_line_index(text, block);
+        let mut elf = Vec::new();
+        for (i, line) in block.lines().enumerate() {
+            let calories: i32 = parse_calories(first_line + i, line)?;
             elf.push(calories);
         }
+        elves.push(Elf::new(elves.len(), elf));
     }
-    elves.push(elf);
 
-    Ok(elves)
+    Ok(Inventory { elves })
 }
 
-// Parse challenge input into a Vec of Vecs.
+// Parse challenge input into an `Inventory`.
 //
-// This implementation uses a "fancier" more functional approach.
-pub fn parse_input_fancy(text: &str) -> Result<Vec<Vec<i32>>> {
+// This implementation uses a "fancier" more functional approach.  As with
+// `blocks`, runs of blank lines and blank lines at either end of the input
+// don't add empty elves.
+pub fn parse_input_fancy(text: &str) -> Result<Inventory> {
     text.lines()
-        .try_fold(vec![vec![]], |mut elves, line| -> Result<Vec<Vec<i32>>> {
-            if line.is_empty() {
-                elves.push(Vec::new());
-                Ok(elves)
-            } else {
-                let calories: i32 = line
-                    .parse()
-                    .map_err(|e| anyhow!("Error parsing '{}': {}", text, e))?;
-                elves.last_mut().unwrap().push(calories);
-                Ok(elves)
-            }
+        .enumerate()
+        .try_fold(
+            vec![vec![]],
+            |mut elves, (i, line)| -> Result<Vec<Vec<i32>>> {
+                if line.is_empty() {
+                    elves.push(Vec::new());
+                    Ok(elves)
+                } else {
+                    let calories: i32 = parse_calories(i, line)?;
+                    elves.last_mut().unwrap().push(calories);
+                    Ok(elves)
+                }
+            },
+        )
+        .map(|elves| {
+            elves
+                .into_iter()
+                .filter(|elf| !elf.is_empty())
+                .collect::<Vec<_>>()
+                .into()
         })
 }
 
+// Parse a single line of calories from raw bytes, accumulating digits by
+// hand.  `line` must not be empty.  Errors match those of `parse_calories`.
+fn parse_calories_bytes(index: usize, line: &[u8]) -> Result<i32> {
+    let error = |reason| {
+        anyhow!(
+            "Error parsing line {} '{}': {}",
+            index + 1,
+            String::from_utf8_lossy(line),
+            reason
+        )
+    };
+
+    let mut calories: i32 = 0;
+    for &byte in line {
+        if !byte.is_ascii_digit() {
+            return Err(error("invalid digit found in string"));
+        }
+        calories = calories
+            .checked_mul(10)
+            .and_then(|calories| calories.checked_add((byte - b'0') as i32))
+            .ok_or_else(|| error("number too large to fit in target type"))?;
+    }
+    Ok(calories)
+}
+
+// Parse challenge input into an `Inventory`.
+//
+// This implementation works on the raw bytes of the input, skipping UTF-8
+// validation and `str::parse`.  Unlike the other parsers it only accepts
+// unsigned numbers.
+pub fn parse_input_bytes(input: &[u8]) -> Result<Inventory> {
+    let mut elves = Vec::new();
+    let mut elf = Vec::new();
+    for (i, line) in input.split(|&byte| byte == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            if !elf.is_empty() {
+                elves.push(mem::take(&mut elf));
+            }
+        } else {
+            elf.push(parse_calories_bytes(i, line)?);
+        }
+    }
+    if !elf.is_empty() {
+        elves.push(elf);
+    }
+
+    Ok(elves.into())
+}
+
+// How forgiving `parse_input_with` is of untidy input.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ParseMode {
+    // Accept only `\n` line endings, no stray whitespace and exactly one
+    // blank line between elves.
+    Strict,
+    // Accept `\r\n` line endings and surrounding whitespace, and skip the
+    // empty elves implied by leading, trailing or repeated blank lines.
+    #[default]
+    Lenient,
+}
+
+// Parse challenge input into an `Inventory`, tolerating untidy input
+// according to `mode`.
+pub fn parse_input_with(text: &str, mode: ParseMode) -> Result<Inventory> {
+    let strict = mode == ParseMode::Strict;
+    // A final line ending does not start another line.
+    let body = text.strip_suffix('\n').unwrap_or(text);
+    if body.is_empty() {
+        return Ok(Inventory::default());
+    }
+
+    let mut elves = Vec::new();
+    let mut elf: Option<Vec<i32>> = None;
+    let mut last_blank = None;
+    for (i, raw) in body.split('\n').enumerate() {
+        let line = if strict {
+            if raw.ends_with('\r') {
+                return Err(anyhow!("Line {} has a CRLF line ending", i + 1));
+            }
+            if raw.trim() != raw {
+                return Err(anyhow!("Line {} has surrounding whitespace", i + 1));
+            }
+            raw
+        } else {
+            raw.trim()
+        };
+
+        if !line.is_empty() {
+            elf.get_or_insert_with(Vec::new)
+                .push(parse_calories(i, line)?);
+            last_blank = None;
+            continue;
+        }
+
+        match elf.take() {
+            Some(elf) => elves.push(elf),
+            None if strict => {
+                return Err(anyhow!("Line {} is blank but no elf precedes it", i + 1))
+            }
+            None => (),
+        }
+        last_blank = Some(i);
+    }
+
+    if let (true, Some(i)) = (strict, last_blank) {
+        return Err(anyhow!(
+            "Line {} is a blank line at the end of the input",
+            i + 1
+        ));
+    }
+    elves.extend(elf);
+
+    Ok(elves.into())
+}
+
+// Iterator over the total calories carried by each elf.
+pub struct ElfTotals<'a> {
+    lines: Enumerate<Lines<'a>>,
+}
+
+impl Iterator for ElfTotals<'_> {
+    type Item = Result<i32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Blank lines before an elf separate it from the previous one.
+        let mut line = self.lines.next()?;
+        while line.1.is_empty() {
+            line = self.lines.next()?;
+        }
+
+        let mut total = 0;
+        loop {
+            match parse_calories::<i32>(line.0, line.1) {
+                Ok(calories) => total += calories,
+                Err(e) => return Some(Err(e)),
+            }
+            match self.lines.next() {
+                Some(next) if !next.1.is_empty() => line = next,
+                _ => return Some(Ok(total)),
+            }
+        }
+    }
+}
+
+// Parse challenge input into per elf totals.
+//
+// This implementation works lazily from the lines of the input so memory
+// use does not grow with the number of elves.
+pub fn parse_input_iter(text: &str) -> ElfTotals<'_> {
+    ElfTotals {
+        lines: text.lines().enumerate(),
+    }
+}
+
 // Find the max calories of any elf.
 //
 // This implementation uses a straight forward imperative approach.
-pub fn find_max_calories(elves: &Vec<Vec<i32>>) -> i32 {
+pub fn find_max_calories(inventory: &Inventory) -> i32 {
     let mut max = i32::MIN;
-    for elf in elves {
+    for elf in &inventory.elves {
         let mut total_calories = 0;
-        for calories in elf {
+        for calories in &elf.items {
             total_calories += calories;
         }
 
@@ -63,14 +256,12 @@ pub fn find_max_calories(elves: &Vec<Vec<i32>>) -> i32 {
 // Find the max calories of any elf.
 //
 // This implementation uses a "fancier" more functional approach.
-pub fn find_max_calories_fancy(elves: &[Vec<i32>]) -> i32 {
-    elves
-        .iter()
-        .fold(i32::MIN, |max, elf| cmp::max(max, elf.iter().sum()))
+pub fn find_max_calories_fancy(inventory: &Inventory) -> i32 {
+    inventory.totals().fold(i32::MIN, cmp::max)
 }
 
-pub fn find_top_n_calories(elves: &[Vec<i32>], n: usize) -> Vec<i32> {
-    let mut calories: Vec<_> = elves.iter().map(|elf| elf.iter().sum()).collect();
+pub fn find_top_n_calories(inventory: &Inventory, n: usize) -> Vec<i32> {
+    let mut calories: Vec<_> = inventory.totals().collect();
 
     // A sort then a reverse has similar or better performance than using
     // sort_by():
@@ -78,11 +269,93 @@ pub fn find_top_n_calories(elves: &[Vec<i32>], n: usize) -> Vec<i32> {
     calories.sort();
     calories.reverse();
 
-    calories.resize(n, 0);
+    calories.truncate(n);
 
     calories
 }
 
+// Find the calories carried by the `n` elves carrying the most, in
+// descending order.
+//
+// This implementation keeps a bounded min-heap of the best `n` totals so far,
+// which avoids sorting every elf.  If there are fewer than `n` elves, only
+// those are returned.
+pub fn find_top_n_calories_heap(inventory: &Inventory, n: usize) -> Vec<i32> {
+    top_n(inventory.totals(), n)
+}
+
+// Find the elf carrying the most calories.  The first such elf wins ties.
+pub fn find_max_elf(inventory: &Inventory) -> Option<&Elf> {
+    inventory.max_elf()
+}
+
+// Find the `n` elves carrying the most calories, in descending order.
+pub fn find_top_n_elves(inventory: &Inventory, n: usize) -> Vec<&Elf> {
+    inventory.top_n(n)
+}
+
+// Summary statistics over elf calorie totals.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    // Totals in ascending order, used to answer percentile queries.
+    sorted: Vec<i32>,
+}
+
+impl Stats {
+    // Compute statistics over calorie totals, or `None` if there are none.
+    pub fn from_totals(totals: impl IntoIterator<Item = i32>) -> Option<Self> {
+        let mut sorted: Vec<i32> = totals.into_iter().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort();
+
+        let count = sorted.len();
+        let mean = sorted.iter().map(|&total| total as f64).sum::<f64>() / count as f64;
+        let median = if count.is_multiple_of(2) {
+            (sorted[count / 2 - 1] as f64 + sorted[count / 2] as f64) / 2.0
+        } else {
+            sorted[count / 2] as f64
+        };
+
+        Some(Self {
+            count,
+            mean,
+            median,
+            sorted,
+        })
+    }
+
+    pub fn min(&self) -> i32 {
+        self.sorted[0]
+    }
+
+    pub fn max(&self) -> i32 {
+        self.sorted[self.count - 1]
+    }
+
+    // The smallest total which at least `p` percent of elves carry no more
+    // than (the nearest rank method).
+    //
+    // Returns `None` if `p` is outside `0.0..=100.0`.
+    pub fn percentile(&self, p: f64) -> Option<i32> {
+        if !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = (p / 100.0 * self.count as f64).ceil() as usize;
+        Some(self.sorted[rank.clamp(1, self.count) - 1])
+    }
+}
+
+// Compute statistics over the elves' totals, or `None` if there are no
+// elves.
+pub fn stats(inventory: &Inventory) -> Option<Stats> {
+    inventory.stats()
+}
+
 // Compute the answer to part 1 using the imperative methods.
 pub fn part1(input: &str) -> Result<i32> {
     let elves = parse_input(input)?;
@@ -95,19 +368,246 @@ pub fn part1_fancy(input: &str) -> Result<i32> {
     Ok(find_max_calories_fancy(&elves))
 }
 
+// Compute the answer to part 1 without materializing the elves.
+pub fn part1_iter(input: &str) -> Result<i32> {
+    parse_input_iter(input).try_fold(i32::MIN, |max, total| Ok(cmp::max(max, total?)))
+}
+
 pub fn part2(input: &str) -> Result<i32> {
-    let elves = parse_input_fancy(input)?;
-    let top_calories = find_top_n_calories(&elves, 3);
+    part2_top_n(input, 3)
+}
+
+// Compute the total calories carried by the `n` elves carrying the most.
+//
+// Fails if `n` is zero or more than the number of elves, rather than
+// quietly summing fewer totals.
+pub fn part2_top_n(input: &str, n: usize) -> Result<i32> {
+    part2_with(input, n, Implementation::Fancy)
+}
+
+// Which family of functions the `_with` solutions use.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Implementation {
+    // `parse_input`, `find_max_calories` and `find_top_n_calories`.
+    #[default]
+    Imperative,
+    // `parse_input_fancy`, `find_max_calories_fancy` and
+    // `find_top_n_calories_heap`.
+    Fancy,
+}
+
+// Fail unless the top `n` of `count` elves can be picked.
+fn check_top_n(n: usize, count: usize) -> Result<()> {
+    if n == 0 || n > count {
+        return Err(anyhow!("Can't pick the top {} of {} elves", n, count));
+    }
+    Ok(())
+}
+
+// Compute the answer to part 1 using the chosen implementation.
+pub fn part1_with(input: &str, implementation: Implementation) -> Result<i32> {
+    match implementation {
+        Implementation::Imperative => part1(input),
+        Implementation::Fancy => part1_fancy(input),
+    }
+}
+
+// Compute the total calories carried by the `n` elves carrying the most
+// using the chosen implementation.  Fails like `part2_top_n`.
+pub fn part2_with(input: &str, n: usize, implementation: Implementation) -> Result<i32> {
+    let elves = match implementation {
+        Implementation::Imperative => parse_input(input)?,
+        Implementation::Fancy => parse_input_fancy(input)?,
+    };
+    check_top_n(n, elves.len())?;
+    let top_calories = match implementation {
+        Implementation::Imperative => find_top_n_calories(&elves, n),
+        Implementation::Fancy => find_top_n_calories_heap(&elves, n),
+    };
     Ok(top_calories.iter().sum())
 }
 
+// Compute the answer to part 2 without materializing the elves.  Fails like
+// `part2`.
+pub fn part2_iter(input: &str) -> Result<i32> {
+    const N: usize = 3;
+    // Kept in descending order.
+    let mut top = Vec::with_capacity(N + 1);
+    let mut count = 0;
+    for total in parse_input_iter(input) {
+        let total = total?;
+        count += 1;
+        let pos = top
+            .iter()
+            .position(|&calories| total > calories)
+            .unwrap_or(top.len());
+        if pos < N {
+            top.insert(pos, total);
+            top.truncate(N);
+        }
+    }
+
+    check_top_n(N, count)?;
+    Ok(top.iter().sum())
+}
+
+// Offset just past the first blank line starting after `from`, or the end
+// of `text` if there is none.
+fn end_of_next_blank_line(text: &str, mut from: usize) -> usize {
+    if from >= text.len() {
+        return text.len();
+    }
+    while !text.is_char_boundary(from) {
+        from += 1;
+    }
+    while let Some(newline) = text[from..].find('\n') {
+        let line_start = from + newline + 1;
+        let line_end = text[line_start..]
+            .find('\n')
+            .map_or(text.len(), |i| line_start + i + 1);
+        if text[line_start..line_end].trim().is_empty() {
+            return line_end;
+        }
+        from = line_start;
+    }
+    text.len()
+}
+
+// Split `text` into roughly `count` chunks, each ending after a blank line so
+// that no elf is split between chunks.
+fn split_into_chunks(text: &str, count: usize) -> Vec<&str> {
+    let target = text.len().div_ceil(count.max(1)).max(1);
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let end = end_of_next_blank_line(text, start + target - 1);
+        chunks.push(&text[start..end]);
+        start = end;
+    }
+    chunks
+}
+
+fn parallel_chunks(text: &str) -> Vec<&str> {
+    // Several chunks per thread help balance uneven chunk sizes.
+    split_into_chunks(text, rayon::current_num_threads() * 4)
+}
+
+// Compute the answer to part 1 by finding the maximum of each chunk of the
+// input in parallel.
+pub fn part1_parallel(input: &str) -> Result<i32> {
+    let max = parallel_chunks(input)
+        .into_par_iter()
+        .map(part1_iter)
+        .try_reduce(|| i32::MIN, |a, b| Ok(cmp::max(a, b)));
+
+    // Line numbers in errors are relative to their chunk, so rerun serially
+    // to report the failure's real position.
+    max.or_else(|_| part1_iter(input))
+}
+
+// Compute the answer to part 2 for the top `n` elves by finding the top `n`
+// of each chunk of the input in parallel and merging them.  Fails like
+// `part2_top_n`.
+pub fn part2_parallel(input: &str, n: usize) -> Result<i32> {
+    let chunk_top_n = |chunk: &str| -> Result<(usize, Vec<i32>)> {
+        let totals = parse_input_iter(chunk).collect::<Result<Vec<_>>>()?;
+        Ok((totals.len(), top_n(totals, n)))
+    };
+    let merged = parallel_chunks(input)
+        .into_par_iter()
+        .map(chunk_top_n)
+        .try_reduce(
+            || (0, Vec::new()),
+            |(count_a, top_a), (count_b, top_b)| {
+                Ok((count_a + count_b, top_n(top_a.into_iter().chain(top_b), n)))
+            },
+        );
+
+    // As in `part1_parallel`, rerun serially to report the real position of
+    // a parse error.
+    let (count, top) = merged.or_else(|e| {
+        parse_input_iter(input).collect::<Result<Vec<_>>>()?;
+        Err(e)
+    })?;
+    check_top_n(n, count)?;
+    Ok(top.iter().sum())
+}
+
+// Returned by the checked solutions when calorie totals do not fit in an
+// `i64`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CalorieOverflow {
+    // The total of the elf at this (zero based) index overflowed.
+    Elf(usize),
+    // The combined total of the top elves overflowed.
+    Combined,
+}
+
+impl fmt::Display for CalorieOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Elf(index) => write!(f, "calories carried by elf {} overflow", index + 1),
+            Self::Combined => write!(f, "combined calories of the top elves overflow"),
+        }
+    }
+}
+
+impl Error for CalorieOverflow {}
+
+// Parse challenge input into a Vec of Vecs of 64 bit calories.
+pub fn parse_input_wide(text: &str) -> Result<Vec<Vec<i64>>> {
+    blocks(text)
+        .map(|block| {
+            let first_line = first_line_index(text, block);
+            block
+                .lines()
+                .enumerate()
+                .map(|(i, line)| parse_calories(first_line + i, line))
+                .collect()
+        })
+        .collect()
+}
+
+// Total each elf's calories, failing rather than wrapping on overflow.
+pub fn checked_totals(elves: &[Vec<i64>]) -> Result<Vec<i64>, CalorieOverflow> {
+    elves
+        .iter()
+        .enumerate()
+        .map(|(index, elf)| {
+            elf.iter()
+                .try_fold(0i64, |total, &calories| total.checked_add(calories))
+                .ok_or(CalorieOverflow::Elf(index))
+        })
+        .collect()
+}
+
+// Compute the answer to part 1 using 64 bit checked arithmetic.
+pub fn part1_checked(input: &str) -> Result<i64> {
+    let totals = checked_totals(&parse_input_wide(input)?)?;
+    Ok(totals.into_iter().max().unwrap_or(i64::MIN))
+}
+
+// Compute the answer to part 2 using 64 bit checked arithmetic.  Fails like
+// `part2` when there are fewer than three elves.
+pub fn part2_checked(input: &str) -> Result<i64> {
+    let totals = checked_totals(&parse_input_wide(input)?)?;
+    check_top_n(3, totals.len())?;
+    let sum = top_n(totals, 3)
+        .into_iter()
+        .try_fold(0i64, |sum, total| sum.checked_add(total))
+        .ok_or(CalorieOverflow::Combined)?;
+    Ok(sum)
+}
+
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     const EXAMPLE_INPUT_1: &str = include_str!("example-input-1.txt");
 
-    fn parsed_example_input_1() -> Vec<Vec<i32>> {
+    fn parsed_example_input_1() -> Inventory {
         vec![
             vec![1000, 2000, 3000],
             vec![4000],
@@ -115,6 +615,7 @@ mod tests {
             vec![7000, 8000, 9000],
             vec![10000],
         ]
+        .into()
     }
 
     #[test]
@@ -133,6 +634,170 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_blank_runs() {
+        // A double blank line, and blank lines at both ends, separate elves
+        // the same way for every parser.
+        let input = "\n1\n2\n\n\n3\n\n";
+        let expected = Inventory::from(vec![vec![1, 2], vec![3]]);
+        assert_eq!(parse_input(input).unwrap(), expected);
+        assert_eq!(parse_input_fancy(input).unwrap(), expected);
+        assert_eq!(parse_input_bytes(input.as_bytes()).unwrap(), expected);
+        assert_eq!(
+            parse_input_with(input, ParseMode::Lenient).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_input_bytes() {
+        assert_eq!(
+            parse_input_bytes(EXAMPLE_INPUT_1.as_bytes()).unwrap(),
+            parsed_example_input_1()
+        );
+        assert_eq!(
+            parse_input_bytes(b"\r\n1\r\n2\r\n\r\n\r\n3").unwrap(),
+            Inventory::from(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(parse_input_bytes(b"").unwrap(), Inventory::default());
+    }
+
+    #[test]
+    fn test_parse_input_iter() {
+        assert_eq!(
+            parse_input_iter(EXAMPLE_INPUT_1)
+                .collect::<Result<Vec<_>>>()
+                .unwrap(),
+            vec![6000, 4000, 11000, 24000, 10000]
+        );
+
+        // Extra blank lines do not produce empty elves.
+        assert_eq!(
+            parse_input_iter("\n1\n2\n\n\n3\n\n")
+                .collect::<Result<Vec<_>>>()
+                .unwrap(),
+            vec![3, 3]
+        );
+
+        assert!(parse_input_iter("1\nx\n").next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_part_iter() {
+        assert_eq!(part1_iter(EXAMPLE_INPUT_1).unwrap(), 24000);
+        assert_eq!(part2_iter(EXAMPLE_INPUT_1).unwrap(), 45000);
+    }
+
+    #[test]
+    fn test_part2_iter_matches_part2() {
+        for input in [
+            "",
+            "5\n",
+            "5\n\n-3\n",
+            "-1\n\n-2\n\n-3\n\n-4\n",
+            "-1\n-2\n\n7\n\n0\n",
+        ] {
+            match (part2(input), part2_iter(input)) {
+                (Ok(expected), Ok(total)) => assert_eq!(total, expected, "{input:?}"),
+                (Err(expected), Err(error)) => {
+                    assert_eq!(error.to_string(), expected.to_string(), "{input:?}")
+                }
+                (expected, total) => panic!("{input:?}: {total:?}, expected {expected:?}"),
+            }
+        }
+        assert_eq!(part2_iter("-1\n\n-2\n\n-3\n\n-4\n").unwrap(), -6);
+        assert_eq!(
+            part2_iter("5\n\n-3\n").unwrap_err().to_string(),
+            "Can't pick the top 3 of 2 elves"
+        );
+    }
+
+    const MALFORMED_INPUT: &str = "1000\n2000\n\n\n3000\n4x00\n5000\n";
+    const MALFORMED_ERROR: &str = "Error parsing line 6 '4x00': invalid digit found in string";
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input(MALFORMED_INPUT).unwrap_err().to_string(),
+            MALFORMED_ERROR
+        );
+        assert_eq!(
+            parse_input_fancy(MALFORMED_INPUT).unwrap_err().to_string(),
+            MALFORMED_ERROR
+        );
+        assert_eq!(
+            parse_input_wide(MALFORMED_INPUT).unwrap_err().to_string(),
+            MALFORMED_ERROR
+        );
+        assert_eq!(
+            part1_iter(MALFORMED_INPUT).unwrap_err().to_string(),
+            MALFORMED_ERROR
+        );
+        assert_eq!(
+            parse_input_bytes(MALFORMED_INPUT.as_bytes())
+                .unwrap_err()
+                .to_string(),
+            MALFORMED_ERROR
+        );
+    }
+
+    #[test]
+    fn test_parse_modes_tidy_input() {
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            assert_eq!(
+                parse_input_with(EXAMPLE_INPUT_1, mode).unwrap(),
+                parsed_example_input_1()
+            );
+            assert_eq!(parse_input_with("", mode).unwrap(), Inventory::default());
+            assert_eq!(
+                parse_input_with("1\n\n2", mode).unwrap(),
+                Inventory::from(vec![vec![1], vec![2]])
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient() {
+        let untidy = "\r\n 1 \r\n2\t\r\n\r\n  \n\n3\r\n\r\n\r\n";
+        assert_eq!(
+            parse_input_with(untidy, ParseMode::Lenient).unwrap(),
+            Inventory::from(vec![vec![1, 2], vec![3]])
+        );
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let error = |input| {
+            parse_input_with(input, ParseMode::Strict)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(error("1\r\n2\r\n"), "Line 1 has a CRLF line ending");
+        assert_eq!(error("1\n2 \n"), "Line 2 has surrounding whitespace");
+        assert_eq!(
+            error("1\n\n\n2\n"),
+            "Line 3 is blank but no elf precedes it"
+        );
+        assert_eq!(error("\n1\n"), "Line 1 is blank but no elf precedes it");
+        assert_eq!(
+            error("1\n\n"),
+            "Line 2 is a blank line at the end of the input"
+        );
+        assert_eq!(
+            error("1\nx\n"),
+            "Error parsing line 2 'x': invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_out_of_range() {
+        let expected = "Error parsing line 2 '99999999999': number too large to fit in target type";
+        let error = parse_input("1\n99999999999\n").unwrap_err();
+        assert_eq!(error.to_string(), expected);
+        let error = parse_input_bytes(b"1\n99999999999\n").unwrap_err();
+        assert_eq!(error.to_string(), expected);
+    }
+
     #[test]
     fn test_find_max_calories() {
         let elves = parsed_example_input_1();
@@ -145,6 +810,57 @@ mod tests {
         assert_eq!(find_max_calories_fancy(&elves), 24000);
     }
 
+    #[test]
+    fn test_find_max_elf() {
+        let elves = parsed_example_input_1();
+        assert_eq!(
+            find_max_elf(&elves),
+            Some(&Elf {
+                index: 3,
+                items: vec![7000, 8000, 9000],
+                total: 24000
+            })
+        );
+        assert_eq!(find_max_elf(&Inventory::default()), None);
+    }
+
+    #[test]
+    fn test_find_top_n_elves() {
+        let elves = parsed_example_input_1();
+        let top: Vec<_> = find_top_n_elves(&elves, 3)
+            .iter()
+            .map(|elf| (elf.index, elf.total))
+            .collect();
+        assert_eq!(top, vec![(3, 24000), (2, 11000), (4, 10000)]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = stats(&parsed_example_input_1()).unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.mean, 11000.0);
+        assert_eq!(stats.median, 10000.0);
+        assert_eq!(stats.min(), 4000);
+        assert_eq!(stats.max(), 24000);
+        assert_eq!(stats.percentile(0.0), Some(4000));
+        assert_eq!(stats.percentile(40.0), Some(6000));
+        assert_eq!(stats.percentile(50.0), Some(10000));
+        assert_eq!(stats.percentile(100.0), Some(24000));
+        assert_eq!(stats.percentile(101.0), None);
+    }
+
+    #[test]
+    fn test_stats_even_count() {
+        let stats = stats(&vec![vec![1], vec![2], vec![3], vec![10]].into()).unwrap();
+        assert_eq!(stats.median, 2.5);
+        assert_eq!(stats.mean, 4.0);
+    }
+
+    #[test]
+    fn test_stats_empty() {
+        assert_eq!(stats(&Inventory::default()), None);
+    }
+
     #[test]
     fn test_part1() {
         assert_eq!(part1(EXAMPLE_INPUT_1).unwrap(), 24000);
@@ -156,8 +872,182 @@ mod tests {
         assert_eq!(find_top_n_calories(&elves, 3), vec![24000, 11000, 10000]);
     }
 
+    #[test]
+    fn test_find_top_n_calories_heap() {
+        let elves = parsed_example_input_1();
+        assert_eq!(
+            find_top_n_calories_heap(&elves, 3),
+            vec![24000, 11000, 10000]
+        );
+        assert_eq!(find_top_n_calories_heap(&elves, 0), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_find_top_n_more_than_elves() {
+        // Asking for more elves than exist does not pad with zeros.
+        let elves = parsed_example_input_1();
+        let expected = vec![24000, 11000, 10000, 6000, 4000];
+        assert_eq!(find_top_n_calories(&elves, 10), expected);
+        assert_eq!(find_top_n_calories_heap(&elves, 10), expected);
+    }
+
+    #[test]
+    fn test_part_checked() {
+        assert_eq!(part1_checked(EXAMPLE_INPUT_1).unwrap(), 24000);
+        assert_eq!(part2_checked(EXAMPLE_INPUT_1).unwrap(), 45000);
+
+        // Totals which overflow an i32 are fine.
+        let big = format!("{}\n{}\n\n1\n", i32::MAX, i32::MAX);
+        assert_eq!(part1_checked(&big).unwrap(), 2 * i32::MAX as i64);
+    }
+
+    #[test]
+    fn test_part2_checked_matches_part2() {
+        for input in [
+            EXAMPLE_INPUT_1,
+            "",
+            "5\n",
+            "5\n\n-3\n",
+            "-1\n\n-2\n\n-3\n\n-4\n",
+        ] {
+            match (part2(input), part2_checked(input)) {
+                (Ok(expected), Ok(total)) => assert_eq!(total, expected as i64, "{input:?}"),
+                (Err(expected), Err(error)) => {
+                    assert_eq!(error.to_string(), expected.to_string(), "{input:?}")
+                }
+                (expected, total) => panic!("{input:?}: {total:?}, expected {expected:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_checked_overflow() {
+        let input = format!("1\n\n{}\n1\n", i64::MAX);
+        let error = part1_checked(&input).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CalorieOverflow>(),
+            Some(&CalorieOverflow::Elf(1))
+        );
+
+        let input = format!("{}\n\n{}\n\n1\n", i64::MAX, i64::MAX);
+        let error = part2_checked(&input).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CalorieOverflow>(),
+            Some(&CalorieOverflow::Combined)
+        );
+    }
+
     #[test]
     fn test_part2() {
         assert_eq!(part2(EXAMPLE_INPUT_1).unwrap(), 45000);
     }
+
+    #[test]
+    fn test_implementations_agree() {
+        for implementation in [Implementation::Imperative, Implementation::Fancy] {
+            assert_eq!(part1_with(EXAMPLE_INPUT_1, implementation).unwrap(), 24000);
+            assert_eq!(
+                part2_with(EXAMPLE_INPUT_1, 3, implementation).unwrap(),
+                45000
+            );
+            assert!(part2_with(EXAMPLE_INPUT_1, 6, implementation).is_err());
+        }
+    }
+
+    #[test]
+    fn test_split_into_chunks() {
+        let chunks = split_into_chunks(EXAMPLE_INPUT_1, 3);
+        assert_eq!(chunks.concat(), EXAMPLE_INPUT_1);
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.ends_with("\n\n"), "{chunk:?}");
+        }
+        assert!(chunks.len() > 1);
+
+        assert_eq!(split_into_chunks("", 4), Vec::<&str>::new());
+        assert_eq!(split_into_chunks("1\n2\n", 4), vec!["1\n2\n"]);
+    }
+
+    #[test]
+    fn test_parallel() {
+        let large = [EXAMPLE_INPUT_1; 100].join("\n");
+        assert_eq!(part1_parallel(&large).unwrap(), 24000);
+        assert_eq!(part2_parallel(&large, 3).unwrap(), 72000);
+        assert_eq!(part2_parallel(EXAMPLE_INPUT_1, 3).unwrap(), 45000);
+        assert!(part2_parallel(EXAMPLE_INPUT_1, 6).is_err());
+    }
+
+    #[test]
+    fn test_parallel_error_line() {
+        // 50 copies of the 14 line example separated by blank lines, then
+        // a blank line and the bad line.
+        let mut input = [EXAMPLE_INPUT_1; 50].join("\n");
+        input.push_str("\nx\n");
+        let expected = format!(
+            "Error parsing line {} 'x': invalid digit found in string",
+            50 * 15 + 1
+        );
+        assert_eq!(part1_parallel(&input).unwrap_err().to_string(), expected);
+        assert_eq!(part2_parallel(&input, 3).unwrap_err().to_string(), expected);
+    }
+
+    #[test]
+    fn test_part2_top_n() {
+        assert_eq!(part2_top_n(EXAMPLE_INPUT_1, 1).unwrap(), 24000);
+        assert_eq!(part2_top_n(EXAMPLE_INPUT_1, 5).unwrap(), 55000);
+        assert!(part2_top_n(EXAMPLE_INPUT_1, 0).is_err());
+        assert!(part2_top_n(EXAMPLE_INPUT_1, 6).is_err());
+    }
+
+    // Elves carrying nothing render as extra blank lines, including at the
+    // start and end of the input.
+    fn elves_strategy() -> impl Strategy<Value = Vec<Vec<i32>>> {
+        prop::collection::vec(prop::collection::vec(0..100_000, 0..8), 0..40)
+    }
+
+    fn render(elves: &[Vec<i32>]) -> String {
+        elves
+            .iter()
+            .map(|elf| {
+                elf.iter()
+                    .map(|calories| calories.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parsers_agree(elves in elves_strategy()) {
+            let input = render(&elves);
+            // Blank lines separate elves but never make empty ones.
+            let expected = Inventory::from(
+                elves.into_iter().filter(|elf| !elf.is_empty()).collect::<Vec<_>>(),
+            );
+            prop_assert_eq!(&parse_input(&input).unwrap(), &expected);
+            prop_assert_eq!(&parse_input_fancy(&input).unwrap(), &expected);
+            prop_assert_eq!(&parse_input_bytes(input.as_bytes()).unwrap(), &expected);
+            prop_assert_eq!(
+                &parse_input_with(&input, ParseMode::Lenient).unwrap(),
+                &expected
+            );
+            prop_assert_eq!(part1_iter(&input).unwrap(), part1(&input).unwrap());
+        }
+
+        #[test]
+        fn prop_max_calories_agree(elves in elves_strategy()) {
+            let inventory = Inventory::from(elves);
+            prop_assert_eq!(find_max_calories(&inventory), find_max_calories_fancy(&inventory));
+        }
+
+        #[test]
+        fn prop_top_n_agree(elves in elves_strategy(), n in 0usize..50) {
+            let inventory = Inventory::from(elves);
+            prop_assert_eq!(
+                find_top_n_calories(&inventory, n),
+                find_top_n_calories_heap(&inventory, n)
+            );
+        }
+    }
 }
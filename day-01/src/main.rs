@@ -1,33 +1,120 @@
-use std::fs;
-use std::path::PathBuf;
-
-use anyhow::Result;
+use anyhow::{bail, Result};
+use aoc_core::CommonArgs;
+use aoc_viz::Histogram;
 use clap::Parser;
-use day_01_lib::{part1, part1_fancy, part2};
+use day_01_lib::{part1_with, part2_with, Elf, Implementation, Inventory};
+
+// Print where an elf appears in the input and what it carries.
+fn print_elf(elf: &Elf) {
+    println!(
+        "    Elf {} carries {:?} = {}",
+        elf.index + 1,
+        elf.items,
+        elf.total
+    );
+}
+
+// Run `solve` with the implementation selected on the command line, or with
+// both when comparing them.
+fn solve_with(args: &Args, solve: impl Fn(Implementation) -> Result<i32>) -> Result<i32> {
+    if !args.compare {
+        let implementation = if args.fancy {
+            Implementation::Fancy
+        } else {
+            Implementation::Imperative
+        };
+        return solve(implementation);
+    }
+
+    let imperative = solve(Implementation::Imperative)?;
+    let fancy = solve(Implementation::Fancy)?;
+    if imperative != fancy {
+        bail!("Implementations disagree: imperative {imperative}, fancy {fancy}");
+    }
+    Ok(imperative)
+}
+
+const EXAMPLE_INPUT: &str = include_str!("../../day-01-lib/src/example-input-1.txt");
 
 // Command line arguments.
 #[derive(Debug, Parser)]
 struct Args {
-    input: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Use the functional implementation instead of the imperative one
+    #[arg(long)]
+    fancy: bool,
+
+    /// Run both implementations and fail if their answers differ
+    #[arg(long, conflicts_with = "fancy")]
+    compare: bool,
+
+    /// Number of elves to total in part 2
+    #[arg(long, default_value_t = 3)]
+    top: usize,
+
+    /// Print statistics about the elves' calorie totals
+    #[arg(long)]
+    stats: bool,
+
+    /// Draw a histogram of the elves' calorie totals with this many bins
+    #[arg(long, value_name = "BINS", num_args = 0..=1, default_missing_value = "10")]
+    chart: Option<usize>,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
+    args.common.init_logging();
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
+    // Parsed separately from the solutions for reporting individual elves.
+    let inventory: Inventory = input.parse()?;
 
-    let input = fs::read_to_string(&args.input)?;
+    if let Some(calories) = args.common.run_part(1, || {
+        solve_with(&args, |implementation| part1_with(&input, implementation))
+    })? {
+        println!("[Part 1] Most calories carried by an elf: {}", calories);
+        if let Some(elf) = inventory.max_elf() {
+            print_elf(elf);
+        }
+    }
 
-    // Compute the answer both ways and assert that they match.
-    let calories = part1(&input)?;
-    let calories_fancy = part1_fancy(&input)?;
-    assert_eq!(calories, calories_fancy);
+    if let Some(top_calories) = args.common.run_part(2, || {
+        solve_with(&args, |implementation| {
+            part2_with(&input, args.top, implementation)
+        })
+    })? {
+        println!(
+            "[Part 2] Calories carried by top {} elves: {}",
+            args.top, top_calories
+        );
+        for elf in inventory.top_n(args.top) {
+            print_elf(elf);
+        }
+    }
 
-    println!("[Part 1] Most calories carried by an elf: {}", calories);
+    if args.stats {
+        match inventory.stats() {
+            Some(stats) => {
+                println!("[Stats] Elves: {}", stats.count);
+                println!("[Stats] Min: {} Max: {}", stats.min(), stats.max());
+                println!(
+                    "[Stats] Mean: {:.1} Median: {:.1}",
+                    stats.mean, stats.median
+                );
+                for p in [25.0, 75.0, 90.0, 99.0] {
+                    // Every percentile is within 0..=100.
+                    println!("[Stats] p{}: {}", p, stats.percentile(p).unwrap());
+                }
+            }
+            None => println!("[Stats] No elves"),
+        }
+    }
 
-    let top_3_calories = part2(&input)?;
-    println!(
-        "[Part 2] Calories carried by top 3 elevs: {}",
-        top_3_calories
-    );
+    if let Some(bins) = args.chart {
+        let totals = inventory.totals().map(i64::from);
+        print!("{}", Histogram::new(totals, bins.max(1)).render(40));
+    }
 
     Ok(())
 }
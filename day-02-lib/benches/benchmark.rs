@@ -0,0 +1,39 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const INPUT: &str = include_str!("../../day-02/input.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("part1", |b| b.iter(|| day_02_lib::part1(INPUT).unwrap()));
+    c.bench_function("part2", |b| b.iter(|| day_02_lib::part2(INPUT).unwrap()));
+}
+
+// A guide of `rounds` lines cycling through every combination of letters in
+// a scrambled order.
+fn synthetic_guide(rounds: usize) -> String {
+    let mut guide = String::with_capacity(rounds * 4);
+    for i in 0..rounds {
+        let combination = i * 7 % 9;
+        guide.push(char::from(b'A' + (combination / 3) as u8));
+        guide.push(' ');
+        guide.push(char::from(b'X' + (combination % 3) as u8));
+        guide.push('\n');
+    }
+    guide
+}
+
+fn table_benchmark(c: &mut Criterion) {
+    let guide = synthetic_guide(100_000);
+    let mut group = c.benchmark_group("large_guide");
+    group.bench_function("part1", |b| b.iter(|| day_02_lib::part1(&guide).unwrap()));
+    group.bench_function("part1_table", |b| {
+        b.iter(|| day_02_lib::part1_table(&guide).unwrap())
+    });
+    group.bench_function("part2", |b| b.iter(|| day_02_lib::part2(&guide).unwrap()));
+    group.bench_function("part2_table", |b| {
+        b.iter(|| day_02_lib::part2_table(&guide).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, table_benchmark);
+criterion_main!(benches);
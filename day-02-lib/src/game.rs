@@ -0,0 +1,236 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::Ruleset;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Move {
+    Rock,
+    Paper,
+    Scissors,
+    // Only part of the lizard-Spock ruleset.
+    Lizard,
+    Spock,
+}
+
+// The opponent's moves are written A, B and C in the strategy guide, with D
+// and E for the extra lizard-Spock moves.
+impl FromStr for Move {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" => Ok(Self::Rock),
+            "B" => Ok(Self::Paper),
+            "C" => Ok(Self::Scissors),
+            "D" => Ok(Self::Lizard),
+            "E" => Ok(Self::Spock),
+            _ => Err(anyhow!("unknown move type: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Outcome {
+    Loss,
+    Tie,
+    Win,
+}
+
+impl Outcome {
+    // The outcome for us of playing `ours` against `opponent`.  Both moves
+    // must belong to `ruleset`.  The inverse of `calc_move`.
+    pub fn from_moves(opponent: &Move, ours: &Move, ruleset: &Ruleset) -> Self {
+        match ruleset.game().outcome(*ours, *opponent) {
+            aoc_utils::Outcome::Loss => Self::Loss,
+            aoc_utils::Outcome::Draw => Self::Tie,
+            aoc_utils::Outcome::Win => Self::Win,
+        }
+    }
+
+    // The move we need to play against `opponent` to get this outcome.  With
+    // more than one such move, the one closest to `opponent` in the
+    // ruleset's cycle is picked.
+    pub fn calc_move(&self, opponent: &Move, ruleset: &Ruleset) -> Move {
+        ruleset
+            .game()
+            .move_for_outcome(*opponent, self.to_game_outcome())
+    }
+
+    pub(crate) fn to_game_outcome(self) -> aoc_utils::Outcome {
+        match self {
+            Self::Loss => aoc_utils::Outcome::Loss,
+            Self::Tie => aoc_utils::Outcome::Draw,
+            Self::Win => aoc_utils::Outcome::Win,
+        }
+    }
+}
+
+// A round with both moves decided.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Round {
+    pub opponent: Move,
+    pub ours: Move,
+}
+
+impl Round {
+    // Both moves must belong to `ruleset`.
+    pub fn score(&self, ruleset: &Ruleset) -> i32 {
+        self.shape_score(ruleset) + self.outcome_score(ruleset)
+    }
+
+    // The part of `score` earned by the move we played.
+    pub fn shape_score(&self, ruleset: &Ruleset) -> i32 {
+        ruleset.move_score(self.ours)
+    }
+
+    // The part of `score` earned by the outcome of the round.
+    pub fn outcome_score(&self, ruleset: &Ruleset) -> i32 {
+        ruleset.outcome_score(self.outcome(ruleset))
+    }
+
+    // The outcome for us.  Both moves must belong to `ruleset`.
+    pub fn outcome(&self, ruleset: &Ruleset) -> Outcome {
+        Outcome::from_moves(&self.opponent, &self.ours, ruleset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_move() {
+        assert_eq!(Move::Rock, "A".parse().unwrap());
+        assert_eq!(Move::Paper, "B".parse().unwrap());
+        assert_eq!(Move::Scissors, "C".parse().unwrap());
+
+        assert_eq!(Move::Lizard, "D".parse().unwrap());
+        assert_eq!(Move::Spock, "E".parse().unwrap());
+
+        assert!("".parse::<Move>().is_err());
+        assert!("F".parse::<Move>().is_err());
+        assert!("X".parse::<Move>().is_err());
+    }
+
+    #[test]
+    fn test_outcome_move() {
+        // This is a bit of a "change detector" test but does verify core
+        // game logic.
+        let rules = Ruleset::standard();
+        assert_eq!(Outcome::Loss.calc_move(&Move::Rock, &rules), Move::Scissors);
+        assert_eq!(Outcome::Loss.calc_move(&Move::Paper, &rules), Move::Rock);
+        assert_eq!(
+            Outcome::Loss.calc_move(&Move::Scissors, &rules),
+            Move::Paper
+        );
+
+        assert_eq!(Outcome::Tie.calc_move(&Move::Rock, &rules), Move::Rock);
+        assert_eq!(Outcome::Tie.calc_move(&Move::Paper, &rules), Move::Paper);
+        assert_eq!(
+            Outcome::Tie.calc_move(&Move::Scissors, &rules),
+            Move::Scissors
+        );
+
+        assert_eq!(Outcome::Win.calc_move(&Move::Rock, &rules), Move::Paper);
+        assert_eq!(Outcome::Win.calc_move(&Move::Paper, &rules), Move::Scissors);
+        assert_eq!(Outcome::Win.calc_move(&Move::Scissors, &rules), Move::Rock);
+    }
+
+    #[test]
+    fn outcome_from_moves() {
+        let rules = Ruleset::standard();
+        assert_eq!(
+            Outcome::from_moves(&Move::Rock, &Move::Paper, &rules),
+            Outcome::Win
+        );
+        assert_eq!(
+            Outcome::from_moves(&Move::Rock, &Move::Scissors, &rules),
+            Outcome::Loss
+        );
+        assert_eq!(
+            Outcome::from_moves(&Move::Rock, &Move::Rock, &rules),
+            Outcome::Tie
+        );
+    }
+
+    #[test]
+    fn from_moves_inverts_calc_move() {
+        let rules = Ruleset::standard();
+        // All nine pairs of moves.  Each outcome needs exactly one move in
+        // three move games, so the two functions are inverses.
+        for &opponent in rules.moves() {
+            for &ours in rules.moves() {
+                let outcome = Outcome::from_moves(&opponent, &ours, &rules);
+                assert_eq!(
+                    outcome.calc_move(&opponent, &rules),
+                    ours,
+                    "{opponent:?} {ours:?}"
+                );
+            }
+        }
+
+        // With more moves only one direction holds.
+        let rules = Ruleset::lizard_spock();
+        for &opponent in rules.moves() {
+            for outcome in [Outcome::Loss, Outcome::Tie, Outcome::Win] {
+                let ours = outcome.calc_move(&opponent, &rules);
+                assert_eq!(
+                    Outcome::from_moves(&opponent, &ours, &rules),
+                    outcome,
+                    "{opponent:?} {outcome:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn round_outcome() {
+        let rules = Ruleset::standard();
+        let round = |opponent, ours| Round { opponent, ours }.outcome(&rules);
+        assert_eq!(round(Move::Rock, Move::Paper), Outcome::Win);
+        assert_eq!(round(Move::Paper, Move::Rock), Outcome::Loss);
+        assert_eq!(round(Move::Scissors, Move::Scissors), Outcome::Tie);
+    }
+
+    #[test]
+    fn round_score() {
+        assert_eq!(
+            Round {
+                opponent: Move::Rock,
+                ours: Move::Paper
+            }
+            .score(&Ruleset::standard()),
+            8
+        );
+        assert_eq!(
+            Round {
+                opponent: Move::Paper,
+                ours: Move::Rock,
+            }
+            .score(&Ruleset::standard()),
+            1
+        );
+        assert_eq!(
+            Round {
+                opponent: Move::Scissors,
+                ours: Move::Scissors,
+            }
+            .score(&Ruleset::standard()),
+            6
+        );
+    }
+
+    #[test]
+    fn score_breakdown() {
+        let rules = Ruleset::standard();
+        let round = Round {
+            opponent: Move::Rock,
+            ours: Move::Paper,
+        };
+        assert_eq!(round.shape_score(&rules), 2);
+        assert_eq!(round.outcome_score(&rules), 6);
+    }
+}
@@ -0,0 +1,326 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, bail, Error, Result};
+use aoc_parse::{parse_complete, IResult};
+use nom::{
+    branch::alt,
+    character::complete::{line_ending, multispace0, one_of, space0, space1},
+    combinator::{cut, eof, map, map_res, recognize},
+    error::context,
+    multi::many_till,
+    sequence::{preceded, terminated},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{Move, Outcome, Round, Ruleset};
+
+// The second column of the strategy guide.  What it means depends on the
+// puzzle part, see `Interpretation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Response {
+    // V and W only appear in lizard-Spock guides, where they are the extra
+    // moves.
+    V,
+    W,
+    X,
+    Y,
+    Z,
+}
+
+impl FromStr for Response {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "V" => Ok(Self::V),
+            "W" => Ok(Self::W),
+            "X" => Ok(Self::X),
+            "Y" => Ok(Self::Y),
+            "Z" => Ok(Self::Z),
+            _ => Err(anyhow!("unknown response: {}", s)),
+        }
+    }
+}
+
+// How to read the second column of the strategy guide.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Interpretation {
+    // Part 1: X, Y and Z (and V and W) are the moves we play.
+    Move,
+    // Part 2: X, Y and Z are the outcomes we need.
+    Outcome,
+}
+
+impl Interpretation {
+    pub fn our_move(self, opponent: Move, response: Response, ruleset: &Ruleset) -> Result<Move> {
+        let ours = match self {
+            Self::Move => match response {
+                Response::V => Move::Lizard,
+                Response::W => Move::Spock,
+                Response::X => Move::Rock,
+                Response::Y => Move::Paper,
+                Response::Z => Move::Scissors,
+            },
+            Self::Outcome => {
+                let outcome = match response {
+                    Response::X => Outcome::Loss,
+                    Response::Y => Outcome::Tie,
+                    Response::Z => Outcome::Win,
+                    Response::V | Response::W => bail!("{:?} is not an outcome", response),
+                };
+                outcome.calc_move(&opponent, ruleset)
+            }
+        };
+        ruleset.check(ours)?;
+        Ok(ours)
+    }
+}
+
+// A line of the strategy guide.
+#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Entry {
+    pub opponent: Move,
+    pub response: Response,
+}
+
+impl Entry {
+    // Parse an entry, including any whitespace trailing it on its line.
+    fn parse(input: &str) -> IResult<'_, Self> {
+        context("round", |input| {
+            let (input, opponent) = opponent_move(input)?;
+            let (input, _) = space1(input)?;
+            let (input, response) = response(input)?;
+            let (input, _) = space0(input)?;
+
+            Ok((input, Self { opponent, response }))
+        })(input)
+    }
+
+    // Fails if either move is not part of `ruleset`.
+    pub fn round(&self, interpretation: Interpretation, ruleset: &Ruleset) -> Result<Round> {
+        ruleset.check(self.opponent)?;
+        Ok(Round {
+            opponent: self.opponent,
+            ours: interpretation.our_move(self.opponent, self.response, ruleset)?,
+        })
+    }
+}
+
+fn opponent_move(input: &str) -> IResult<'_, Move> {
+    context(
+        "opponent move",
+        map_res(recognize(one_of("ABCDE")), str::parse),
+    )(input)
+}
+
+fn response(input: &str) -> IResult<'_, Response> {
+    context("response", map_res(recognize(one_of("VWXYZ")), str::parse))(input)
+}
+
+impl FromStr for Entry {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_complete(s, Self::parse).map_err(|e| anyhow!("Error parsing round: {}", e))
+    }
+}
+
+// Parse every entry in the guide so errors report their line in the file.
+// `cut` stops a malformed line from being mistaken for the end of the list.
+pub fn parse_strategy_guide(s: &str) -> Result<Vec<Entry>> {
+    parse_complete(
+        s,
+        map(
+            many_till(
+                terminated(
+                    cut(Entry::parse),
+                    cut(context("end of line", alt((line_ending, eof)))),
+                ),
+                preceded(multispace0, eof),
+            ),
+            |(entries, _)| entries,
+        ),
+    )
+    .map_err(|e| anyhow!("Error parsing strategy guide: {}", e))
+}
+
+pub fn game_score(
+    guide: &[Entry],
+    interpretation: Interpretation,
+    ruleset: &Ruleset,
+) -> Result<i32> {
+    guide
+        .iter()
+        .map(|entry| Ok(entry.round(interpretation, ruleset)?.score(ruleset)))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn parse_response() {
+        assert_eq!(Response::X, "X".parse().unwrap());
+        assert_eq!(Response::Y, "Y".parse().unwrap());
+        assert_eq!(Response::Z, "Z".parse().unwrap());
+
+        assert_eq!(Response::V, "V".parse().unwrap());
+        assert_eq!(Response::W, "W".parse().unwrap());
+
+        assert!("".parse::<Response>().is_err());
+        assert!("A".parse::<Response>().is_err());
+    }
+
+    #[test]
+    fn parse_entry() {
+        assert_eq!(
+            Entry {
+                opponent: Move::Rock,
+                response: Response::Y
+            },
+            "A Y".parse().unwrap()
+        );
+
+        assert!("".parse::<Entry>().is_err());
+        assert!("A".parse::<Entry>().is_err());
+        assert!("A Y Z".parse::<Entry>().is_err());
+        assert!("Y Y".parse::<Entry>().is_err());
+
+        // Any run of spaces or tabs separates the columns.
+        assert_eq!(
+            "B\tX ".parse::<Entry>().unwrap(),
+            Entry {
+                opponent: Move::Paper,
+                response: Response::X
+            }
+        );
+    }
+
+    #[test]
+    fn untidy_guide() {
+        assert_eq!(
+            parse_strategy_guide("A Y  \r\nB X\r\nC Z\r\n\r\n").unwrap(),
+            parse_strategy_guide(EXAMPLE_INPUT).unwrap()
+        );
+        assert_eq!(parse_strategy_guide("").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let error = |input| parse_strategy_guide(input).unwrap_err().to_string();
+        assert_eq!(
+            error("A Y\nB Q\n"),
+            "Error parsing strategy guide: line 2, column 3: expected response while parsing round\n  B Q\n    ^"
+        );
+        assert_eq!(
+            error("A Y\nX Y\n"),
+            "Error parsing strategy guide: line 2, column 1: expected opponent move while parsing round\n  X Y\n  ^"
+        );
+        assert_eq!(
+            error("A YZ\n"),
+            "Error parsing strategy guide: line 1, column 4: expected end of line\n  A YZ\n     ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_strategy_guide() {
+        assert_eq!(
+            parse_strategy_guide(EXAMPLE_INPUT).unwrap(),
+            vec![
+                Entry {
+                    opponent: Move::Rock,
+                    response: Response::Y,
+                },
+                Entry {
+                    opponent: Move::Paper,
+                    response: Response::X,
+                },
+                Entry {
+                    opponent: Move::Scissors,
+                    response: Response::Z,
+                },
+            ]
+        )
+    }
+
+    #[test]
+    fn interpretations() {
+        let entry: Entry = "A Y".parse().unwrap();
+        assert_eq!(
+            entry
+                .round(Interpretation::Move, &Ruleset::standard())
+                .unwrap(),
+            Round {
+                opponent: Move::Rock,
+                ours: Move::Paper
+            }
+        );
+        assert_eq!(
+            entry
+                .round(Interpretation::Outcome, &Ruleset::standard())
+                .unwrap(),
+            Round {
+                opponent: Move::Rock,
+                ours: Move::Rock
+            }
+        );
+    }
+
+    #[test]
+    fn moves_outside_ruleset() {
+        let spock: Entry = "E X".parse().unwrap();
+        assert!(spock
+            .round(Interpretation::Move, &Ruleset::standard())
+            .is_err());
+        assert!(spock
+            .round(Interpretation::Move, &Ruleset::lizard_spock())
+            .is_ok());
+
+        let lizard: Entry = "A V".parse().unwrap();
+        assert!(lizard
+            .round(Interpretation::Move, &Ruleset::standard())
+            .is_err());
+        assert_eq!(
+            lizard
+                .round(Interpretation::Move, &Ruleset::lizard_spock())
+                .unwrap()
+                .ours,
+            Move::Lizard
+        );
+        assert!(lizard
+            .round(Interpretation::Outcome, &Ruleset::lizard_spock())
+            .is_err());
+    }
+
+    #[test]
+    fn lizard_spock_game_score() {
+        let guide = parse_strategy_guide("E V\nD Z\n").unwrap();
+        // Lizard (5) beats Spock (4) for 11, then Scissors (3) beats Lizard
+        // for 9.
+        assert_eq!(
+            game_score(&guide, Interpretation::Move, &Ruleset::lizard_spock()).unwrap(),
+            20
+        );
+        // Win against Spock with Lizard (11) and against Lizard with Rock,
+        // the closest winning move, (7).
+        let guide = parse_strategy_guide("E Z\nD Z\n").unwrap();
+        assert_eq!(
+            game_score(&guide, Interpretation::Outcome, &Ruleset::lizard_spock()).unwrap(),
+            18
+        );
+    }
+
+    #[test]
+    fn test_game_score() {
+        let guide = parse_strategy_guide(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            game_score(&guide, Interpretation::Move, &Ruleset::standard()).unwrap(),
+            15
+        );
+        assert_eq!(
+            game_score(&guide, Interpretation::Outcome, &Ruleset::standard()).unwrap(),
+            12
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+
+mod game;
+mod guide;
+mod ruleset;
+mod table;
+mod tournament;
+
+pub use game::{Move, Outcome, Round};
+pub use guide::{game_score, parse_strategy_guide, Entry, Interpretation, Response};
+pub use ruleset::Ruleset;
+pub use table::{part1_table, part2_table, ScoreTable};
+pub use tournament::{play_tournament, Player, Standing, Strategy};
+
+// Compute the answer to part 1.
+pub fn part1(input: &str) -> Result<i32> {
+    part1_with(input, &Ruleset::standard())
+}
+
+// Compute the answer to part 2.
+pub fn part2(input: &str) -> Result<i32> {
+    part2_with(input, &Ruleset::standard())
+}
+
+// Compute the answer to part 1 playing under `ruleset`.
+pub fn part1_with(input: &str, ruleset: &Ruleset) -> Result<i32> {
+    let guide = parse_strategy_guide(input)?;
+    game_score(&guide, Interpretation::Move, ruleset)
+}
+
+// Compute the answer to part 2 playing under `ruleset`.
+pub fn part2_with(input: &str, ruleset: &Ruleset) -> Result<i32> {
+    let guide = parse_strategy_guide(input)?;
+    game_score(&guide, Interpretation::Outcome, ruleset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 12);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, bail, Error, Result};
+use aoc_utils::{CyclicGame, Scoring};
+
+use crate::{Move, Outcome};
+
+// Everything that varies between games: which moves may be played, which
+// beats which, and how many points each move and outcome is worth.
+//
+// The moves are listed as a cycle in which every move beats the moves an odd
+// number of steps behind it (see `CyclicGame`), so any odd number of moves
+// can be played without writing out who beats whom.
+#[derive(Clone, Debug)]
+pub struct Ruleset {
+    name: String,
+    game: CyclicGame<Move>,
+}
+
+impl Ruleset {
+    // Panics if `cycle` has an even number of moves or `scoring` doesn't
+    // have a score for each of them.
+    pub fn new(name: &str, cycle: Vec<Move>, scoring: Scoring) -> Self {
+        Self {
+            name: name.to_owned(),
+            game: CyclicGame::new(cycle).with_scoring(scoring),
+        }
+    }
+
+    // Rock, paper, scissors, scored as in the puzzle.
+    pub fn standard() -> Self {
+        Self::new(
+            "standard",
+            vec![Move::Rock, Move::Paper, Move::Scissors],
+            Scoring {
+                moves: vec![1, 2, 3],
+                loss: 0,
+                draw: 3,
+                win: 6,
+            },
+        )
+    }
+
+    // Rock, paper, scissors, lizard, Spock.  The extra moves are worth 4 and
+    // 5 points in the order they appear in the cycle.
+    pub fn lizard_spock() -> Self {
+        Self::new(
+            "lizard-spock",
+            vec![
+                Move::Rock,
+                Move::Paper,
+                Move::Scissors,
+                Move::Spock,
+                Move::Lizard,
+            ],
+            Scoring::standard(5),
+        )
+    }
+
+    // Every ruleset selectable by name.
+    pub fn builtin() -> Vec<Self> {
+        vec![Self::standard(), Self::lizard_spock()]
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn game(&self) -> &CyclicGame<Move> {
+        &self.game
+    }
+
+    // The moves in cycle order.
+    pub fn moves(&self) -> &[Move] {
+        self.game.moves()
+    }
+
+    pub fn scoring(&self) -> &Scoring {
+        self.game.scoring()
+    }
+
+    // Points for playing `m`, which must be part of this ruleset.
+    pub fn move_score(&self, m: Move) -> i32 {
+        let index = self.moves().iter().position(|&other| other == m);
+        self.scoring().moves[index.unwrap_or_else(|| panic!("{:?} is not part of {}", m, self))]
+    }
+
+    // Points for getting `outcome` in a round.
+    pub fn outcome_score(&self, outcome: Outcome) -> i32 {
+        self.scoring().outcome(outcome.to_game_outcome())
+    }
+
+    // Fail if `m` can't be played under this ruleset.
+    pub fn check(&self, m: Move) -> Result<()> {
+        if !self.moves().contains(&m) {
+            bail!("{:?} is not a move in the {} ruleset", m, self);
+        }
+        Ok(())
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl fmt::Display for Ruleset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+// Look up a builtin ruleset by name.
+impl FromStr for Ruleset {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::builtin()
+            .into_iter()
+            .find(|ruleset| ruleset.name == s)
+            .ok_or_else(|| anyhow!("unknown ruleset: {}", s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Round;
+
+    #[test]
+    fn parse_ruleset() {
+        for ruleset in Ruleset::builtin() {
+            assert_eq!(
+                ruleset.to_string().parse::<Ruleset>().unwrap().name(),
+                ruleset.name()
+            );
+        }
+        assert!("rps".parse::<Ruleset>().is_err());
+    }
+
+    #[test]
+    fn ruleset_moves() {
+        assert!(Ruleset::standard().check(Move::Scissors).is_ok());
+        assert!(Ruleset::standard().check(Move::Spock).is_err());
+        assert!(Ruleset::lizard_spock().check(Move::Spock).is_ok());
+        assert_eq!(Ruleset::lizard_spock().move_score(Move::Lizard), 5);
+    }
+
+    #[test]
+    fn lizard_spock_rules() {
+        use Move::*;
+
+        // Every (winner, loser) pair from the rules of the game.
+        let wins = [
+            (Scissors, Paper),
+            (Paper, Rock),
+            (Rock, Lizard),
+            (Lizard, Spock),
+            (Spock, Scissors),
+            (Scissors, Lizard),
+            (Lizard, Paper),
+            (Paper, Spock),
+            (Spock, Rock),
+            (Rock, Scissors),
+        ];
+        let ruleset = Ruleset::lizard_spock();
+        for &opponent in ruleset.moves() {
+            for &ours in ruleset.moves() {
+                let round = Round { opponent, ours };
+                let outcome_score = if ours == opponent {
+                    3
+                } else if wins.contains(&(ours, opponent)) {
+                    6
+                } else {
+                    0
+                };
+                assert_eq!(
+                    round.score(&ruleset),
+                    ruleset.move_score(ours) + outcome_score,
+                    "{round:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_ruleset() {
+        // Only the outcome counts.
+        let ruleset = Ruleset::new(
+            "outcomes-only",
+            vec![Move::Rock, Move::Paper, Move::Scissors],
+            Scoring {
+                moves: vec![0, 0, 0],
+                loss: -1,
+                draw: 0,
+                win: 1,
+            },
+        );
+        let round = Round {
+            opponent: Move::Rock,
+            ours: Move::Paper,
+        };
+        assert_eq!(round.score(&ruleset), 1);
+    }
+}
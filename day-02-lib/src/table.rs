@@ -0,0 +1,117 @@
+use anyhow::{bail, Result};
+
+use crate::{parse_strategy_guide, Entry, Interpretation, Move, Response, Ruleset};
+
+// Scores of every standard rock-paper-scissors round, indexed by the
+// offsets of the guide's letters from 'A' and 'X'.
+//
+// This skips parsing into `Entry`s and matching on moves, at the cost of
+// only supporting the standard ruleset.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoreTable {
+    scores: [[i32; 3]; 3],
+}
+
+impl ScoreTable {
+    // Precompute the scores using the match-based scorer, so the two can't
+    // disagree.
+    pub fn new(interpretation: Interpretation) -> Self {
+        let ruleset = Ruleset::standard();
+        let opponents = [Move::Rock, Move::Paper, Move::Scissors];
+        let responses = [Response::X, Response::Y, Response::Z];
+
+        let mut scores = [[0; 3]; 3];
+        for (row, &opponent) in scores.iter_mut().zip(&opponents) {
+            for (score, &response) in row.iter_mut().zip(&responses) {
+                let entry = Entry { opponent, response };
+                // Every standard move and X, Y and Z are valid.
+                *score = entry
+                    .round(interpretation, &ruleset)
+                    .unwrap()
+                    .score(&ruleset);
+            }
+        }
+
+        Self { scores }
+    }
+
+    // Look up the score of a guide line such as "A Y".
+    fn line_score(&self, line: &[u8]) -> Option<i32> {
+        match *line {
+            [opponent @ b'A'..=b'C', b' ', response @ b'X'..=b'Z'] => {
+                Some(self.scores[(opponent - b'A') as usize][(response - b'X') as usize])
+            }
+            _ => None,
+        }
+    }
+
+    // Total score of a standard strategy guide.  Trailing whitespace and
+    // blank lines are ignored.
+    pub fn game_score(&self, input: &str) -> Result<i32> {
+        let mut total = 0;
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            match self.line_score(line.as_bytes()) {
+                Some(score) => total += score,
+                None => {
+                    // Let the full parser describe what is wrong, if it can.
+                    parse_strategy_guide(input)?;
+                    bail!("Line {} is not a standard round: '{}'", i + 1, line);
+                }
+            }
+        }
+        Ok(total)
+    }
+}
+
+// Compute the answer to part 1 using a lookup table.
+pub fn part1_table(input: &str) -> Result<i32> {
+    ScoreTable::new(Interpretation::Move).game_score(input)
+}
+
+// Compute the answer to part 2 using a lookup table.
+pub fn part2_table(input: &str) -> Result<i32> {
+    ScoreTable::new(Interpretation::Outcome).game_score(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn table() {
+        let table = ScoreTable::new(Interpretation::Move);
+        assert_eq!(table.line_score(b"A Y"), Some(8));
+        assert_eq!(table.line_score(b"C Z"), Some(6));
+        assert_eq!(table.line_score(b"D Z"), None);
+        assert_eq!(table.line_score(b"A  Y"), None);
+    }
+
+    #[test]
+    fn matches_scorer() {
+        let every_round: String = ["A", "B", "C"]
+            .iter()
+            .flat_map(|a| ["X", "Y", "Z"].map(|x| format!("{a} {x}\n")))
+            .collect();
+        for input in [EXAMPLE_INPUT, every_round.as_str()] {
+            assert_eq!(part1_table(input).unwrap(), part1(input).unwrap());
+            assert_eq!(part2_table(input).unwrap(), part2(input).unwrap());
+        }
+        assert_eq!(part1_table(EXAMPLE_INPUT).unwrap(), 15);
+        assert_eq!(part2_table(EXAMPLE_INPUT).unwrap(), 12);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input| part1_table(input).unwrap_err().to_string();
+        assert!(error("A Y\nB Q\n").starts_with("Error parsing strategy guide: line 2, column 3"));
+        assert_eq!(error("A Y\nE V\n"), "Line 2 is not a standard round: 'E V'");
+        assert_eq!(part1_table("A Y \r\n\r\n").unwrap(), 8);
+    }
+}
@@ -0,0 +1,218 @@
+// Round robin tournaments between players following strategy guides or
+// playing at random.
+
+use anyhow::{bail, Result};
+
+use crate::{Entry, Interpretation, Move, Outcome, Round, Ruleset};
+
+// How a player picks their move each round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Strategy {
+    // Play these moves in order, starting over after the last one.
+    Sequence(Vec<Move>),
+    // Play a uniformly random move from the ruleset.
+    Random,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Player {
+    pub name: String,
+    pub strategy: Strategy,
+}
+
+impl Player {
+    // A player making the moves we would make following `guide`.
+    pub fn from_guide(
+        name: &str,
+        guide: &[Entry],
+        interpretation: Interpretation,
+        ruleset: &Ruleset,
+    ) -> Result<Self> {
+        if guide.is_empty() {
+            bail!("{}'s strategy guide is empty", name);
+        }
+        let moves = guide
+            .iter()
+            .map(|entry| Ok(entry.round(interpretation, ruleset)?.ours))
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            name: name.to_owned(),
+            strategy: Strategy::Sequence(moves),
+        })
+    }
+
+    pub fn random(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            strategy: Strategy::Random,
+        }
+    }
+
+    fn next_move(&self, round: usize, ruleset: &Ruleset, rng: &mut fastrand::Rng) -> Move {
+        match &self.strategy {
+            Strategy::Sequence(moves) => moves[round % moves.len()],
+            Strategy::Random => ruleset.moves()[rng.usize(..ruleset.moves().len())],
+        }
+    }
+}
+
+// A player's results over the whole tournament.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub score: i64,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Standing {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    // Fraction of rounds won, or zero if no rounds were played.
+    pub fn win_rate(&self) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => self.wins as f64 / rounds as f64,
+        }
+    }
+
+    // Record a round played as `ours`.
+    fn record(&mut self, ours: Move, opponent: Move, ruleset: &Ruleset) {
+        let round = Round { opponent, ours };
+        self.score += i64::from(round.score(ruleset));
+        match round.outcome(ruleset) {
+            Outcome::Win => self.wins += 1,
+            Outcome::Tie => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+    }
+}
+
+// Play every pair of `players` against each other for `rounds` rounds under
+// `ruleset`.  Random players draw their moves from a generator seeded with
+// `seed` so tournaments can be replayed.
+//
+// Returns a standing for each player, in the order they were given.  Fails
+// if there are fewer than two players or a player's moves are not part of
+// `ruleset`.
+pub fn play_tournament(
+    players: &[Player],
+    rounds: usize,
+    ruleset: &Ruleset,
+    seed: u64,
+) -> Result<Vec<Standing>> {
+    if players.len() < 2 {
+        bail!("A tournament needs at least two players");
+    }
+    for player in players {
+        if let Strategy::Sequence(moves) = &player.strategy {
+            for &m in moves {
+                ruleset.check(m)?;
+            }
+        }
+    }
+
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let mut standings: Vec<_> = players
+        .iter()
+        .map(|player| Standing {
+            name: player.name.clone(),
+            ..Default::default()
+        })
+        .collect();
+
+    for a in 0..players.len() {
+        for b in a + 1..players.len() {
+            for round in 0..rounds {
+                let move_a = players[a].next_move(round, ruleset, &mut rng);
+                let move_b = players[b].next_move(round, ruleset, &mut rng);
+                standings[a].record(move_a, move_b, ruleset);
+                standings[b].record(move_b, move_a, ruleset);
+            }
+        }
+    }
+
+    Ok(standings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_strategy_guide;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    fn sequence(name: &str, moves: &[Move]) -> Player {
+        Player {
+            name: name.to_owned(),
+            strategy: Strategy::Sequence(moves.to_vec()),
+        }
+    }
+
+    #[test]
+    fn from_guide() {
+        let guide = parse_strategy_guide(EXAMPLE_INPUT).unwrap();
+        let ruleset = Ruleset::standard();
+        let player = Player::from_guide("example", &guide, Interpretation::Move, &ruleset).unwrap();
+        assert_eq!(
+            player.strategy,
+            Strategy::Sequence(vec![Move::Paper, Move::Rock, Move::Scissors])
+        );
+        let player =
+            Player::from_guide("example", &guide, Interpretation::Outcome, &ruleset).unwrap();
+        assert_eq!(
+            player.strategy,
+            Strategy::Sequence(vec![Move::Rock, Move::Rock, Move::Rock])
+        );
+        assert!(Player::from_guide("empty", &[], Interpretation::Move, &ruleset).is_err());
+    }
+
+    #[test]
+    fn sequences() {
+        let players = [
+            sequence("rock", &[Move::Rock]),
+            sequence("paper", &[Move::Paper]),
+            sequence("cycle", &[Move::Rock, Move::Paper, Move::Scissors]),
+        ];
+        let standings = play_tournament(&players, 3, &Ruleset::standard(), 0).unwrap();
+
+        // Rock loses to paper every round (1 point each) and draws (4), loses
+        // (1) and wins (7) against the cycle.
+        assert_eq!(
+            standings[0],
+            Standing {
+                name: "rock".to_owned(),
+                score: 3 + 4 + 1 + 7,
+                wins: 1,
+                draws: 1,
+                losses: 4,
+            }
+        );
+        assert_eq!(standings[1].wins, 3 + 1);
+        assert_eq!(standings[2].rounds(), 6);
+        assert!((standings[1].win_rate() - 4.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn random_players_are_seeded() {
+        let players = [Player::random("a"), Player::random("b")];
+        let ruleset = Ruleset::lizard_spock();
+        let first = play_tournament(&players, 100, &ruleset, 42).unwrap();
+        let second = play_tournament(&players, 100, &ruleset, 42).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first[0].wins, first[1].losses);
+        assert_eq!(first[0].rounds(), 100);
+    }
+
+    #[test]
+    fn invalid_tournaments() {
+        let ruleset = Ruleset::standard();
+        assert!(play_tournament(&[Player::random("a")], 1, &ruleset, 0).is_err());
+        let players = [Player::random("a"), sequence("spock", &[Move::Spock])];
+        assert!(play_tournament(&players, 1, &ruleset, 0).is_err());
+    }
+}
@@ -0,0 +1,144 @@
+// Play against the opponent's moves from a strategy guide, reading our moves
+// from the user.
+
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use day_02_lib::{Entry, Move, Outcome, Round, Ruleset};
+
+// The move in `ruleset` whose name starts with `answer`, ignoring case.
+// Returns `None` when no move or more than one move matches.
+fn parse_answer(answer: &str, ruleset: &Ruleset) -> Option<Move> {
+    let answer = answer.trim().to_lowercase();
+    if answer.is_empty() {
+        return None;
+    }
+    let mut matches = ruleset
+        .moves()
+        .iter()
+        .filter(|m| format!("{:?}", m).to_lowercase().starts_with(&answer));
+    match (matches.next(), matches.next()) {
+        (Some(&m), None) => Some(m),
+        _ => None,
+    }
+}
+
+// Play each of the opponent's moves in `guide` against a move read from
+// `input`, reporting results and the running score to `output`.
+//
+// Entering "quit" or reaching the end of `input` stops the game early.
+// Returns our total score.
+pub fn play(
+    guide: &[Entry],
+    ruleset: &Ruleset,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<i32> {
+    let choices = ruleset
+        .moves()
+        .iter()
+        .map(|m| format!("{:?}", m).to_lowercase())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut total = 0;
+    'rounds: for (i, entry) in guide.iter().enumerate() {
+        ruleset.check(entry.opponent)?;
+        let ours = loop {
+            write!(
+                output,
+                "Round {}: your opponent plays {:?}. Your move ({})? ",
+                i + 1,
+                entry.opponent,
+                choices
+            )?;
+            output.flush()?;
+
+            let mut answer = String::new();
+            if input.read_line(&mut answer)? == 0 || answer.trim() == "quit" {
+                writeln!(output)?;
+                break 'rounds;
+            }
+            match parse_answer(&answer, ruleset) {
+                Some(ours) => break ours,
+                None => writeln!(output, "Unknown or ambiguous move '{}'", answer.trim())?,
+            }
+        };
+
+        let round = Round {
+            opponent: entry.opponent,
+            ours,
+        };
+        let score = round.score(ruleset);
+        total += score;
+        let result = match round.outcome(ruleset) {
+            Outcome::Loss => "You lose",
+            Outcome::Tie => "It's a tie",
+            Outcome::Win => "You win",
+        };
+        writeln!(output, "{}: {} points, {} in total", result, score, total)?;
+    }
+
+    writeln!(output, "Final score: {}", total)?;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use day_02_lib::parse_strategy_guide;
+
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("../../day-02-lib/src/example-input.txt");
+
+    #[test]
+    fn answers() {
+        let standard = Ruleset::standard();
+        assert_eq!(parse_answer("r", &standard), Some(Move::Rock));
+        assert_eq!(parse_answer("Paper\n", &standard), Some(Move::Paper));
+        assert_eq!(parse_answer("s", &standard), Some(Move::Scissors));
+        assert_eq!(parse_answer("", &standard), None);
+        assert_eq!(parse_answer("spock", &standard), None);
+
+        let lizard_spock = Ruleset::lizard_spock();
+        assert_eq!(parse_answer("s", &lizard_spock), None);
+        assert_eq!(parse_answer("sp", &lizard_spock), Some(Move::Spock));
+    }
+
+    #[test]
+    fn play_example() {
+        let guide = parse_strategy_guide(EXAMPLE_INPUT).unwrap();
+        let mut output = Vec::new();
+        // The part 1 moves, with a typo along the way.
+        let total = play(
+            &guide,
+            &Ruleset::standard(),
+            "paper\nx\nrock\nscissors\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(total, 15);
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Unknown or ambiguous move 'x'"));
+        assert!(output.contains("You win: 8 points, 8 in total"));
+        assert!(output.ends_with("Final score: 15\n"));
+    }
+
+    #[test]
+    fn quit_early() {
+        let guide = parse_strategy_guide(EXAMPLE_INPUT).unwrap();
+        let mut output = Vec::new();
+        let total = play(
+            &guide,
+            &Ruleset::standard(),
+            "p\nquit\n".as_bytes(),
+            &mut output,
+        )
+        .unwrap();
+        assert_eq!(total, 8);
+
+        let total = play(&guide, &Ruleset::standard(), "".as_bytes(), &mut output).unwrap();
+        assert_eq!(total, 0);
+    }
+}
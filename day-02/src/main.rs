@@ -1,27 +1,181 @@
-use std::{fs, path::PathBuf};
+use std::{
+    cmp::Reverse,
+    fs,
+    io::{self, IsTerminal},
+    path::PathBuf,
+};
 
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result};
+use aoc_core::CommonArgs;
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use day_02_lib::{
+    parse_strategy_guide, part1_with, part2_with, play_tournament, Entry, Interpretation, Player,
+    Ruleset,
+};
+use log::warn;
 
-mod part1;
-mod part2;
+mod interactive;
+
+const EXAMPLE_INPUT: &str = include_str!("../../day-02-lib/src/example-input.txt");
 
 // Command line arguments.
 #[derive(Debug, Parser)]
+#[command(subcommand_negates_reqs = true)]
 struct Args {
-    input: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Rules to play by: "standard" or "lizard-spock"
+    #[arg(long, global = true, default_value = "standard")]
+    ruleset: Ruleset,
+
+    /// Play against the opponent's moves yourself instead of solving
+    #[arg(long)]
+    interactive: bool,
+
+    /// Print how each round was scored
+    #[arg(long)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Play strategy guides and random players against each other
+    Tournament(TournamentArgs),
 }
+
+#[derive(Debug, ClapArgs)]
+struct TournamentArgs {
+    /// Strategy guides to enter, each played as one player
+    guides: Vec<PathBuf>,
+
+    /// Number of players picking random moves
+    #[arg(long, default_value_t = 0)]
+    random: usize,
+
+    /// Rounds played between each pair of players
+    #[arg(long, default_value_t = 100)]
+    rounds: usize,
+
+    /// Seed for the random players
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Read the guides' second column as outcomes, as in part 2
+    #[arg(long)]
+    outcomes: bool,
+}
+
+fn tournament(args: &TournamentArgs, ruleset: &Ruleset) -> Result<()> {
+    let interpretation = if args.outcomes {
+        Interpretation::Outcome
+    } else {
+        Interpretation::Move
+    };
+
+    let mut players = Vec::new();
+    for path in &args.guides {
+        let input = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let guide = parse_strategy_guide(&input)?;
+        let name = path.display().to_string();
+        players.push(Player::from_guide(&name, &guide, interpretation, ruleset)?);
+    }
+    for i in 0..args.random {
+        players.push(Player::random(&format!("random-{}", i + 1)));
+    }
+
+    let mut standings = play_tournament(&players, args.rounds, ruleset, args.seed)?;
+    standings.sort_by_key(|standing| Reverse(standing.score));
+
+    let name_width = standings.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    for (rank, standing) in standings.iter().enumerate() {
+        println!(
+            "{:>2}. {:<name_width$} score {:>8}  W/D/L {}/{}/{}  win rate {:.1}%",
+            rank + 1,
+            standing.name,
+            standing.score,
+            standing.wins,
+            standing.draws,
+            standing.losses,
+            standing.win_rate() * 100.0,
+        );
+    }
+
+    Ok(())
+}
+
+// Print each round of `guide` with its score and the running total.
+fn print_rounds(guide: &[Entry], interpretation: Interpretation, ruleset: &Ruleset) -> Result<()> {
+    let mut total = 0;
+    for (i, entry) in guide.iter().enumerate() {
+        let round = entry.round(interpretation, ruleset)?;
+        let score = round.score(ruleset);
+        total += score;
+        println!(
+            "    Round {}: opponent {:?}, ours {:?}, {:?}: shape {} + outcome {} = {}, total {}",
+            i + 1,
+            round.opponent,
+            round.ours,
+            round.outcome(ruleset),
+            round.shape_score(ruleset),
+            round.outcome_score(ruleset),
+            score,
+            total
+        );
+    }
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
+    args.common.init_logging();
+
+    if let Some(Command::Tournament(tournament_args)) = &args.command {
+        return tournament(tournament_args, &args.ruleset);
+    }
+
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
+
+    if args.interactive {
+        if !io::stdin().is_terminal() {
+            warn!("--interactive expects to be run from a terminal");
+        }
+        let guide = parse_strategy_guide(&input)?;
+        interactive::play(&guide, &args.ruleset, io::stdin().lock(), io::stdout())?;
+        return Ok(());
+    }
 
-    let guide_1 = part1::parse_strategy_guide(&input)?;
-    let score_1 = part1::game_score(&guide_1);
-    println!("[Part 1] Score: {}", score_1);
+    if let Some(score_1) = args
+        .common
+        .run_part(1, || part1_with(&input, &args.ruleset))?
+    {
+        println!("[Part 1] Score: {}", score_1);
+        if args.verbose {
+            print_rounds(
+                &parse_strategy_guide(&input)?,
+                Interpretation::Move,
+                &args.ruleset,
+            )?;
+        }
+    }
 
-    let guide_2 = part2::parse_strategy_guide(&input)?;
-    let score_2 = part2::game_score(&guide_2);
-    println!("[Part 2] Score: {}", score_2);
+    if let Some(score_2) = args
+        .common
+        .run_part(2, || part2_with(&input, &args.ruleset))?
+    {
+        println!("[Part 2] Score: {}", score_2);
+        if args.verbose {
+            print_rounds(
+                &parse_strategy_guide(&input)?,
+                Interpretation::Outcome,
+                &args.ruleset,
+            )?;
+        }
+    }
 
     Ok(())
 }
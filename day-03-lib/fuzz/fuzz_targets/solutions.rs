@@ -0,0 +1,42 @@
+// Feed arbitrary bytes to every day 3 entry point, checking that none of
+// them panic.  Run with `cargo +nightly fuzz run solutions` from day-03-lib.
+
+#![no_main]
+
+use day_03_lib::{
+    badges, part1_bitmask, part1_parallel, part1_reader, part1_sorted, part1_with, part2_bitmask,
+    part2_parallel, part2_reader, part2_sorted, part2_with, PriorityTable, Rucksack,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let priorities = PriorityTable::standard();
+    // The first byte picks the group size, including the invalid 0.
+    let Some((&group_size, data)) = data.split_first() else {
+        return;
+    };
+    let group_size = usize::from(group_size % 5);
+
+    // The streaming solutions see invalid UTF-8 as well.
+    let _ = part1_reader(data, &priorities);
+    let _ = part2_reader(data, group_size, &priorities);
+
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let _ = part1_with(input, &priorities);
+    let _ = part1_bitmask(input, &priorities);
+    let _ = part1_sorted(input, &priorities);
+    let _ = part1_parallel(input, &priorities);
+    let _ = part2_with(input, group_size, &priorities);
+    let _ = part2_bitmask(input, group_size, &priorities);
+    let _ = part2_sorted(input, group_size, &priorities);
+    let _ = part2_parallel(input, group_size, &priorities);
+    let _ = badges(input, group_size, &priorities);
+    for line in input.lines() {
+        if let Ok(sack) = line.parse::<Rucksack>() {
+            let _ = sack.shared_item_priority(&priorities);
+        }
+    }
+    let _ = input.parse::<PriorityTable>();
+});
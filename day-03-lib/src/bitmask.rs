@@ -0,0 +1,206 @@
+// Solutions using a `u64` bitmask with one bit per item priority in place of
+// `HashSet<char>`.  Intersecting rucksacks is then a single `&`.  Rucksacks
+// holding items a set can't, those without a priority or with one of 64 or
+// more, are left to the hash set solutions, which only fail if such an item
+// is the shared one.
+
+use std::ops::BitAnd;
+
+use anyhow::{bail, Context, Result};
+use itertools::Itertools;
+
+use crate::{
+    part1::line_priority,
+    part2::{group_badge, group_context, groups},
+    rucksack::split_compartments,
+    PriorityTable, RucksackError,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn parse(items: &str, priorities: &PriorityTable) -> Result<Self> {
+        items.chars().try_fold(Self::default(), |set, item| {
+            let priority = priorities.priority(item)?;
+            if priority >= u64::BITS {
+                bail!(
+                    "'{}' has priority {}, too high for an item set",
+                    item,
+                    priority
+                );
+            }
+            Ok(Self(set.0 | 1 << priority))
+        })
+    }
+
+    pub fn len(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // Priorities of the items in the set, in ascending order.
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (0..u64::BITS).filter(move |&priority| self.contains(priority))
+    }
+
+    pub fn contains(self, priority: u32) -> bool {
+        priority < u64::BITS && self.0 & 1 << priority != 0
+    }
+
+    // The priority of the only item in the set.  `items` is the text of any
+    // rucksack containing the set, used to name the items in errors.
+    fn only_priority(self, items: &str, priorities: &PriorityTable) -> Result<u32, RucksackError> {
+        if self.len() == 1 {
+            return Ok(self.0.trailing_zeros());
+        }
+        let shared = self
+            .priorities()
+            .filter_map(|priority| {
+                items
+                    .chars()
+                    .find(|&item| priorities.priority(item).ok() == Some(priority))
+            })
+            .collect();
+        RucksackError::only_item(shared).map(|_| unreachable!("the set has one item"))
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+pub fn part1_bitmask(input: &str, priorities: &PriorityTable) -> Result<u32> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let solve = || -> Result<u32> {
+                let (a, b) = split_compartments(line)?;
+                match (ItemSet::parse(a, priorities), ItemSet::parse(b, priorities)) {
+                    (Ok(a), Ok(b)) => Ok((a & b).only_priority(line, priorities)?),
+                    _ => line_priority(line, priorities),
+                }
+            };
+            solve().with_context(|| format!("Error on line {} '{}'", i + 1, line))
+        })
+        .sum()
+}
+
+pub fn part2_bitmask(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<u32> {
+    groups(input, group_size)?
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let Ok(shared) = group
+                .iter()
+                .map(|line| ItemSet::parse(line, priorities))
+                .fold_ok(ItemSet(!0), BitAnd::bitand)
+            else {
+                return Ok(group_badge(i, group, priorities)?.priority);
+            };
+            shared
+                .only_priority(group[0], priorities)
+                .with_context(|| group_context(i, group))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn item_set() {
+        let priorities = PriorityTable::standard();
+        let set = ItemSet::parse("abA", &priorities).unwrap();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.priorities().collect::<Vec<_>>(), vec![1, 2, 27]);
+        assert!((set & ItemSet::parse("cB", &priorities).unwrap()).is_empty());
+        assert_eq!(
+            (set & ItemSet::parse("zA", &priorities).unwrap())
+                .priorities()
+                .collect::<Vec<_>>(),
+            vec![27]
+        );
+        assert!(ItemSet::parse("a1", &priorities).is_err());
+    }
+
+    #[test]
+    fn matches_hash_set_solutions() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(part1_bitmask(EXAMPLE_INPUT, &priorities).unwrap(), 157);
+        assert_eq!(
+            part1_bitmask(EXAMPLE_INPUT, &priorities).unwrap(),
+            part1::solution(EXAMPLE_INPUT, &priorities).unwrap()
+        );
+        assert_eq!(part2_bitmask(EXAMPLE_INPUT, 3, &priorities).unwrap(), 70);
+        assert_eq!(
+            part2_bitmask(EXAMPLE_INPUT, 3, &priorities).unwrap(),
+            part2::solution(EXAMPLE_INPUT, 3, &priorities).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        assert!(part1_bitmask("abc\n", &priorities).is_err());
+        // The same messages as the hash set solutions.
+        for input in ["aa\nabc\n", "abcd\n", "abab\n"] {
+            assert_eq!(
+                format!("{:#}", part1_bitmask(input, &priorities).unwrap_err()),
+                format!("{:#}", part1::solution(input, &priorities).unwrap_err())
+            );
+        }
+        for input in ["ab\nac\nbc\n", "a\na\na\nab\nab\nab\n"] {
+            assert_eq!(
+                format!("{:#}", part2_bitmask(input, 3, &priorities).unwrap_err()),
+                format!("{:#}", part2::solution(input, 3, &priorities).unwrap_err())
+            );
+        }
+        assert!(part1_bitmask("abcd\n", &priorities).is_err());
+        assert!(part1_bitmask("abab\n", &priorities).is_err());
+        assert!(part2_bitmask("a\na\n", 3, &priorities).is_err());
+        assert!(part2_bitmask("ab\nab\nab\n", 3, &priorities).is_err());
+        assert_eq!(
+            part2_bitmask("abc\nxbz\nAd\nAe\n", 2, &priorities).unwrap(),
+            2 + 27
+        );
+    }
+
+    #[test]
+    fn items_without_priorities() {
+        let priorities = PriorityTable::standard();
+        // Only the shared item needs a priority, as with the hash sets.
+        assert_eq!(part1_bitmask("a1ba\n", &priorities).unwrap(), 1);
+        assert_eq!(part2_bitmask("a1\nab\nac\n", 3, &priorities).unwrap(), 1);
+        assert_eq!(
+            format!("{:#}", part1_bitmask("a1b1\n", &priorities).unwrap_err()),
+            format!("{:#}", part1::solution("a1b1\n", &priorities).unwrap_err())
+        );
+        assert_eq!(
+            format!(
+                "{:#}",
+                part2_bitmask("1a\n1b\n1c\n", 3, &priorities).unwrap_err()
+            ),
+            format!(
+                "{:#}",
+                part2::solution("1a\n1b\n1c\n", 3, &priorities).unwrap_err()
+            )
+        );
+
+        // Priorities too high for a set are fine too.
+        let priorities = PriorityTable::standard().with_alphabet(['🎄'], 100);
+        assert_eq!(part1_bitmask("🎄a🎄b\n", &priorities).unwrap(), 100);
+    }
+}
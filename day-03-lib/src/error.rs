@@ -0,0 +1,46 @@
+use std::{error::Error, fmt};
+
+// Ways a rucksack or group of rucksacks can fail to match the puzzle's
+// promises.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RucksackError {
+    // Items can't be split evenly between two compartments.
+    OddItemCount(usize),
+    NoSharedItem,
+    // Sorted so the message doesn't depend on set iteration order.
+    SeveralSharedItems(Vec<char>),
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OddItemCount(count) => {
+                write!(f, "{} items can't be split between two compartments", count)
+            }
+            Self::NoSharedItem => write!(f, "no shared items between rucksacks"),
+            Self::SeveralSharedItems(items) => {
+                write!(
+                    f,
+                    "more than one shared item between rucksacks: {:?}",
+                    items
+                )
+            }
+        }
+    }
+}
+
+impl Error for RucksackError {}
+
+impl RucksackError {
+    // Check that `shared` holds exactly one item and return it.
+    pub fn only_item(mut shared: Vec<char>) -> Result<char, Self> {
+        match shared.len() {
+            0 => Err(Self::NoSharedItem),
+            1 => Ok(shared[0]),
+            _ => {
+                shared.sort_unstable();
+                Err(Self::SeveralSharedItems(shared))
+            }
+        }
+    }
+}
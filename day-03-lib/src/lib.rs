@@ -0,0 +1,162 @@
+use anyhow::Result;
+
+mod bitmask;
+mod error;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod part1;
+mod part2;
+mod priority;
+mod rucksack;
+mod sorted;
+mod stream;
+
+pub use bitmask::{part1_bitmask, part2_bitmask, ItemSet};
+pub use error::RucksackError;
+#[cfg(feature = "parallel")]
+pub use parallel::{part1_parallel, part2_parallel};
+pub use part2::{badges, Badge};
+pub use priority::PriorityTable;
+pub use rucksack::Rucksack;
+pub use sorted::{part1_sorted, part2_sorted};
+pub use stream::{part1_reader, part2_reader};
+
+// Elves carrying each badge in part 2 of the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+// Compute the answer to part 1.
+pub fn part1(input: &str) -> Result<u32> {
+    part1_with(input, &PriorityTable::standard())
+}
+
+// Compute the answer to part 2.
+pub fn part2(input: &str) -> Result<u32> {
+    part2_with(input, GROUP_SIZE, &PriorityTable::standard())
+}
+
+// Compute the answer to part 1 scoring items with `priorities`.
+pub fn part1_with(input: &str, priorities: &PriorityTable) -> Result<u32> {
+    part1::solution(input, priorities)
+}
+
+// Compute the answer to part 2 for groups of `group_size` elves, scoring
+// items with `priorities`.
+pub fn part2_with(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<u32> {
+    part2::solution(input, group_size, priorities)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 157);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 70);
+    }
+
+    // Inputs of up to a dozen lines drawn from `line`, so line counts are
+    // often not a multiple of the group size.
+    fn input_strategy(line: &'static str) -> impl Strategy<Value = String> {
+        prop::collection::vec(line, 0..12).prop_map(|lines| lines.join("\n"))
+    }
+
+    // Every part 1 solution's answer, or `None` where it failed.
+    fn part1_answers(input: &str, priorities: &PriorityTable) -> Vec<Option<u32>> {
+        let answers = vec![
+            part1_with(input, priorities).ok(),
+            part1_bitmask(input, priorities).ok(),
+            part1_sorted(input, priorities).ok(),
+            part1_reader(input.as_bytes(), priorities).ok(),
+        ];
+        #[cfg(feature = "parallel")]
+        let answers = [answers, vec![part1_parallel(input, priorities).ok()]].concat();
+        answers
+    }
+
+    // Every part 2 solution's answer, or `None` where it failed.
+    fn part2_answers(
+        input: &str,
+        group_size: usize,
+        priorities: &PriorityTable,
+    ) -> Vec<Option<u32>> {
+        let answers = vec![
+            part2_with(input, group_size, priorities).ok(),
+            part2_bitmask(input, group_size, priorities).ok(),
+            part2_sorted(input, group_size, priorities).ok(),
+            part2_reader(input.as_bytes(), group_size, priorities).ok(),
+        ];
+        #[cfg(feature = "parallel")]
+        let answers = [
+            answers,
+            vec![part2_parallel(input, group_size, priorities).ok()],
+        ]
+        .concat();
+        answers
+    }
+
+    proptest! {
+        // Arbitrary text, including non-ASCII and empty lines, never makes a
+        // solution panic.
+        #[test]
+        fn prop_never_panics(input in input_strategy("\\PC{0,8}"), group_size in 0usize..5) {
+            let priorities = PriorityTable::standard();
+            part1_answers(&input, &priorities);
+            part2_answers(&input, group_size, &priorities);
+            badges(&input, group_size, &priorities).ok();
+            for line in input.lines() {
+                if let Ok(sack) = line.parse::<Rucksack>() {
+                    sack.shared_item_priority(&priorities).ok();
+                }
+            }
+        }
+
+        // Every representation gives the same answer or every one fails,
+        // including when some items have no priority.  The bitmask and
+        // sorted solutions fail with the same errors as the hash sets.
+        #[test]
+        fn prop_solutions_agree(
+            input in input_strategy("[a-zA-Z0-9.,;:!?-]{0,8}"),
+            group_size in 0usize..5,
+        ) {
+            let priorities = PriorityTable::standard();
+            let answers = part1_answers(&input, &priorities);
+            prop_assert!(answers.iter().all(|answer| *answer == answers[0]), "{:?}", answers);
+            let answers = part2_answers(&input, group_size, &priorities);
+            prop_assert!(answers.iter().all(|answer| *answer == answers[0]), "{:?}", answers);
+
+            let describe = |result: Result<u32>| result.map_err(|e| format!("{:#}", e));
+            let expected = describe(part1_with(&input, &priorities));
+            prop_assert_eq!(&describe(part1_bitmask(&input, &priorities)), &expected);
+            prop_assert_eq!(&describe(part1_sorted(&input, &priorities)), &expected);
+            let expected = describe(part2_with(&input, group_size, &priorities));
+            prop_assert_eq!(
+                &describe(part2_bitmask(&input, group_size, &priorities)),
+                &expected
+            );
+            prop_assert_eq!(
+                &describe(part2_sorted(&input, group_size, &priorities)),
+                &expected
+            );
+        }
+
+        // Lines with an odd number of items are always rejected in part 1.
+        #[test]
+        fn prop_odd_lines_fail(line in "\\PC{0,8}") {
+            prop_assume!(line.chars().count() % 2 == 1);
+            let priorities = PriorityTable::standard();
+            prop_assert_eq!(
+                part1_answers(&line, &priorities),
+                vec![None; part1_answers("", &priorities).len()]
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+// Solutions spreading rucksacks and groups across threads with rayon.  Only
+// built with the `parallel` feature.
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::{
+    part1::{self, line_priority},
+    part2::{group_badge, groups},
+    PriorityTable,
+};
+
+// Compute the answer to part 1, scoring lines in parallel.
+pub fn part1_parallel(input: &str, priorities: &PriorityTable) -> Result<u32> {
+    let total = input
+        .par_lines()
+        .map(|line| line_priority(line, priorities))
+        .try_reduce(|| 0, |a, b| Ok(a + b));
+
+    // `par_lines` doesn't know line numbers, so rerun serially to report
+    // where a failure happened.
+    total.or_else(|_| part1::solution(input, priorities))
+}
+
+// Compute the answer to part 2, finding the badges of groups in parallel.
+pub fn part2_parallel(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<u32> {
+    groups(input, group_size)?
+        .par_iter()
+        .enumerate()
+        .map(|(i, group)| Ok(group_badge(i, group, priorities)?.priority))
+        .try_reduce(|| 0, |a, b| Ok(a + b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::part2;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn matches_serial_solutions() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(part1_parallel(EXAMPLE_INPUT, &priorities).unwrap(), 157);
+        assert_eq!(part2_parallel(EXAMPLE_INPUT, 3, &priorities).unwrap(), 70);
+
+        let input = EXAMPLE_INPUT.repeat(100);
+        assert_eq!(
+            part1_parallel(&input, &priorities).unwrap(),
+            part1::solution(&input, &priorities).unwrap()
+        );
+        assert_eq!(
+            part2_parallel(&input, 3, &priorities).unwrap(),
+            part2::solution(&input, 3, &priorities).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        let input = format!("{}abab\n", EXAMPLE_INPUT.repeat(10));
+        assert_eq!(
+            format!("{:#}", part1_parallel(&input, &priorities).unwrap_err()),
+            "Error on line 61 'abab': more than one shared item between rucksacks: ['a', 'b']"
+        );
+        let input = format!("{}a\nb\nc\n", EXAMPLE_INPUT.repeat(10));
+        assert_eq!(
+            format!("{:#}", part2_parallel(&input, 3, &priorities).unwrap_err()),
+            format!("{:#}", part2::solution(&input, 3, &priorities).unwrap_err())
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::{Context, Result};
+
+use crate::{PriorityTable, Rucksack};
+
+// Priority of the item shared between the compartments of `line`.
+pub fn line_priority(line: &str, priorities: &PriorityTable) -> Result<u32> {
+    Rucksack::parse(line)?.shared_item_priority(priorities)
+}
+
+pub fn solution(input: &str, priorities: &PriorityTable) -> Result<u32> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line_priority(line, priorities)
+                .with_context(|| format!("Error on line {} '{}'", i + 1, line))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RucksackError;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(
+            format!("{:#}", solution("aa\nabc\n", &priorities).unwrap_err()),
+            "Error on line 2 'abc': 3 items can't be split between two compartments"
+        );
+        assert_eq!(
+            format!("{:#}", solution("abcd\n", &priorities).unwrap_err()),
+            "Error on line 1 'abcd': no shared items between rucksacks"
+        );
+        assert_eq!(
+            format!("{:#}", solution("abab\n", &priorities).unwrap_err()),
+            "Error on line 1 'abab': more than one shared item between rucksacks: ['a', 'b']"
+        );
+        let error = solution("abab\n", &priorities).unwrap_err();
+        assert_eq!(
+            error.root_cause().downcast_ref::<RucksackError>(),
+            Some(&RucksackError::SeveralSharedItems(vec!['a', 'b']))
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(solution(EXAMPLE_INPUT, &priorities).unwrap(), 157);
+
+        let priorities = PriorityTable::new().with_alphabet("🎄🎁⭐".chars(), 1);
+        assert_eq!(
+            solution("🎄🎁🎁⭐\n⭐🎄🎁🎄\n", &priorities).unwrap(),
+            2 + 1
+        );
+        assert!(solution("abca\n", &priorities).is_err());
+    }
+}
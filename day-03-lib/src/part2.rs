@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+
+use crate::{PriorityTable, RucksackError};
+
+// All of a rucksack's items.  Unlike `Rucksack`, lines needn't split evenly
+// into compartments since part 2 ignores them.
+struct Contents {
+    items: HashSet<char>,
+}
+
+impl Contents {
+    fn parse(input: &str) -> Self {
+        Contents {
+            items: input.chars().collect(),
+        }
+    }
+}
+
+// The one item carried by every rucksack in `group`.
+fn shared_item(group: &[Contents]) -> Result<char, RucksackError> {
+    let Some((first, rest)) = group.split_first() else {
+        return Err(RucksackError::NoSharedItem);
+    };
+    let shared_items: Vec<_> = first
+        .items
+        .iter()
+        .filter(|item| rest.iter().all(|sack| sack.items.contains(item)))
+        .copied()
+        .collect();
+
+    RucksackError::only_item(shared_items)
+}
+
+// Split the lines of `input` into groups of `group_size`, failing unless
+// every line falls into a full group.
+pub fn groups(input: &str, group_size: usize) -> Result<Vec<Vec<&str>>> {
+    let lines: Vec<_> = input.lines().collect();
+    check_group_count(lines.len(), group_size)?;
+    Ok(lines.chunks(group_size).map(<[_]>::to_vec).collect())
+}
+
+pub fn check_group_size(group_size: usize) -> Result<()> {
+    if group_size == 0 {
+        bail!("group size must be at least 1");
+    }
+    Ok(())
+}
+
+// Fail unless `rucksacks` can be split into full groups of `group_size`.
+pub fn check_group_count(rucksacks: usize, group_size: usize) -> Result<()> {
+    check_group_size(group_size)?;
+    if !rucksacks.is_multiple_of(group_size) {
+        bail!(
+            "{} rucksacks can't be split into groups of {}",
+            rucksacks,
+            group_size
+        );
+    }
+    Ok(())
+}
+
+// Describe `group`, the group at `index` in the output of `groups`, in
+// errors.
+pub fn group_context(index: usize, group: &[&str]) -> String {
+    let lines: Vec<_> = group.iter().map(|line| format!("'{}'", line)).collect();
+    format!(
+        "Error in the group on lines {}-{} {}",
+        index * group.len() + 1,
+        (index + 1) * group.len(),
+        lines.join(", ")
+    )
+}
+
+// The badge carried by a group of elves.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Badge {
+    // One based input lines of the group's first and last rucksacks.
+    pub first_line: usize,
+    pub last_line: usize,
+    pub item: char,
+    pub priority: u32,
+}
+
+// The badge of `group`, the group at `index` in the output of `groups`.
+pub fn group_badge(index: usize, group: &[&str], priorities: &PriorityTable) -> Result<Badge> {
+    let group_size = group.len();
+    let sacks: Vec<_> = group.iter().map(|line| Contents::parse(line)).collect();
+    let badge = || -> Result<Badge> {
+        let item = shared_item(&sacks)?;
+        Ok(Badge {
+            first_line: index * group_size + 1,
+            last_line: (index + 1) * group_size,
+            item,
+            priority: priorities.priority(item)?,
+        })
+    };
+    badge().with_context(|| group_context(index, group))
+}
+
+// The badge of every group, in input order.
+pub fn badges(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<Vec<Badge>> {
+    groups(input, group_size)?
+        .iter()
+        .enumerate()
+        .map(|(i, group)| group_badge(i, group, priorities))
+        .collect()
+}
+
+pub fn solution(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<u32> {
+    Ok(badges(input, group_size, priorities)?
+        .iter()
+        .map(|badge| badge.priority)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn parse_rucksack() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let sack = Contents::parse(input);
+        assert_eq!(
+            sack.items,
+            vec![
+                'v', 'J', 'r', 'w', 'p', 'W', 't', 'w', 'J', 'g', 'W', 'r', 'h', 'c', 's', 'F',
+                'M', 'M', 'f', 'F', 'F', 'h', 'F', 'p'
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn test_shared_item() {
+        let sacks = |lines: &[&str]| lines.iter().map(|l| Contents::parse(l)).collect::<Vec<_>>();
+
+        let group = sacks(&[
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+        ]);
+        assert_eq!(shared_item(&group).unwrap(), 'r');
+        // The first two share several items.
+        assert!(shared_item(&group[..2]).is_err());
+
+        assert!(shared_item(&sacks(&["a", "b", "c"])).is_err());
+        assert!(shared_item(&sacks(&["abc", "abd", "abe"])).is_err());
+        assert_eq!(
+            shared_item(&sacks(&["abc", "ab", "bd", "eb"])).unwrap(),
+            'b'
+        );
+        assert!(shared_item(&[]).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(
+            format!(
+                "{:#}",
+                solution("ab\nac\nbc\nab\nab\nab\n", 3, &priorities).unwrap_err()
+            ),
+            "Error in the group on lines 1-3 'ab', 'ac', 'bc': no shared items between rucksacks"
+        );
+        assert_eq!(
+            format!("{:#}", solution("a\na\na\nab\nab\nab\n", 3, &priorities).unwrap_err()),
+            "Error in the group on lines 4-6 'ab', 'ab', 'ab': more than one shared item between rucksacks: ['a', 'b']"
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        assert_eq!(
+            groups("a\nb\nc\nd\n", 2).unwrap(),
+            vec![vec!["a", "b"], vec!["c", "d"]]
+        );
+        assert_eq!(
+            groups("a\nb\nc\nd\n", 3).unwrap_err().to_string(),
+            "4 rucksacks can't be split into groups of 3"
+        );
+        assert!(groups("a\n", 0).is_err());
+    }
+
+    #[test]
+    fn test_badges() {
+        let priorities = PriorityTable::standard();
+        let badges = badges(EXAMPLE_INPUT, 3, &priorities).unwrap();
+        assert_eq!(
+            badges,
+            vec![
+                Badge {
+                    first_line: 1,
+                    last_line: 3,
+                    item: 'r',
+                    priority: 18,
+                },
+                Badge {
+                    first_line: 4,
+                    last_line: 6,
+                    item: 'Z',
+                    priority: 52,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_solution() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(solution(EXAMPLE_INPUT, 3, &priorities).unwrap(), 70);
+        assert_eq!(
+            solution("abc\nxbz\nAd\nAe\n", 2, &priorities).unwrap(),
+            2 + 27
+        );
+        assert!(solution(EXAMPLE_INPUT, 4, &priorities).is_err());
+    }
+}
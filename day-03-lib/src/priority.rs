@@ -0,0 +1,180 @@
+use std::{fs, ops::RangeInclusive, path::Path, str::FromStr};
+
+use anyhow::{anyhow, bail, Context, Error, Result};
+use aoc_utils::CharScorer;
+
+// Maps each item to its priority.  The puzzle's table gives a-z priorities
+// 1-26 and A-Z 27-52, but any alphabet, including non-ASCII items, can be
+// built up with `with_alphabet` and `with_range` or loaded from a config.
+#[derive(Clone, Debug)]
+pub struct PriorityTable {
+    scorer: CharScorer,
+}
+
+impl PriorityTable {
+    // A table with no items.
+    pub fn new() -> Self {
+        Self {
+            scorer: CharScorer::new(),
+        }
+    }
+
+    // The puzzle's priorities.
+    pub fn standard() -> Self {
+        Self::new()
+            .with_range('a'..='z', 1)
+            .with_range('A'..='Z', 27)
+    }
+
+    // Give the items of `alphabet` consecutive priorities starting at
+    // `first_priority`.  Later assignments override earlier ones.
+    pub fn with_alphabet(
+        mut self,
+        alphabet: impl IntoIterator<Item = char>,
+        first_priority: u32,
+    ) -> Self {
+        self.scorer = self.scorer.with_alphabet(alphabet, first_priority);
+        self
+    }
+
+    pub fn with_range(self, range: RangeInclusive<char>, first_priority: u32) -> Self {
+        self.with_alphabet(range, first_priority)
+    }
+
+    pub fn priority(&self, item: char) -> Result<u32> {
+        self.scorer
+            .score(item)
+            .ok_or_else(|| anyhow!("'{}' is not an item with a priority", item))
+    }
+
+    // Load a table from a config file in the format read by `from_str`.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?
+            .parse()
+            .with_context(|| format!("Error in priority table {}", path.display()))
+    }
+}
+
+impl Default for PriorityTable {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+// Parse a config with one alphabet per line: its first priority followed by
+// either its items in order or an inclusive range such as `a..=z`.  Blank
+// lines and lines starting with `#` are skipped.  The puzzle's table is:
+//
+//     1 a..=z
+//     27 A..=Z
+impl FromStr for PriorityTable {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .enumerate()
+            .map(|(i, line)| (i, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .try_fold(Self::new(), |table, (i, line)| {
+                let parse_line = || -> Result<Self> {
+                    let Some((first_priority, items)) = line.split_once(char::is_whitespace) else {
+                        bail!("expected a priority followed by items");
+                    };
+                    let first_priority = first_priority
+                        .parse()
+                        .with_context(|| format!("invalid priority '{}'", first_priority))?;
+                    let items = items.trim();
+                    Ok(match parse_range(items) {
+                        Some(range) => table.with_range(range, first_priority),
+                        None => table.with_alphabet(items.chars(), first_priority),
+                    })
+                };
+                parse_line().with_context(|| format!("Error on line {} '{}'", i + 1, line))
+            })
+    }
+}
+
+// Parse `items` as a range between two single items, e.g. `a..=z`.
+fn parse_range(items: &str) -> Option<RangeInclusive<char>> {
+    let mut chars = items.chars();
+    let start = chars.next()?;
+    let end = chars.as_str().strip_prefix("..=")?;
+    let mut chars = end.chars();
+    let end = chars.next()?;
+    chars.next().is_none().then_some(start..=end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard() {
+        let priorities = PriorityTable::standard();
+
+        // Check valid ranges.
+        assert_eq!(priorities.priority('a').unwrap(), 1);
+        assert_eq!(priorities.priority('z').unwrap(), 26);
+        assert_eq!(priorities.priority('A').unwrap(), 27);
+        assert_eq!(priorities.priority('Z').unwrap(), 52);
+
+        // Check edges of valid ranges.
+        assert!(priorities.priority('`').is_err()); // Comes before 'a'.
+        assert!(priorities.priority('{').is_err()); // Comes after 'z'.
+        assert!(priorities.priority('@').is_err()); // Comes before 'A'.
+        assert!(priorities.priority('[').is_err()); // Comes after 'Z'.
+
+        // Non alphabetic characters are not valid.
+        assert!(priorities.priority('0').is_err());
+
+        // Non ascii characters are not valid.
+        assert!(priorities.priority('🎄').is_err());
+    }
+
+    #[test]
+    fn builder() {
+        let priorities = PriorityTable::new()
+            .with_alphabet("🎄🎁⭐".chars(), 1)
+            .with_range('α'..='ω', 4);
+        assert_eq!(priorities.priority('🎁').unwrap(), 2);
+        assert_eq!(priorities.priority('β').unwrap(), 5);
+        assert!(priorities.priority('a').is_err());
+    }
+
+    #[test]
+    fn parse() {
+        let priorities: PriorityTable = "# The puzzle's table.\n1 a..=z\n\n27 A..=Z\n"
+            .parse()
+            .unwrap();
+        for item in ['a', 'q', 'Z', '🎄'] {
+            assert_eq!(
+                priorities.priority(item).ok(),
+                PriorityTable::standard().priority(item).ok()
+            );
+        }
+
+        let priorities: PriorityTable = "10 🎄🎁⭐\n".parse().unwrap();
+        assert_eq!(priorities.priority('⭐').unwrap(), 12);
+        // Anything other than a range between two single items is an
+        // alphabet.
+        let priorities: PriorityTable = "1 ab..=z\n".parse().unwrap();
+        assert_eq!(priorities.priority('=').unwrap(), 5);
+        assert!(priorities.priority('c').is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                "1 a..=z\nabc\n".parse::<PriorityTable>().unwrap_err()
+            ),
+            "Error on line 2 'abc': expected a priority followed by items"
+        );
+        assert_eq!(
+            format!("{:#}", "x abc\n".parse::<PriorityTable>().unwrap_err()),
+            "Error on line 1 'x abc': invalid priority 'x': invalid digit found in string"
+        );
+    }
+}
@@ -0,0 +1,139 @@
+use std::{collections::HashSet, str::FromStr};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{PriorityTable, RucksackError};
+
+// A rucksack whose items are split evenly between two compartments.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Rucksack {
+    // Define rucksack as having multiple compartments in expectation that part 2 will need it.
+    compartments: Vec<HashSet<char>>,
+}
+
+// Split a rucksack's items evenly between its two compartments.
+pub fn split_compartments(input: &str) -> Result<(&str, &str), RucksackError> {
+    let count = input.chars().count();
+    if !count.is_multiple_of(2) {
+        return Err(RucksackError::OddItemCount(count));
+    }
+    let middle = input
+        .char_indices()
+        .nth(count / 2)
+        .map_or(input.len(), |(i, _)| i);
+    Ok(input.split_at(middle))
+}
+
+impl Rucksack {
+    pub fn parse(input: &str) -> Result<Self, RucksackError> {
+        // Assumes only two compartments.
+        let (a, b) = split_compartments(input)?;
+
+        Ok(Rucksack {
+            compartments: vec![a.chars().collect(), b.chars().collect()],
+        })
+    }
+
+    pub fn compartments(&self) -> &[HashSet<char>] {
+        &self.compartments
+    }
+
+    // Every item in the rucksack, whichever compartment it's in.
+    pub fn items(&self) -> HashSet<char> {
+        self.compartments.iter().flatten().copied().collect()
+    }
+
+    // Items found in every compartment.
+    pub fn shared_items(&self) -> Vec<char> {
+        let Some((first, rest)) = self.compartments.split_first() else {
+            return Vec::new();
+        };
+        first
+            .iter()
+            .filter(|item| rest.iter().all(|compartment| compartment.contains(item)))
+            .copied()
+            .collect()
+    }
+
+    // The one item found in every compartment.
+    pub fn shared_item(&self) -> Result<char, RucksackError> {
+        RucksackError::only_item(self.shared_items())
+    }
+
+    pub fn shared_item_priority(&self, priorities: &PriorityTable) -> Result<u32> {
+        priorities.priority(self.shared_item()?)
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = RucksackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rucksack() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let sack = Rucksack::parse(input).unwrap();
+        assert_eq!(sack.compartments().len(), 2);
+        assert_eq!(
+            sack.compartments()[0],
+            vec!['v', 'J', 'r', 'w', 'p', 'W', 't', 'w', 'J', 'g', 'W', 'r']
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(
+            sack.compartments()[1],
+            vec!['h', 'c', 's', 'F', 'M', 'M', 'f', 'F', 'F', 'h', 'F', 'p']
+                .into_iter()
+                .collect()
+        );
+        assert_eq!(input.parse::<Rucksack>().unwrap(), sack);
+        assert_eq!(sack.items().len(), 14);
+    }
+
+    #[test]
+    fn shared_items() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let sack = Rucksack::parse(input).unwrap();
+        assert_eq!(sack.shared_items(), vec!['p']);
+        assert_eq!(sack.shared_item(), Ok('p'));
+    }
+
+    #[test]
+    fn shared_item_priority() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp";
+        let sack = Rucksack::parse(input).unwrap();
+        assert_eq!(
+            sack.shared_item_priority(&PriorityTable::standard())
+                .unwrap(),
+            16
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Rucksack::parse("abc").err(),
+            Some(RucksackError::OddItemCount(3))
+        );
+        // Items are counted in characters rather than bytes.
+        assert!(Rucksack::parse("🎄a").is_ok());
+
+        assert_eq!(
+            Rucksack::parse("abcd").unwrap().shared_item(),
+            Err(RucksackError::NoSharedItem)
+        );
+        assert_eq!(
+            Rucksack::parse("abba").unwrap().shared_item(),
+            Err(RucksackError::SeveralSharedItems(vec!['a', 'b']))
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// Solutions intersecting sorted, deduplicated lists of items in place of
+// `HashSet<char>`.
+
+use std::cmp::Ordering;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    part2::{group_context, groups},
+    rucksack::split_compartments,
+    PriorityTable, RucksackError,
+};
+
+fn sorted_items(items: &str) -> Vec<char> {
+    let mut items: Vec<_> = items.chars().collect();
+    items.sort_unstable();
+    items.dedup();
+    items
+}
+
+// Items in both of the sorted lists `a` and `b`, in order.
+fn intersect(a: &[char], b: &[char]) -> Vec<char> {
+    let mut shared = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                shared.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    shared
+}
+
+pub fn part1_sorted(input: &str, priorities: &PriorityTable) -> Result<u32> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let solve = || -> Result<u32> {
+                let (a, b) = split_compartments(line)?;
+                let shared = intersect(&sorted_items(a), &sorted_items(b));
+                priorities.priority(RucksackError::only_item(shared)?)
+            };
+            solve().with_context(|| format!("Error on line {} '{}'", i + 1, line))
+        })
+        .sum()
+}
+
+pub fn part2_sorted(input: &str, group_size: usize, priorities: &PriorityTable) -> Result<u32> {
+    groups(input, group_size)?
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let solve = || -> Result<u32> {
+                let shared = group
+                    .iter()
+                    .map(|line| sorted_items(line))
+                    .reduce(|a, b| intersect(&a, &b))
+                    .unwrap_or_default();
+                priorities.priority(RucksackError::only_item(shared)?)
+            };
+            solve().with_context(|| group_context(i, group))
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1_with, part2_with};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_intersect() {
+        assert_eq!(
+            intersect(&sorted_items("cabbage"), &sorted_items("garbage")),
+            vec!['a', 'b', 'e', 'g']
+        );
+        assert!(intersect(&sorted_items("abc"), &[]).is_empty());
+    }
+
+    #[test]
+    fn matches_hash_set_solutions() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(part1_sorted(EXAMPLE_INPUT, &priorities).unwrap(), 157);
+        assert_eq!(part2_sorted(EXAMPLE_INPUT, 3, &priorities).unwrap(), 70);
+        assert_eq!(
+            part2_sorted("abc\nxbz\nAd\nAe\n", 2, &priorities).unwrap(),
+            2 + 27
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        // The same messages as the hash set solutions.
+        for input in ["aa\nabc\n", "abcd\n", "abab\n", "a1a1\n"] {
+            assert_eq!(
+                format!("{:#}", part1_sorted(input, &priorities).unwrap_err()),
+                format!("{:#}", part1_with(input, &priorities).unwrap_err())
+            );
+        }
+        for input in ["ab\nac\nbc\n", "a\na\na\nab\nab\nab\n", "a\nb\n"] {
+            assert_eq!(
+                format!("{:#}", part2_sorted(input, 3, &priorities).unwrap_err()),
+                format!("{:#}", part2_with(input, 3, &priorities).unwrap_err())
+            );
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// Solutions reading rucksacks one line at a time, so inputs of any size can
+// be piped through in constant memory.
+
+use std::io::BufRead;
+
+use anyhow::{Context, Result};
+
+use crate::{
+    part1::line_priority,
+    part2::{check_group_count, check_group_size, group_badge},
+    PriorityTable,
+};
+
+// Compute the answer to part 1 from the lines of `reader`.
+pub fn part1_reader(reader: impl BufRead, priorities: &PriorityTable) -> Result<u32> {
+    let mut total = 0;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.with_context(|| format!("Failed to read line {}", i + 1))?;
+        total += line_priority(&line, priorities)
+            .with_context(|| format!("Error on line {} '{}'", i + 1, line))?;
+    }
+    Ok(total)
+}
+
+// Compute the answer to part 2 from the lines of `reader`, holding only one
+// group in memory at a time.  Unlike `part2_with`, groups before a trailing
+// partial group are checked before its size is reported.
+pub fn part2_reader(
+    reader: impl BufRead,
+    group_size: usize,
+    priorities: &PriorityTable,
+) -> Result<u32> {
+    check_group_size(group_size)?;
+
+    let mut total = 0;
+    let mut rucksacks = 0;
+    let mut group = Vec::with_capacity(group_size);
+    for (i, line) in reader.lines().enumerate() {
+        group.push(line.with_context(|| format!("Failed to read line {}", i + 1))?);
+        rucksacks += 1;
+        if group.len() == group_size {
+            let lines: Vec<_> = group.iter().map(String::as_str).collect();
+            total += group_badge(i / group_size, &lines, priorities)?.priority;
+            group.clear();
+        }
+    }
+
+    check_group_count(rucksacks, group_size)?;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1_with, part2_with};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn matches_in_memory_solutions() {
+        let priorities = PriorityTable::standard();
+        assert_eq!(
+            part1_reader(EXAMPLE_INPUT.as_bytes(), &priorities).unwrap(),
+            157
+        );
+        assert_eq!(
+            part2_reader(EXAMPLE_INPUT.as_bytes(), 3, &priorities).unwrap(),
+            70
+        );
+        assert_eq!(
+            part2_reader("abc\nxbz\nAd\nAe\n".as_bytes(), 2, &priorities).unwrap(),
+            2 + 27
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let priorities = PriorityTable::standard();
+        for input in ["aa\nabc\n", "abcd\n", "aa\r\nb\n"] {
+            assert_eq!(
+                format!(
+                    "{:#}",
+                    part1_reader(input.as_bytes(), &priorities).unwrap_err()
+                ),
+                format!("{:#}", part1_with(input, &priorities).unwrap_err())
+            );
+        }
+        for input in ["ab\nac\nbc\nab\nab\nab\n", "a\nb\n"] {
+            assert_eq!(
+                format!(
+                    "{:#}",
+                    part2_reader(input.as_bytes(), 3, &priorities).unwrap_err()
+                ),
+                format!("{:#}", part2_with(input, 3, &priorities).unwrap_err())
+            );
+        }
+        assert!(part2_reader(EXAMPLE_INPUT.as_bytes(), 0, &priorities).is_err());
+
+        // Invalid UTF-8.
+        assert_eq!(
+            format!(
+                "{:#}",
+                part1_reader(&b"aa\n\xff\xff\n"[..], &priorities).unwrap_err()
+            ),
+            "Failed to read line 2: stream did not contain valid UTF-8"
+        );
+    }
+}
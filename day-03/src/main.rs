@@ -1,62 +1,128 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader},
+    path::{Path, PathBuf},
+};
 
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
+use aoc_core::CommonArgs;
 use clap::Parser;
+use day_03_lib::{
+    badges, part1_bitmask, part1_reader, part1_with, part2_bitmask, part2_reader, part2_with,
+    PriorityTable,
+};
 
-mod part1;
-mod part2;
+type Part1 = fn(&str, &PriorityTable) -> Result<u32>;
+type Part2 = fn(&str, usize, &PriorityTable) -> Result<u32>;
 
-fn item_priority(item: char) -> Result<u32> {
-    if item.is_ascii_lowercase() {
-        Ok(item as u32 - 'a' as u32 + 1)
-    } else if item.is_ascii_uppercase() {
-        Ok(item as u32 - 'A' as u32 + 27)
-    } else {
-        Err(anyhow!("'{}' is not an alphabetic character", item))
-    }
-}
+const EXAMPLE_INPUT: &str = include_str!("../../day-03-lib/src/example-input.txt");
 
 // Command line arguments.
 #[derive(Debug, Parser)]
 struct Args {
-    input: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Store rucksack contents as bitmasks instead of hash sets
+    #[arg(long)]
+    bitmask: bool,
+
+    /// Score lines and groups on several threads
+    #[cfg(feature = "parallel")]
+    #[arg(long, conflicts_with = "bitmask")]
+    parallel: bool,
+
+    /// Number of elves in each group sharing a badge
+    #[arg(long, default_value_t = day_03_lib::GROUP_SIZE)]
+    group_size: usize,
+
+    /// List each group's badge and its priority
+    #[arg(long)]
+    report: bool,
+
+    /// Read the input a line at a time, from stdin if the input is "-"
+    #[arg(long, requires = "part", conflicts_with_all = ["bitmask", "report"])]
+    stream: bool,
+
+    /// Config file of item priorities to use instead of the puzzle's
+    #[arg(long, value_name = "FILE")]
+    priorities: Option<PathBuf>,
 }
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
+// Open the input for streaming.  Only one part can be solved per run since
+// stdin can't be rewound.
+fn open_input(args: &Args) -> Result<Box<dyn BufRead>> {
+    match &args.common.input {
+        _ if args.common.example => Ok(Box::new(EXAMPLE_INPUT.as_bytes())),
+        Some(path) if path == Path::new("-") => Ok(Box::new(io::stdin().lock())),
+        Some(path) => {
+            let file =
+                File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+            Ok(Box::new(BufReader::new(file)))
+        }
+        None => unreachable!("clap requires an input unless --example is given"),
+    }
+}
 
-    let total = part1::solution(&input)?;
-    println!("[Part 1] Sum of shared item priorities: {}", total);
+fn stream(args: &Args, priorities: &PriorityTable) -> Result<()> {
+    if let Some(total) = args
+        .common
+        .run_part(1, || part1_reader(open_input(args)?, priorities))?
+    {
+        println!("[Part 1] Sum of shared item priorities: {}", total);
+    }
 
-    let total = part2::solution(&input)?;
-    println!("[Part 2] Sum group priorities: {}", total);
+    if let Some(total) = args.common.run_part(2, || {
+        part2_reader(open_input(args)?, args.group_size, priorities)
+    })? {
+        println!("[Part 2] Sum group priorities: {}", total);
+    }
 
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_item_priority() {
-        // Check valid ranges.
-        assert_eq!(item_priority('a').unwrap(), 1);
-        assert_eq!(item_priority('z').unwrap(), 26);
-        assert_eq!(item_priority('A').unwrap(), 27);
-        assert_eq!(item_priority('Z').unwrap(), 52);
-
-        // Check edges of valid ranges.
-        assert!(item_priority('`').is_err()); // Comes before 'a'.
-        assert!(item_priority('{').is_err()); // Comes after 'z'.
-        assert!(item_priority('@').is_err()); // Comes before 'A'.
-        assert!(item_priority('[').is_err()); // Comes after 'Z'.
-
-        // Non alphabetic characters are not valid.
-        assert!(item_priority('0').is_err());
-
-        // Non ascii characters are not valid.
-        assert!(item_priority('🎄').is_err());
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common.init_logging();
+    let priorities = match &args.priorities {
+        Some(path) => PriorityTable::from_file(path)?,
+        None => PriorityTable::standard(),
+    };
+    if args.stream {
+        return stream(&args, &priorities);
     }
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
+
+    let (solve_1, solve_2): (Part1, Part2) = if args.bitmask {
+        (part1_bitmask, part2_bitmask)
+    } else {
+        (part1_with, part2_with)
+    };
+    #[cfg(feature = "parallel")]
+    let (solve_1, solve_2): (Part1, Part2) = if args.parallel {
+        (day_03_lib::part1_parallel, day_03_lib::part2_parallel)
+    } else {
+        (solve_1, solve_2)
+    };
+
+    if let Some(total) = args.common.run_part(1, || solve_1(&input, &priorities))? {
+        println!("[Part 1] Sum of shared item priorities: {}", total);
+    }
+
+    if let Some(total) = args
+        .common
+        .run_part(2, || solve_2(&input, args.group_size, &priorities))?
+    {
+        println!("[Part 2] Sum group priorities: {}", total);
+        if args.report {
+            for badge in badges(&input, args.group_size, &priorities)? {
+                println!(
+                    "    Lines {}-{}: badge '{}', priority {}",
+                    badge.first_line, badge.last_line, badge.item, badge.priority
+                );
+            }
+        }
+    }
+
+    Ok(())
 }
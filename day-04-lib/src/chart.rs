@@ -0,0 +1,64 @@
+use std::ops::RangeInclusive;
+
+use anyhow::{anyhow, Result};
+use aoc_interval::Interval;
+use aoc_viz::SpanChart;
+
+use crate::{Assignment, SectionId};
+
+// Convert a range to the `i64` sections `SpanChart` draws.
+fn chart_range<T: SectionId>(range: &RangeInclusive<T>) -> Result<RangeInclusive<i64>> {
+    let convert = |value: T| {
+        value
+            .try_into()
+            .map_err(|_| anyhow!("Section {} is too large to draw", value))
+    };
+    Ok(convert(*range.start())?..=convert(*range.end())?)
+}
+
+// Chart every assignment as a group of bars, one per range, with the
+// sections it shares with the other ranges highlighted.
+pub fn overlap_chart<T: SectionId>(assignments: &[Assignment<T>]) -> Result<SpanChart> {
+    let mut chart = SpanChart::new();
+    for (i, assignment) in assignments.iter().enumerate() {
+        if i > 0 {
+            chart.gap();
+        }
+        for (j, range) in assignment.ranges.iter().enumerate() {
+            let highlights = assignment
+                .ranges
+                .iter()
+                .enumerate()
+                .filter(|&(k, _)| k != j)
+                .filter_map(|(_, other)| range.intersection(other))
+                .map(|overlap| chart_range(&overlap))
+                .collect::<Result<_>>()?;
+            let label = format!("{}: {}-{}", i + 1, range.start(), range.end());
+            chart.row(label, chart_range(range)?, highlights);
+        }
+    }
+    Ok(chart)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_assignments;
+
+    #[test]
+    fn chart() {
+        let assignments = parse_assignments::<u32>("2-4,6-8\n2-6,4-8,6-6\n").unwrap();
+        assert_eq!(
+            overlap_chart(&assignments).unwrap().render(80),
+            "1: 2-4 ===....\n\
+             1: 6-8 ....===\n\
+             \n\
+             2: 2-6 ==###..\n\
+             2: 4-8 ..###==\n\
+             2: 6-6 ....#..\n"
+        );
+
+        let assignments = parse_assignments::<u64>("0-1,1-18446744073709551615\n").unwrap();
+        assert!(overlap_chart(&assignments).is_err());
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::Result;
+
+mod assignment;
+mod chart;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod report;
+mod sweep;
+mod union;
+mod validate;
+
+pub use aoc_interval::{ContainsRange, Discrete, Interval};
+pub use assignment::{amount_overlapping, parse_assignments, parse_range, Assignment, SectionId};
+pub use chart::overlap_chart;
+#[cfg(feature = "parallel")]
+pub use parallel::{part1_parallel, part2_parallel};
+pub use report::write_report;
+pub use sweep::{sweep, Coverage};
+pub use union::Union;
+pub use validate::{validate, Issue, Violation};
+
+// Compute the answer to part 1.
+pub fn part1(input: &str) -> Result<u32> {
+    part1_with::<u32>(input)
+}
+
+// Compute the answer to part 2.
+pub fn part2(input: &str) -> Result<u32> {
+    part2_with::<u32>(input)
+}
+
+// Compute the answer to part 1 with section IDs parsed as `T`.
+pub fn part1_with<T: SectionId>(input: &str) -> Result<u32> {
+    Ok(parse_assignments::<T>(input)?
+        .iter()
+        // Rust bools are guaranteed to be 0 or 1.
+        .map(|assignment| assignment.is_completely_overlapping() as u32)
+        .sum())
+}
+
+// Compute the answer to part 2 with section IDs parsed as `T`.
+pub fn part2_with<T: SectionId>(input: &str) -> Result<u32> {
+    Ok(parse_assignments::<T>(input)?
+        .iter()
+        // Rust bools are guaranteed to be 0 or 1.
+        .map(|assignment| assignment.is_overlapping() as u32)
+        .sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn many_ranges() {
+        let assignment: Assignment<u32> = "2-4,6-8,7-7".parse().unwrap();
+        assert!(assignment.is_completely_overlapping());
+        assert!(assignment.is_overlapping());
+        assert_eq!(assignment.amount_overlapping(), 1);
+
+        let assignment: Assignment<u32> = "2-4,6-8,1-1,4-6".parse().unwrap();
+        assert!(!assignment.is_completely_overlapping());
+        assert!(assignment.is_overlapping());
+        assert_eq!(assignment.amount_overlapping(), 2);
+
+        let assignment: Assignment<u32> = "1-1,3-3,5-5".parse().unwrap();
+        assert!(!assignment.is_overlapping());
+        assert_eq!(part1("2-4,6-8,7-7\n1-1,3-3,5-5\n").unwrap(), 1);
+        assert_eq!(part2("2-4,6-8,1-1,4-6\n1-1,3-3\n").unwrap(), 1);
+    }
+
+    #[test]
+    fn parse_errors() {
+        // The solutions report where parsing failed.
+        let error = part1("2-4,6-8\n2-x,1-1\n").unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Error parsing input: line 2, column 3"));
+        let error = part2("2-4,6-8\n2-4\n").unwrap_err();
+        assert!(error.to_string().contains("line 2, column 4"), "{error}");
+    }
+
+    #[test]
+    fn id_types() {
+        // Too large for a `u32`.
+        let input = "4000000000-5000000000,4500000000-4500000009\n";
+        assert!(part1(input).is_err());
+        assert_eq!(part1_with::<u64>(input).unwrap(), 1);
+
+        let input = "-5--1,-2-3\n";
+        assert!(part2_with::<u64>(input).is_err());
+        assert_eq!(part2_with::<i64>(input).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), 4);
+    }
+}
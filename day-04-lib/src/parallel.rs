@@ -0,0 +1,74 @@
+// Solutions parsing and checking assignments across threads with rayon.
+// Only built with the `parallel` feature.
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::{part1_with, part2_with, Assignment, SectionId};
+
+// Count the lines of `input` for which `check` holds, parsing and checking
+// lines in parallel.
+fn count_lines<T: SectionId + Send>(input: &str, check: fn(&Assignment<T>) -> bool) -> Result<u32> {
+    // Trailing blank lines are allowed, as when parsing serially.
+    input
+        .trim_end()
+        .par_lines()
+        .map(|line| Ok(check(&line.parse::<Assignment<T>>()?) as u32))
+        .try_reduce(|| 0, |a, b| Ok(a + b))
+}
+
+// Compute the answer to part 1 with section IDs parsed as `T`, checking
+// lines in parallel.
+pub fn part1_parallel<T: SectionId + Send>(input: &str) -> Result<u32> {
+    // `par_lines` doesn't know line numbers, so rerun serially to report
+    // where a failure happened.
+    count_lines(input, Assignment::<T>::is_completely_overlapping)
+        .or_else(|_| part1_with::<T>(input))
+}
+
+// Compute the answer to part 2 with section IDs parsed as `T`, checking
+// lines in parallel.
+pub fn part2_parallel<T: SectionId + Send>(input: &str) -> Result<u32> {
+    count_lines(input, Assignment::<T>::is_overlapping).or_else(|_| part2_with::<T>(input))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn matches_serial_solutions() {
+        assert_eq!(part1_parallel::<u32>(EXAMPLE_INPUT).unwrap(), 2);
+        assert_eq!(part2_parallel::<u32>(EXAMPLE_INPUT).unwrap(), 4);
+
+        let input = format!("{}\n\n", EXAMPLE_INPUT.repeat(100));
+        assert_eq!(
+            part1_parallel::<u64>(&input).unwrap(),
+            part1_with::<u64>(&input).unwrap()
+        );
+        assert_eq!(
+            part2_parallel::<i64>(&input).unwrap(),
+            part2_with::<i64>(&input).unwrap()
+        );
+    }
+
+    #[test]
+    fn errors() {
+        // A blank line between assignments is an error either way.
+        for input in [
+            format!("{}2-x,1-1\n", EXAMPLE_INPUT.repeat(10)),
+            format!("{}\n{}", EXAMPLE_INPUT, EXAMPLE_INPUT),
+        ] {
+            assert_eq!(
+                part1_parallel::<u32>(&input).unwrap_err().to_string(),
+                part1_with::<u32>(&input).unwrap_err().to_string()
+            );
+            assert_eq!(
+                part2_parallel::<u32>(&input).unwrap_err().to_string(),
+                part2_with::<u32>(&input).unwrap_err().to_string()
+            );
+        }
+    }
+}
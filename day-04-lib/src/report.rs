@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::{Assignment, SectionId};
+
+// Write a CSV row per assignment with its ranges, whether any range fully or
+// only partially overlaps another, and the overlap summed over every pair.
+pub fn write_report<T: SectionId>(
+    assignments: &[Assignment<T>],
+    mut out: impl Write,
+) -> Result<()> {
+    writeln!(
+        out,
+        "line,ranges,fully_overlapping,partially_overlapping,overlap"
+    )?;
+    for (i, assignment) in assignments.iter().enumerate() {
+        let ranges: Vec<_> = assignment
+            .ranges
+            .iter()
+            .map(|range| format!("{}-{}", range.start(), range.end()))
+            .collect();
+        let fully = assignment.is_completely_overlapping();
+        writeln!(
+            out,
+            "{},\"{}\",{},{},{}",
+            i + 1,
+            ranges.join(","),
+            fully,
+            assignment.is_overlapping() && !fully,
+            assignment.amount_overlapping()
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_assignments;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn report() {
+        let mut out = Vec::new();
+        write_report(&parse_assignments::<u32>(EXAMPLE_INPUT).unwrap(), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "line,ranges,fully_overlapping,partially_overlapping,overlap\n\
+             1,\"2-4,6-8\",false,false,0\n\
+             2,\"2-3,4-5\",false,false,0\n\
+             3,\"5-7,7-9\",false,true,1\n\
+             4,\"2-8,3-7\",true,false,5\n\
+             5,\"6-6,4-6\",true,false,1\n\
+             6,\"2-6,4-8\",false,true,3\n"
+        );
+    }
+}
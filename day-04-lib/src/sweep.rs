@@ -0,0 +1,129 @@
+use crate::{Assignment, SectionId};
+
+// Statistics over every range in the input, each one an elf's assignment.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Coverage<T> {
+    // Most ranges containing a single section, and the first such section.
+    pub max_overlapping: usize,
+    pub busiest_section: Option<T>,
+    // Sections in at least one range.
+    pub covered: u128,
+}
+
+// Sweep across the start and end of every range in order, tracking how many
+// ranges are open, in O(n log n) for n ranges.
+pub fn sweep<T: SectionId>(assignments: &[Assignment<T>]) -> Coverage<T> {
+    // Starts sort before ends at the same section since ranges include both
+    // ends.
+    const START: u8 = 0;
+    const END: u8 = 1;
+    let mut events: Vec<_> = assignments
+        .iter()
+        .flat_map(|assignment| &assignment.ranges)
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| [(*range.start(), START), (*range.end(), END)])
+        .collect();
+    events.sort_unstable();
+
+    let mut coverage = Coverage {
+        max_overlapping: 0,
+        busiest_section: None,
+        covered: 0,
+    };
+    let mut open = 0;
+    let mut covered_since = None;
+    for (section, kind) in events {
+        if kind == START {
+            open += 1;
+            covered_since.get_or_insert(section);
+            if open > coverage.max_overlapping {
+                coverage.max_overlapping = open;
+                coverage.busiest_section = Some(section);
+            }
+        } else {
+            open -= 1;
+            if open == 0 {
+                if let Some(start) = covered_since.take() {
+                    coverage.covered += T::span(start, section);
+                }
+            }
+        }
+    }
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_interval::IntervalSet;
+
+    use super::*;
+    use crate::parse_assignments;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_sweep() {
+        let assignments = parse_assignments::<u32>(EXAMPLE_INPUT).unwrap();
+        assert_eq!(
+            sweep(&assignments),
+            Coverage {
+                max_overlapping: 8,
+                busiest_section: Some(6),
+                covered: 8,
+            }
+        );
+
+        // Touching ranges overlap at their shared section, and adjacent
+        // ones leave no gap.
+        let assignments = parse_assignments::<i64>("1-3,3-4\n5-5,10-12\n").unwrap();
+        assert_eq!(
+            sweep(&assignments),
+            Coverage {
+                max_overlapping: 2,
+                busiest_section: Some(3),
+                covered: 8,
+            }
+        );
+
+        let assignments = parse_assignments::<u64>("0-18446744073709551615,5-5\n").unwrap();
+        assert_eq!(sweep(&assignments).covered, 1 << 64);
+        assert_eq!(sweep::<u32>(&[]).busiest_section, None);
+    }
+
+    #[test]
+    fn sweep_matches_brute_force() {
+        // Every pair of ranges within 0..6, alongside a fixed assignment.
+        let ranges: Vec<_> = (0..6u32)
+            .flat_map(|start| (start..6).map(move |end| start..=end))
+            .collect();
+        for a in &ranges {
+            for b in &ranges {
+                let assignments = [
+                    Assignment {
+                        ranges: vec![a.clone(), b.clone()],
+                    },
+                    Assignment {
+                        ranges: vec![2..=3, 5..=5],
+                    },
+                ];
+                let all_ranges: Vec<_> = assignments.iter().flat_map(|a| &a.ranges).collect();
+                let counts: Vec<_> = (0..6)
+                    .map(|section| all_ranges.iter().filter(|r| r.contains(&section)).count())
+                    .collect();
+                let max = *counts.iter().max().unwrap();
+                let coverage = sweep(&assignments);
+                assert_eq!(coverage.max_overlapping, max, "{a:?} and {b:?}");
+                assert_eq!(
+                    coverage.busiest_section,
+                    counts
+                        .iter()
+                        .position(|&count| count == max)
+                        .map(|s| s as u32),
+                    "{a:?} and {b:?}"
+                );
+                let covered: IntervalSet<u32> = all_ranges.into_iter().cloned().collect();
+                assert_eq!(coverage.covered, covered.covered_len(), "{a:?} and {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::ops::RangeInclusive;
+
+use aoc_interval::{gaps, merge};
+
+use crate::{Assignment, SectionId};
+
+// Every range in the input merged into sorted, disjoint, non-adjacent
+// ranges.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Union<T> {
+    pub ranges: Vec<RangeInclusive<T>>,
+}
+
+impl<T: SectionId> Union<T> {
+    pub fn new(assignments: &[Assignment<T>]) -> Self {
+        Self {
+            ranges: merge(
+                assignments
+                    .iter()
+                    .flat_map(|assignment| assignment.ranges.iter().cloned()),
+            ),
+        }
+    }
+
+    // Sections in at least one range.
+    pub fn covered(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|range| T::span(*range.start(), *range.end()))
+            .sum()
+    }
+
+    // Sections between the first and last covered ones that no range
+    // includes, in order.
+    pub fn gaps(&self) -> Vec<RangeInclusive<T>> {
+        gaps(&self.ranges)
+    }
+
+    // The `n` widest gaps, widest first and the earliest of equally wide ones
+    // before the others.
+    pub fn largest_gaps(&self, n: usize) -> Vec<RangeInclusive<T>> {
+        let mut gaps = self.gaps();
+        // A stable sort keeps equally wide gaps in order.
+        gaps.sort_by_key(|gap| std::cmp::Reverse(T::span(*gap.start(), *gap.end())));
+        gaps.truncate(n);
+        gaps
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_assignments;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn example() {
+        let union = Union::new(&parse_assignments::<u32>(EXAMPLE_INPUT).unwrap());
+        assert_eq!(union.ranges, vec![2..=9]);
+        assert_eq!(union.covered(), 8);
+        assert_eq!(union.gaps(), vec![]);
+    }
+
+    #[test]
+    fn gaps() {
+        let input = "1-2,20-25\n4-5,7-7\n12-15,5-6\n30-30,4-4\n";
+        let union = Union::new(&parse_assignments::<u32>(input).unwrap());
+        assert_eq!(union.ranges, vec![1..=2, 4..=7, 12..=15, 20..=25, 30..=30]);
+        assert_eq!(union.covered(), 2 + 4 + 4 + 6 + 1);
+        assert_eq!(union.gaps(), vec![3..=3, 8..=11, 16..=19, 26..=29]);
+        assert_eq!(union.largest_gaps(3), vec![8..=11, 16..=19, 26..=29]);
+        assert_eq!(union.largest_gaps(10).len(), 4);
+    }
+
+    #[test]
+    fn whole_type() {
+        let input = "0-9,10-18446744073709551615\n";
+        let union = Union::new(&parse_assignments::<u64>(input).unwrap());
+        assert_eq!(union.ranges, vec![0..=u64::MAX]);
+        assert_eq!(union.covered(), 1 << 64);
+    }
+}
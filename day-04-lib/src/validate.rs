@@ -0,0 +1,150 @@
+// Checks for assignments that parse but probably aren't what was meant.
+
+use std::{collections::BTreeMap, fmt, ops::RangeInclusive};
+
+use crate::{Assignment, SectionId};
+
+// Something suspicious about one line of the input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Violation<T> {
+    // A range starting after it ends, which contains no sections.
+    Reversed(RangeInclusive<T>),
+    // The same ranges as an earlier line.
+    Duplicate { first_line: usize },
+    // A range reaching outside the allowed sections.
+    OutOfBounds(RangeInclusive<T>),
+}
+
+// A violation and the line it was found on, counting from 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Issue<T> {
+    pub line: usize,
+    pub violation: Violation<T>,
+}
+
+impl<T: SectionId> fmt::Display for Issue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.violation {
+            Violation::Reversed(range) => {
+                write!(
+                    f,
+                    "range {}-{} ends before it starts",
+                    range.start(),
+                    range.end()
+                )
+            }
+            Violation::Duplicate { first_line } => {
+                write!(f, "same ranges as line {}", first_line)
+            }
+            Violation::OutOfBounds(range) => {
+                write!(
+                    f,
+                    "range {}-{} is out of bounds",
+                    range.start(),
+                    range.end()
+                )
+            }
+        }
+    }
+}
+
+// Find every violation in `assignments`, one per line of the input, in line
+// order.  Ranges must lie within `bounds` if given.
+pub fn validate<T: SectionId>(
+    assignments: &[Assignment<T>],
+    bounds: Option<&RangeInclusive<T>>,
+) -> Vec<Issue<T>> {
+    let mut issues = Vec::new();
+    let mut first_lines = BTreeMap::new();
+    for (i, assignment) in assignments.iter().enumerate() {
+        let line = i + 1;
+        for range in &assignment.ranges {
+            if range.is_empty() {
+                issues.push(Issue {
+                    line,
+                    violation: Violation::Reversed(range.clone()),
+                });
+            }
+            if let Some(bounds) = bounds {
+                if !bounds.contains(range.start()) || !bounds.contains(range.end()) {
+                    issues.push(Issue {
+                        line,
+                        violation: Violation::OutOfBounds(range.clone()),
+                    });
+                }
+            }
+        }
+
+        let key: Vec<_> = assignment
+            .ranges
+            .iter()
+            .map(|range| (*range.start(), *range.end()))
+            .collect();
+        if let Some(&first_line) = first_lines.get(&key) {
+            issues.push(Issue {
+                line,
+                violation: Violation::Duplicate { first_line },
+            });
+        } else {
+            first_lines.insert(key, line);
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_assignments;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn valid() {
+        let assignments = parse_assignments::<u32>(EXAMPLE_INPUT).unwrap();
+        assert_eq!(validate(&assignments, None), vec![]);
+        assert_eq!(validate(&assignments, Some(&(2..=9))), vec![]);
+    }
+
+    #[test]
+    fn violations() {
+        let assignments =
+            parse_assignments::<u32>("2-4,6-8\n4-2,3-3\n2-4,6-8\n0-1,5-100\n").unwrap();
+        let issues = validate(&assignments, Some(&(1..=99)));
+        assert_eq!(
+            issues,
+            vec![
+                Issue {
+                    line: 2,
+                    violation: Violation::Reversed(RangeInclusive::new(4, 2)),
+                },
+                Issue {
+                    line: 3,
+                    violation: Violation::Duplicate { first_line: 1 },
+                },
+                Issue {
+                    line: 4,
+                    violation: Violation::OutOfBounds(0..=1),
+                },
+                Issue {
+                    line: 4,
+                    violation: Violation::OutOfBounds(5..=100),
+                },
+            ]
+        );
+        let messages: Vec<_> = issues.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            [
+                "line 2: range 4-2 ends before it starts",
+                "line 3: same ranges as line 1",
+                "line 4: range 0-1 is out of bounds",
+                "line 4: range 5-100 is out of bounds",
+            ]
+        );
+
+        // Without bounds any section is allowed.
+        assert_eq!(validate(&assignments, None).len(), 2);
+    }
+}
@@ -1,225 +1,162 @@
-use std::{cmp, fs, ops::RangeInclusive, path::PathBuf, str::FromStr};
-
-use anyhow::{anyhow, Error, Result};
-use clap::Parser;
-use nom::{
-    bytes::complete::tag,
-    character::complete::{char, one_of},
-    combinator::{map_res, recognize},
-    multi::{many0, many1},
-    sequence::terminated,
-    Finish, IResult,
+use std::{fs, io, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use aoc_core::CommonArgs;
+use clap::{Parser, ValueEnum};
+use day_04_lib::{
+    overlap_chart, parse_assignments, parse_range, part1_with, part2_with, sweep, validate,
+    write_report, SectionId, Union,
 };
 
-trait Overlap<T: PartialOrd + Ord + Clone> {
-    fn overlap(&self, range: &RangeInclusive<T>) -> RangeInclusive<T>;
-}
+type Solution = fn(&str) -> Result<u32>;
 
-impl<T: PartialOrd + Ord + Clone> Overlap<T> for RangeInclusive<T> {
-    fn overlap(&self, range: &RangeInclusive<T>) -> RangeInclusive<T> {
-        let start = cmp::max(self.start(), range.start());
-        let end = cmp::min(self.end(), range.end());
+const EXAMPLE_INPUT: &str = include_str!("../../day-04-lib/src/example-input.txt");
 
-        start.clone()..=end.clone()
-    }
+// Command line arguments.
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Integer type to parse section IDs as
+    #[arg(long, value_enum, default_value_t = IdType::U32)]
+    id_type: IdType,
+
+    /// Parse and check lines on several threads
+    #[cfg(feature = "parallel")]
+    #[arg(long)]
+    parallel: bool,
+
+    /// Check the input for reversed ranges, duplicate lines and sections
+    /// outside --bounds before solving
+    #[arg(long)]
+    validate: bool,
+
+    /// Sections allowed by --validate
+    #[arg(long, requires = "validate", value_name = "START-END")]
+    bounds: Option<String>,
+
+    /// Print a CSV row describing each line's overlaps instead of the answers
+    #[arg(long)]
+    report: bool,
+
+    /// Report the most ranges overlapping any one section and the number of
+    /// sections covered
+    #[arg(long)]
+    sweep: bool,
+
+    /// Merge every range in the input and report the sections covered and
+    /// the widest gaps between them
+    #[arg(long)]
+    union: bool,
+
+    /// Draw each line's ranges as bars with their overlaps highlighted
+    #[arg(long)]
+    visualize: bool,
+
+    /// Write the overlap chart to an SVG file
+    #[arg(long, value_name = "FILE")]
+    svg: Option<PathBuf>,
 }
 
-trait ContainsRange<T: PartialOrd> {
-    fn contains_range(&self, range: &RangeInclusive<T>) -> bool;
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum IdType {
+    U32,
+    U64,
+    I64,
 }
 
-impl<T: PartialOrd> ContainsRange<T> for RangeInclusive<T> {
-    fn contains_range(&self, range: &RangeInclusive<T>) -> bool {
-        self.contains(range.start()) && self.contains(range.end())
+fn run<T: SectionId + Send>(args: &Args, input: &str) -> Result<()> {
+    if args.validate {
+        let bounds = args.bounds.as_deref().map(parse_range::<T>).transpose()?;
+        let issues = validate(&parse_assignments::<T>(input)?, bounds.as_ref());
+        for issue in &issues {
+            eprintln!("{}", issue);
+        }
+        if !issues.is_empty() {
+            bail!("Found {} problems in the input", issues.len());
+        }
     }
-}
-
-#[derive(Debug, Eq, PartialEq)]
-struct Pair {
-    a: RangeInclusive<u32>,
-    b: RangeInclusive<u32>,
-}
 
-impl Pair {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, a) = range_value(input)?;
-        let (input, _) = tag(",")(input)?;
-        let (input, b) = range_value(input)?;
-
-        Ok((input, Self { a, b }))
+    if args.report {
+        return write_report(&parse_assignments::<T>(input)?, io::stdout().lock());
     }
 
-    fn is_completely_overlapping(&self) -> bool {
-        self.a.contains_range(&self.b) || self.b.contains_range(&self.a)
+    if args.sweep {
+        let coverage = sweep(&parse_assignments::<T>(input)?);
+        if let Some(section) = coverage.busiest_section {
+            println!(
+                "Most ranges overlapping one section: {} (section {})",
+                coverage.max_overlapping, section
+            );
+        }
+        println!("Sections covered: {}", coverage.covered);
+        return Ok(());
     }
 
-    fn amount_overlapping(&self) -> u32 {
-        let overlap = &self.a.overlap(&self.b);
-        if overlap.is_empty() {
-            0
-        } else {
-            *overlap.end() - *overlap.start() + 1
+    if args.union {
+        let union = Union::new(&parse_assignments::<T>(input)?);
+        println!(
+            "Sections covered: {} in {} ranges",
+            union.covered(),
+            union.ranges.len()
+        );
+        for gap in union.largest_gaps(5) {
+            println!(
+                "Gap: {}-{} ({} sections)",
+                gap.start(),
+                gap.end(),
+                T::span(*gap.start(), *gap.end())
+            );
         }
+        return Ok(());
     }
 
-    fn is_overlapping(&self) -> bool {
-        self.amount_overlapping() > 0
+    if args.visualize || args.svg.is_some() {
+        let chart = overlap_chart(&parse_assignments::<T>(input)?)?;
+        if args.visualize {
+            print!("{}", chart.render(80));
+        }
+        if let Some(path) = &args.svg {
+            fs::write(path, chart.render_svg(800))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        return Ok(());
     }
-}
 
-// Adapted from https://github.com/Geal/nom/blob/main/doc/nom_recipes.md#integers
-fn decimal_value(input: &str) -> IResult<&str, u32> {
-    map_res(
-        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
-        |value: &str| value.parse::<u32>(),
-    )(input)
-}
-
-fn range_value(input: &str) -> IResult<&str, RangeInclusive<u32>> {
-    let (input, start) = decimal_value(input)?;
-    let (input, _) = tag("-")(input)?;
-    let (input, end) = decimal_value(input)?;
-
-    Ok((input, start..=end))
-}
-
-impl FromStr for Pair {
-    // the error must be owned as well
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::parse(s)
-            .finish()
-            .map_err(|e| anyhow!("Error parsing pair: {}", e))
-            .map(|val| val.1)
+    let (solve_1, solve_2): (Solution, Solution) = (part1_with::<T>, part2_with::<T>);
+    #[cfg(feature = "parallel")]
+    let (solve_1, solve_2): (Solution, Solution) = if args.parallel {
+        (
+            day_04_lib::part1_parallel::<T>,
+            day_04_lib::part2_parallel::<T>,
+        )
+    } else {
+        (solve_1, solve_2)
+    };
+
+    if let Some(total) = args.common.run_part(1, || solve_1(input))? {
+        println!(
+            "[Part: 1] Number of completely overlapping ranges: {}",
+            total
+        );
     }
-}
-
-fn solution_part1(input: &str) -> Result<u32> {
-    input
-        .lines()
-        .map(|line| {
-            // Rust bools are guaranteed to be 0 or 1.
-            Ok(line.parse::<Pair>()?.is_completely_overlapping() as u32)
-        })
-        .sum()
-}
 
-fn solution_part2(input: &str) -> Result<u32> {
-    input
-        .lines()
-        .map(|line| {
-            // Rust bools are guaranteed to be 0 or 1.
-            Ok(line.parse::<Pair>()?.is_overlapping() as u32)
-        })
-        .sum()
-}
+    if let Some(total) = args.common.run_part(2, || solve_2(input))? {
+        println!("[Part: 2] Amount of overlapping ranges: {}", total);
+    }
 
-// Command line arguments.
-#[derive(Debug, Parser)]
-struct Args {
-    input: PathBuf,
+    Ok(())
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
-
-    let total = solution_part1(&input)?;
-    println!(
-        "[Part: 1] Number of completely overlapping ranges: {}",
-        total
-    );
-
-    let total = solution_part2(&input)?;
-    println!("[Part: 2] Amount of overlapping ranges: {}", total);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
-
-    #[test]
-    fn test_decimal_value() {
-        assert_eq!(decimal_value("0").unwrap(), ("", 0));
-    }
-
-    #[test]
-    fn test_range_value() {
-        assert_eq!(range_value("0-1").unwrap(), ("", 0..=1));
-    }
-
-    #[test]
-    fn parse_pair() {
-        assert_eq!(
-            "2-4,6-8".parse::<Pair>().unwrap(),
-            Pair { a: 2..=4, b: 6..=8 }
-        );
-    }
-
-    #[test]
-    fn pair_overlap() {
-        assert!(!"2-4,6-8"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!(!"2-3,4-6"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!(!"5-7,7-9"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!("2-8,3-7"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-
-        // Test all combinations of overlaps.
-        assert!("6-6,4-6"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!("4-4,4-6"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!("4-6,4-6"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-        assert!("4-6,4-4"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-
-        assert!(!"2-6,4-8"
-            .parse::<Pair>()
-            .unwrap()
-            .is_completely_overlapping());
-    }
-
-    #[test]
-    fn test_overlap() {
-        assert_eq!("2-4,6-8".parse::<Pair>().unwrap().amount_overlapping(), 0);
-        assert_eq!("2-3,4-5".parse::<Pair>().unwrap().amount_overlapping(), 0);
-        assert_eq!("5-7,7-9".parse::<Pair>().unwrap().amount_overlapping(), 1);
-        assert_eq!("2-8,3-7".parse::<Pair>().unwrap().amount_overlapping(), 5);
-        assert_eq!("6-6,4-6".parse::<Pair>().unwrap().amount_overlapping(), 1);
-        assert_eq!("2-6,4-8".parse::<Pair>().unwrap().amount_overlapping(), 3);
-    }
-
-    #[test]
-    fn test_solution_part1() {
-        assert_eq!(solution_part1(EXAMPLE_INPUT).unwrap(), 2);
-    }
+    args.common.init_logging();
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
 
-    #[test]
-    fn test_solution_part2() {
-        assert_eq!(solution_part2(EXAMPLE_INPUT).unwrap(), 4);
+    match args.id_type {
+        IdType::U32 => run::<u32>(&args, &input),
+        IdType::U64 => run::<u64>(&args, &input),
+        IdType::I64 => run::<i64>(&args, &input),
     }
 }
@@ -0,0 +1,133 @@
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use day_05_lib::{generate, CraneModel, MoveMethod, Problem};
+
+const INPUT: &str = include_str!("../../day-05/input.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("part1", |b| b.iter(|| day_05_lib::part1(INPUT).unwrap()));
+    c.bench_function("part2", |b| b.iter(|| day_05_lib::part2(INPUT).unwrap()));
+}
+
+// Compare moving crates in place with the original moves that pop crates
+// one at a time or split them off into a new `VecDeque`, on generated
+// instruction lists of increasing length.  On a machine with a single core:
+//
+//     moves      9000 allocating  9000 in place  9001 allocating  9001 in place
+//     10,000             0.70 ms        0.71 ms          1.00 ms        0.94 ms
+//     100,000             5.9 ms         6.8 ms           7.3 ms         7.3 ms
+//     1,000,000            53 ms          58 ms            82 ms          57 ms
+//
+// Moves average ten crates, so the checks and bookkeeping around each
+// instruction cost as much as moving the crates.  Dropping the `VecDeque`
+// the CrateMover 9001 allocated saves up to 30% on the longest list but
+// next to nothing on the shorter ones.  The CrateMover 9000 never allocated,
+// and draining its crates in place is 1-15% slower than popping them.
+fn move_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("moves");
+    for moves in [10_000, 100_000, 1_000_000] {
+        let problem: Problem = generate(9, 50, moves, 5).unwrap().input.parse().unwrap();
+        for (model, model_name) in [
+            (CraneModel::CrateMover9000, "9000"),
+            (CraneModel::CrateMover9001, "9001"),
+        ] {
+            for (method, method_name) in [
+                (MoveMethod::Allocating, "allocating"),
+                (MoveMethod::InPlace, "in_place"),
+            ] {
+                let id = BenchmarkId::new(format!("{}_{}", model_name, method_name), moves);
+                group.bench_with_input(id, &problem, |b, problem| {
+                    b.iter_batched(
+                        || problem.clone(),
+                        |mut problem| problem.execute_with(model, method).unwrap(),
+                        BatchSize::LargeInput,
+                    )
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+// Parse generated inputs with a thousand stacks and increasing numbers of
+// moves.  On a machine with a single core:
+//
+//     moves      parse
+//     10,000     11 ms
+//     100,000    91 ms
+//     1,000,000  0.90 s
+//
+// Placing each crate used to search every stack index, which took 97 ms
+// at 10,000 moves.
+fn parse_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for moves in [10_000, 100_000, 1_000_000] {
+        let input = generate(1000, 50, moves, 5).unwrap().input;
+        group.bench_with_input(BenchmarkId::from_parameter(moves), &input, |b, input| {
+            b.iter(|| input.parse::<Problem>().unwrap())
+        });
+    }
+    group.finish();
+}
+
+// Carry out a million generated moves on a thousand stacks serially and in
+// parallel with windows of different lengths.  On a machine with a single
+// core:
+//
+//     serial        87 ms
+//     window 256   546 ms
+//     window 4096  166 ms
+//     window 65536 131 ms
+//
+// Splitting windows into segments and moving crates out and back costs more
+// than carrying out the moves, and random moves over a thousand stacks join
+// most of them into one segment past a few thousand instructions.  Running
+// in parallel only pays off on several cores for inputs whose instructions
+// keep to separate groups of stacks.  Serial steps used to log every stack
+// even with logging off, which took 775 ms here.
+#[cfg(feature = "parallel")]
+fn parallel_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    let problem: Problem = generate(1000, 50, 1_000_000, 5)
+        .unwrap()
+        .input
+        .parse()
+        .unwrap();
+    group.bench_function("serial", |b| {
+        b.iter_batched(
+            || problem.clone(),
+            |mut problem| problem.execute(CraneModel::CrateMover9001).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+    for window in [256, 4096, 65536] {
+        group.bench_with_input(BenchmarkId::new("window", window), &window, |b, &window| {
+            b.iter_batched(
+                || problem.clone(),
+                |mut problem| {
+                    problem
+                        .execute_parallel(CraneModel::CrateMover9001, window)
+                        .unwrap()
+                },
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+#[cfg(feature = "parallel")]
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    move_benchmark,
+    parse_benchmark,
+    parallel_benchmark
+);
+#[cfg(not(feature = "parallel"))]
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    move_benchmark,
+    parse_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,52 @@
+use std::{error::Error, fmt};
+
+use crate::Instruction;
+
+// Why an instruction can't be carried out.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InstructionErrorKind {
+    // The instruction names a stack, by its 1 based position, past the last
+    // of `stacks`.
+    NoSuchStack { stack: usize, stacks: usize },
+    // The source stack holds fewer crates than the instruction moves.
+    NotEnoughCrates { amount: usize, available: usize },
+}
+
+// An instruction that can't be carried out, along with its 1 based number
+// and text for reporting.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstructionError {
+    pub number: usize,
+    pub text: String,
+    pub kind: InstructionErrorKind,
+}
+
+impl InstructionError {
+    pub fn new(number: usize, instruction: &Instruction, kind: InstructionErrorKind) -> Self {
+        Self {
+            number,
+            text: instruction.to_string(),
+            kind,
+        }
+    }
+}
+
+impl fmt::Display for InstructionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "instruction {}: '{}' ", self.number, self.text)?;
+        match self.kind {
+            InstructionErrorKind::NoSuchStack { stack, stacks } => {
+                write!(f, "references stack {} but only {} exist", stack, stacks)
+            }
+            InstructionErrorKind::NotEnoughCrates { amount, available } => {
+                write!(
+                    f,
+                    "moves {} crates but the stack only holds {}",
+                    amount, available
+                )
+            }
+        }
+    }
+}
+
+impl Error for InstructionError {}
@@ -0,0 +1,154 @@
+// Random inputs of any size, along with the stacks they should end up as,
+// for stress testing and benchmarks.
+
+use std::{collections::VecDeque, fmt::Write};
+
+use anyhow::{bail, Result};
+
+use crate::{draw_stacks, CraneModel, Stack};
+
+// The most crates a generated instruction moves.
+const MAX_MOVE: usize = 20;
+
+// A generated input and the stacks left by carrying out its instructions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub final_9000: Vec<Stack>,
+    pub final_9001: Vec<Stack>,
+}
+
+impl Generated {
+    // The stacks left by a crane of type `model`.
+    pub fn final_stacks(&self, model: CraneModel) -> &[Stack] {
+        match model {
+            CraneModel::CrateMover9000 => &self.final_9000,
+            CraneModel::CrateMover9001 => &self.final_9001,
+        }
+    }
+
+    // The crates left on top of the stacks by a crane of type `model`, the
+    // answer to the puzzle.
+    pub fn tops(&self, model: CraneModel) -> String {
+        self.final_stacks(model)
+            .iter()
+            .filter_map(|stack| stack.values.back())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+// Generate `stacks` stacks of between 2 and `height` crates, labelled with
+// random letters, and `moves` moves of up to 20 crates between them.  No
+// move takes a stack's last crate, so every stack has a crate on top at the
+// end.  The same `seed` gives the same input.
+pub fn generate(stacks: usize, height: usize, moves: usize, seed: u64) -> Result<Generated> {
+    if stacks < 2 {
+        bail!("Moving crates needs at least two stacks");
+    }
+    if height < 2 {
+        bail!("Stacks need room for at least two crates");
+    }
+
+    let mut rng = fastrand::Rng::with_seed(seed);
+    let initial: Vec<_> = (0..stacks)
+        .map(|i| Stack {
+            values: (0..rng.usize(2..=height))
+                .map(|_| rng.uppercase().to_string())
+                .collect(),
+            index: i as u32 + 1,
+        })
+        .collect();
+    let mut input = draw_stacks(&initial);
+    input.push('\n');
+
+    let mut final_9000 = initial.clone();
+    let mut final_9001 = initial;
+    // Stacks with crates to spare, so sources can be picked without
+    // retrying.  There are more crates than stacks, so there is always one.
+    // Both cranes move the same number of crates, so either set of stacks
+    // can be used to count them.
+    let mut sources: Vec<usize> = (0..stacks).collect();
+    for _ in 0..moves {
+        let source = rng.usize(..sources.len());
+        let src = sources[source];
+        let dest = (src + rng.usize(1..stacks)) % stacks;
+        let spare = final_9000[src].values.len() - 1;
+        let amount = rng.usize(1..=spare.min(MAX_MOVE));
+        // Writing to a String can't fail.
+        writeln!(input, "move {} from {} to {}", amount, src + 1, dest + 1).unwrap();
+
+        let moved = take_top(&mut final_9000[src].values, amount);
+        final_9000[dest].values.extend(moved.into_iter().rev());
+        let moved = take_top(&mut final_9001[src].values, amount);
+        final_9001[dest].values.extend(moved);
+
+        if amount == spare {
+            sources.swap_remove(source);
+        }
+        if final_9000[dest].values.len() - amount == 1 {
+            sources.push(dest);
+        }
+    }
+
+    Ok(Generated {
+        input,
+        final_9000,
+        final_9001,
+    })
+}
+
+// Remove the top `amount` crates from `values`, bottom first.
+fn take_top(values: &mut VecDeque<String>, amount: usize) -> Vec<String> {
+    values.drain(values.len() - amount..).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{solution, Problem};
+
+    #[test]
+    fn known_answers() {
+        for seed in 0..20 {
+            let generated = generate(5, 6, 100, seed).unwrap();
+            for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+                let mut problem: Problem = generated.input.parse().unwrap();
+                assert_eq!(problem.instructions.len(), 100);
+                problem.execute(model).unwrap();
+                assert_eq!(problem.stacks, generated.final_stacks(model));
+                assert_eq!(
+                    solution(&generated.input, model).unwrap(),
+                    generated.tops(model)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn many_stacks() {
+        // Stack indices wider than the crates above them.
+        let generated = generate(1200, 3, 2000, 1).unwrap();
+        let problem: Problem = generated.input.parse().unwrap();
+        assert_eq!(problem.stacks.len(), 1200);
+        assert_eq!(problem.stacks[1199].index, 1200);
+    }
+
+    #[test]
+    fn seeded() {
+        assert_eq!(
+            generate(9, 8, 50, 3).unwrap(),
+            generate(9, 8, 50, 3).unwrap()
+        );
+        assert_ne!(
+            generate(9, 8, 50, 3).unwrap().input,
+            generate(9, 8, 50, 4).unwrap().input
+        );
+    }
+
+    #[test]
+    fn invalid_sizes() {
+        assert!(generate(1, 8, 50, 0).is_err());
+        assert!(generate(9, 1, 50, 0).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use std::fmt;
+
+use aoc_parse::IResult;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, one_of},
+    combinator::{cut, map_res, recognize, verify},
+    error::context,
+    multi::{many0, many1},
+    sequence::terminated,
+};
+use serde::{Deserialize, Serialize};
+
+// A step of the rearrangement.  Stacks are 0 based.  The puzzle only uses
+// `Move`; the rest make up the extended instruction set.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Instruction {
+    // Move `amount` crates from the top of `src` to the top of `dest`.
+    Move {
+        src: usize,
+        dest: usize,
+        amount: usize,
+    },
+    // Exchange the crates of two stacks.
+    Swap {
+        a: usize,
+        b: usize,
+    },
+    // Move `by` crates, one at a time, from the top of `stack` to its
+    // bottom.
+    Rotate {
+        stack: usize,
+        by: usize,
+    },
+    // Turn `stack` upside down.
+    Reverse {
+        stack: usize,
+    },
+}
+
+impl Instruction {
+    // Parse a move, the only instruction in the puzzle.
+    pub(crate) fn parse(i: &str) -> IResult<'_, Self> {
+        context("instruction", Self::parse_move)(i)
+    }
+
+    // Parse any instruction, including the extended ones.
+    pub(crate) fn parse_extended(i: &str) -> IResult<'_, Self> {
+        context(
+            "instruction",
+            alt((
+                Self::parse_move,
+                Self::parse_swap,
+                Self::parse_rotate,
+                Self::parse_reverse,
+            )),
+        )(i)
+    }
+
+    // This and the parsers below commit once the instruction's name has
+    // been read, so errors are reported within it rather than as an unknown
+    // instruction.  Stacks are converted from 1 based indexing to 0 based.
+    fn parse_move(i: &str) -> IResult<'_, Self> {
+        let (i, _) = tag("move ")(i)?;
+        cut(|i| {
+            let (i, amount) = decimal_value(i)?;
+            let (i, _) = tag(" from ")(i)?;
+            let (i, src) = stack_number(i)?;
+            let (i, _) = tag(" to ")(i)?;
+            let (i, dest) = stack_number(i)?;
+            Ok((
+                i,
+                Self::Move {
+                    src: src - 1,
+                    dest: dest - 1,
+                    amount,
+                },
+            ))
+        })(i)
+    }
+
+    fn parse_swap(i: &str) -> IResult<'_, Self> {
+        let (i, _) = tag("swap ")(i)?;
+        cut(|i| {
+            let (i, a) = stack_number(i)?;
+            let (i, _) = char(' ')(i)?;
+            let (i, b) = stack_number(i)?;
+            Ok((i, Self::Swap { a: a - 1, b: b - 1 }))
+        })(i)
+    }
+
+    fn parse_rotate(i: &str) -> IResult<'_, Self> {
+        let (i, _) = tag("rotate ")(i)?;
+        cut(|i| {
+            let (i, stack) = stack_number(i)?;
+            let (i, _) = tag(" by ")(i)?;
+            let (i, by) = decimal_value(i)?;
+            Ok((
+                i,
+                Self::Rotate {
+                    stack: stack - 1,
+                    by,
+                },
+            ))
+        })(i)
+    }
+
+    fn parse_reverse(i: &str) -> IResult<'_, Self> {
+        let (i, _) = tag("reverse ")(i)?;
+        cut(|i| {
+            let (i, stack) = stack_number(i)?;
+            Ok((i, Self::Reverse { stack: stack - 1 }))
+        })(i)
+    }
+
+    // The stacks the instruction touches, each once.
+    pub(crate) fn stacks(&self) -> impl Iterator<Item = usize> {
+        let [a, b] = match *self {
+            Self::Move { src, dest, .. } => [src, dest],
+            Self::Swap { a, b } => [a, b],
+            Self::Rotate { stack, .. } | Self::Reverse { stack } => [stack, stack],
+        };
+        [a, b].into_iter().take(if a == b { 1 } else { 2 })
+    }
+
+    // The same instruction with each stack renumbered by `f`.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn map_stacks(&self, mut f: impl FnMut(usize) -> usize) -> Self {
+        match *self {
+            Self::Move { src, dest, amount } => Self::Move {
+                src: f(src),
+                dest: f(dest),
+                amount,
+            },
+            Self::Swap { a, b } => Self::Swap { a: f(a), b: f(b) },
+            Self::Rotate { stack, by } => Self::Rotate {
+                stack: f(stack),
+                by,
+            },
+            Self::Reverse { stack } => Self::Reverse { stack: f(stack) },
+        }
+    }
+}
+
+// Written as in the input, converting back to 1 based indexing.
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Move { src, dest, amount } => {
+                write!(f, "move {} from {} to {}", amount, src + 1, dest + 1)
+            }
+            Self::Swap { a, b } => write!(f, "swap {} {}", a + 1, b + 1),
+            Self::Rotate { stack, by } => write!(f, "rotate {} by {}", stack + 1, by),
+            Self::Reverse { stack } => write!(f, "reverse {}", stack + 1),
+        }
+    }
+}
+
+// Adapted from https://github.com/Geal/nom/blob/main/doc/nom_recipes.md#integers
+fn decimal_value(input: &str) -> IResult<'_, usize> {
+    context(
+        "number",
+        map_res(
+            recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
+            |value: &str| value.parse::<usize>(),
+        ),
+    )(input)
+}
+
+// Stacks are numbered from 1.
+fn stack_number(input: &str) -> IResult<'_, usize> {
+    context("stack number", verify(decimal_value, |&number| number > 0))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_instruction() {
+        assert_eq!(
+            Instruction::parse("move 1 from 2 to 3").unwrap(),
+            (
+                "",
+                Instruction::Move {
+                    src: 1,
+                    dest: 2,
+                    amount: 1
+                }
+            )
+        );
+        // The extended instructions are only accepted when asked for.
+        assert!(Instruction::parse("swap 1 2").is_err());
+        assert_eq!(
+            Instruction::parse_extended("swap 1 2").unwrap(),
+            ("", Instruction::Swap { a: 0, b: 1 })
+        );
+        assert_eq!(
+            Instruction::parse_extended("rotate 3 by 10").unwrap(),
+            ("", Instruction::Rotate { stack: 2, by: 10 })
+        );
+        assert_eq!(
+            Instruction::parse_extended("reverse 1").unwrap(),
+            ("", Instruction::Reverse { stack: 0 })
+        );
+    }
+
+    #[test]
+    fn test_instruction_display() {
+        for text in [
+            "move 3 from 1 to 3",
+            "swap 2 1",
+            "rotate 3 by 0",
+            "reverse 12",
+        ] {
+            let instruction = Instruction::parse_extended(text).unwrap().1;
+            assert_eq!(instruction.to_string(), text);
+        }
+    }
+}
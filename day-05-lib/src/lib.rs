@@ -0,0 +1,146 @@
+use anyhow::{bail, Result};
+use log::info;
+
+mod error;
+mod generate;
+mod instruction;
+mod optimize;
+#[cfg(feature = "parallel")]
+mod parallel;
+mod problem;
+mod snapshot;
+mod stack;
+mod trace;
+
+pub use error::{InstructionError, InstructionErrorKind};
+pub use generate::{generate, Generated};
+pub use instruction::Instruction;
+pub use optimize::optimize;
+#[cfg(feature = "parallel")]
+pub use parallel::{
+    part1_parallel, part2_parallel, segments, solution_parallel, Segment, DEFAULT_WINDOW,
+};
+pub use problem::{CraneModel, MoveMethod, Problem, DEFAULT_HISTORY_LIMIT};
+pub use snapshot::Snapshot;
+pub use stack::{draw_stacks, Stack};
+pub use trace::{trace, Trace, TraceStep};
+
+use stack::parse_stacks;
+
+// Rearrange the stacks with a crane of type `model`.
+pub fn rearrange(mut problem: Problem, model: CraneModel) -> Result<Problem> {
+    problem.execute(model)?;
+    Ok(problem)
+}
+
+// Rearrange the stacks with a crane of type `model` and read the crates left
+// on top.
+pub fn solution(input: &str, model: CraneModel) -> Result<String> {
+    rearrange(input.parse()?, model)?.tops()
+}
+
+// Rearrange the stacks with optimized instructions, checking that they leave
+// the same stacks as the original ones.
+pub fn solution_optimized(mut problem: Problem, model: CraneModel) -> Result<String> {
+    let expected = rearrange(problem.clone(), model)?;
+    let count = problem.instructions.len();
+    problem.instructions = optimize(problem.instructions, model).into();
+    info!(
+        "Optimized {} instructions to {}",
+        count,
+        problem.instructions.len()
+    );
+    problem.execute(model)?;
+
+    if problem.stacks != expected.stacks {
+        bail!("The optimized instructions left the stacks differently");
+    }
+    problem.tops()
+}
+
+// Compute the answer to part 1.
+pub fn part1(input: &str) -> Result<String> {
+    solution(input, CraneModel::CrateMover9000)
+}
+
+// Compute the answer to part 2.
+pub fn part2(input: &str) -> Result<String> {
+    solution(input, CraneModel::CrateMover9001)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap(), "CMZ".to_string());
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap(), "MCD".to_string());
+    }
+
+    // Drive the simulation one instruction at a time, as the animation and
+    // debugger do.
+    #[test]
+    fn test_public_api() {
+        let mut problem = Problem::parse(EXAMPLE_INPUT).unwrap();
+        assert_eq!(problem.remaining(), 4);
+        assert_eq!(
+            problem.next_instruction().unwrap().to_string(),
+            "move 1 from 2 to 1"
+        );
+        problem.step(CraneModel::CrateMover9001).unwrap();
+        assert_eq!(problem.executed(), 1);
+        assert_eq!(problem.stacks()[0].top(), Some("D"));
+        assert_eq!(problem.stacks()[0].index(), 1);
+        while !problem.is_finished() {
+            problem.step(CraneModel::CrateMover9001).unwrap();
+        }
+        assert_eq!(problem.tops().unwrap(), "MCD");
+        assert!(problem.step(CraneModel::CrateMover9001).is_err());
+
+        // The same problem built in code.
+        let stacks = vec![
+            Stack::new(1, ["Z", "N"]),
+            Stack::new(2, ["M", "C", "D"]),
+            Stack::new(3, ["P"]),
+        ];
+        let instructions: Vec<_> = [(1, 2, 1), (3, 1, 3), (2, 2, 1), (1, 1, 2)]
+            .into_iter()
+            .map(
+                |(amount, src, dest): (usize, usize, usize)| Instruction::Move {
+                    src: src - 1,
+                    dest: dest - 1,
+                    amount,
+                },
+            )
+            .collect();
+        let mut problem = Problem::new(stacks.clone(), instructions).unwrap();
+        assert_eq!(problem, Problem::parse(EXAMPLE_INPUT).unwrap());
+        problem.execute(CraneModel::CrateMover9000).unwrap();
+        assert_eq!(problem.tops().unwrap(), "CMZ");
+        assert_eq!(problem.stacks()[2].crates(), &["P", "D", "N", "Z"]);
+
+        let error = Problem::new(stacks, vec![Instruction::Reverse { stack: 3 }]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "instruction 1: 'reverse 4' references stack 4 but only 3 exist"
+        );
+    }
+
+    #[test]
+    fn test_solution_optimized() {
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            let problem = EXAMPLE_INPUT.parse().unwrap();
+            assert_eq!(
+                solution_optimized(problem, model).unwrap(),
+                solution(EXAMPLE_INPUT, model).unwrap()
+            );
+        }
+    }
+}
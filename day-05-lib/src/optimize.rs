@@ -0,0 +1,159 @@
+// Shortening instruction lists without changing where the crates end up.
+
+use crate::{CraneModel, Instruction};
+
+// Rewrite `instructions` for a crane of type `model`, cancelling moves that
+// are immediately undone and, for the CrateMover 9000, merging consecutive
+// moves between the same stacks.
+//
+// The CrateMover 9001 keeps each batch of crates in order, so moving `a`
+// crates and then `b` stacks them differently from moving `a + b` at once
+// and its moves aren't merged.  Other instructions are kept as they are and
+// separate the moves either side of them.  Instructions are assumed to be
+// valid; an impossible move may be cancelled rather than fail.
+pub fn optimize(
+    instructions: impl IntoIterator<Item = Instruction>,
+    model: CraneModel,
+) -> Vec<Instruction> {
+    let mut optimized: Vec<Instruction> = Vec::new();
+    for instruction in instructions {
+        let Instruction::Move { src, dest, amount } = instruction else {
+            optimized.push(instruction);
+            continue;
+        };
+        match optimized.last_mut() {
+            // Moving crates back where they came from undoes the previous
+            // move with either crane.
+            Some(Instruction::Move {
+                src: last_src,
+                dest: last_dest,
+                amount: last_amount,
+            }) if *last_src == dest && *last_dest == src && *last_amount == amount => {
+                optimized.pop();
+            }
+            Some(Instruction::Move {
+                src: last_src,
+                dest: last_dest,
+                amount: last_amount,
+            }) if model == CraneModel::CrateMover9000 && *last_src == src && *last_dest == dest => {
+                *last_amount += amount;
+            }
+            _ => optimized.push(instruction),
+        }
+    }
+    optimized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Problem;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    fn moves(moves: &[(usize, usize, usize)]) -> Vec<Instruction> {
+        moves
+            .iter()
+            .map(|&(amount, src, dest)| Instruction::Move { src, dest, amount })
+            .collect()
+    }
+
+    #[test]
+    fn merging() {
+        let instructions = moves(&[(1, 0, 1), (2, 0, 1), (1, 1, 2), (1, 0, 1)]);
+        assert_eq!(
+            optimize(instructions.clone(), CraneModel::CrateMover9000),
+            moves(&[(3, 0, 1), (1, 1, 2), (1, 0, 1)])
+        );
+        assert_eq!(
+            optimize(instructions.clone(), CraneModel::CrateMover9001),
+            instructions
+        );
+    }
+
+    #[test]
+    fn cancelling() {
+        // Cancelling a pair can expose another one.
+        let instructions = moves(&[(1, 0, 1), (2, 1, 2), (2, 2, 1), (1, 1, 0), (1, 2, 0)]);
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            assert_eq!(optimize(instructions.clone(), model), moves(&[(1, 2, 0)]));
+        }
+        // Only moves of the same number of crates cancel.
+        let instructions = moves(&[(2, 0, 1), (1, 1, 0)]);
+        assert_eq!(
+            optimize(instructions.clone(), CraneModel::CrateMover9001),
+            instructions
+        );
+        // A merged move can be cancelled as a whole.
+        assert_eq!(
+            optimize(
+                moves(&[(1, 0, 1), (1, 0, 1), (2, 1, 0)]),
+                CraneModel::CrateMover9000
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn other_instructions() {
+        // Would cancel and then merge without the instructions between.
+        let mut instructions = moves(&[(1, 0, 1), (1, 1, 0), (1, 1, 0)]);
+        instructions.insert(1, Instruction::Reverse { stack: 0 });
+        instructions.insert(3, Instruction::Swap { a: 0, b: 1 });
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            assert_eq!(optimize(instructions.clone(), model), instructions);
+        }
+    }
+
+    // Random valid moves between three stacks of ten crates each, biased
+    // towards repeating or undoing the previous move.
+    fn random_problem(rng: &mut fastrand::Rng) -> Problem {
+        let mut problem = EXAMPLE_INPUT.parse::<Problem>().unwrap();
+        for (i, stack) in problem.stacks.iter_mut().enumerate() {
+            stack.values = (0..10).map(|j| format!("{}{}", i, j)).collect();
+        }
+        problem.instructions.clear();
+
+        let mut sizes = [10; 3];
+        let (mut src, mut dest, mut amount) = (0, 1, 1);
+        for _ in 0..50 {
+            match rng.u8(0..3) {
+                0 => {}
+                1 => (src, dest) = (dest, src),
+                _ => {
+                    src = rng.usize(0..3);
+                    dest = (src + rng.usize(1..3)) % 3;
+                    amount = rng.usize(1..4);
+                }
+            }
+            let amount = amount.min(sizes[src]);
+            if amount == 0 {
+                continue;
+            }
+            sizes[src] -= amount;
+            sizes[dest] += amount;
+            problem
+                .instructions
+                .push_back(Instruction::Move { src, dest, amount });
+        }
+        problem
+    }
+
+    #[test]
+    fn same_final_state() {
+        let mut rng = fastrand::Rng::with_seed(5);
+        for _ in 0..200 {
+            let problem = random_problem(&mut rng);
+            for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+                let mut expected = problem.clone();
+                expected.execute(model).unwrap();
+
+                let mut optimized = problem.clone();
+                optimized.instructions = optimize(problem.instructions.clone(), model).into();
+                assert!(optimized.instructions.len() <= problem.instructions.len());
+                optimized.execute(model).unwrap();
+                assert_eq!(optimized.stacks, expected.stacks, "{:?}", problem);
+            }
+        }
+    }
+}
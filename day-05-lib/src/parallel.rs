@@ -0,0 +1,295 @@
+// Carrying out instructions on separate threads with rayon, for generated
+// inputs with many stacks.  Only built with the `parallel` feature.
+//
+// Instructions touching different stacks can be carried out in any order.
+// The instructions are cut into windows of consecutive instructions and each
+// window split into segments which share no stacks, so the segments of a
+// window can run at the same time.
+
+use std::{collections::VecDeque, mem};
+
+use anyhow::Result;
+use rayon::prelude::*;
+
+use crate::{CraneModel, Instruction, MoveMethod, Problem, Stack};
+
+// Instructions per window when solving in parallel.  Shorter windows split
+// into more segments but spend longer handing out work, which costs more
+// than moving a few crates; see the benchmarks.
+pub const DEFAULT_WINDOW: usize = 65536;
+
+// Instructions from one window which only touch `stacks`, in their original
+// order.  Stacks in `instructions` are renumbered as positions in `stacks`,
+// which lists them in the order they're first touched.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Segment {
+    pub stacks: Vec<usize>,
+    pub instructions: Vec<Instruction>,
+}
+
+// Split `instructions` into windows of up to `window` instructions and each
+// window into segments touching disjoint sets of stacks.
+pub fn segments(instructions: &[Instruction], window: usize) -> Vec<Vec<Segment>> {
+    let stacks = instructions
+        .iter()
+        .flat_map(Instruction::stacks)
+        .max()
+        .map_or(0, |max| max + 1);
+    // Indexed by stack.  Entries touched by a window are reset after it, so
+    // each window costs time in its length rather than the number of stacks.
+    let mut parent: Vec<usize> = (0..stacks).collect();
+    let mut segment_of = vec![usize::MAX; stacks];
+    let mut position = vec![usize::MAX; stacks];
+
+    instructions
+        .chunks(window.max(1))
+        .map(|chunk| {
+            for instruction in chunk {
+                let mut touched = instruction.stacks();
+                let first = find(&mut parent, touched.next().unwrap());
+                for stack in touched {
+                    let root = find(&mut parent, stack);
+                    parent[root] = first;
+                }
+            }
+
+            let mut segments: Vec<Segment> = Vec::new();
+            for instruction in chunk {
+                let root = find(&mut parent, instruction.stacks().next().unwrap());
+                if segment_of[root] == usize::MAX {
+                    segment_of[root] = segments.len();
+                    segments.push(Segment {
+                        stacks: Vec::new(),
+                        instructions: Vec::new(),
+                    });
+                }
+                let segment = &mut segments[segment_of[root]];
+                let renumbered = instruction.map_stacks(|stack| {
+                    if position[stack] == usize::MAX {
+                        position[stack] = segment.stacks.len();
+                        segment.stacks.push(stack);
+                    }
+                    position[stack]
+                });
+                segment.instructions.push(renumbered);
+            }
+
+            for instruction in chunk {
+                for stack in instruction.stacks() {
+                    parent[stack] = stack;
+                    segment_of[stack] = usize::MAX;
+                    position[stack] = usize::MAX;
+                }
+            }
+            segments
+        })
+        .collect()
+}
+
+// The root of `stack`'s set, halving paths along the way.
+fn find(parent: &mut [usize], mut stack: usize) -> usize {
+    while parent[stack] != stack {
+        parent[stack] = parent[parent[stack]];
+        stack = parent[stack];
+    }
+    stack
+}
+
+impl Problem {
+    // Carry out every remaining instruction like `execute`, running the
+    // segments of each window of `window` instructions on separate threads.
+    // Leaves the same stacks, and fails with the same error, as `execute`.
+    // Instructions run this way can't be undone.
+    pub fn execute_parallel(&mut self, model: CraneModel, window: usize) -> Result<()> {
+        let runnable = self.runnable();
+        let instructions: Vec<_> = self.instructions.drain(..runnable).collect();
+        self.clear_history();
+
+        for segments in segments(&instructions, window) {
+            // Move each segment's crates out so the segments can be
+            // rearranged at once, then put them back.
+            let mut parts = segments
+                .iter()
+                .map(|segment| {
+                    let stacks = segment
+                        .stacks
+                        .iter()
+                        .map(|&stack| Stack {
+                            values: mem::take(&mut self.stacks[stack].values),
+                            index: self.stacks[stack].index,
+                        })
+                        .collect();
+                    Problem::from_parts(stacks, VecDeque::new(), 0)
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let result = parts
+                .par_iter_mut()
+                .zip(&segments)
+                .try_for_each(|(part, segment)| {
+                    segment.instructions.iter().try_for_each(|instruction| {
+                        part.apply(instruction, model, MoveMethod::InPlace)
+                    })
+                });
+            for (part, segment) in parts.into_iter().zip(&segments) {
+                for (stack, &index) in part.stacks.into_iter().zip(&segment.stacks) {
+                    self.stacks[index].values = stack.values;
+                }
+            }
+            result?;
+            self.executed += segments
+                .iter()
+                .map(|segment| segment.instructions.len())
+                .sum::<usize>();
+        }
+
+        // Anything left starts with an instruction moving more crates than
+        // its stack holds, which fails here as it would serially.
+        self.execute(model)
+    }
+
+    // Number of remaining instructions which can be carried out before one
+    // moves more crates than its source stack holds.  Only moves change
+    // how many crates a stack holds, so this is cheap to work out without
+    // moving any.
+    fn runnable(&self) -> usize {
+        let mut sizes: Vec<_> = self.stacks.iter().map(|stack| stack.values.len()).collect();
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            match *instruction {
+                Instruction::Move { src, dest, amount } => {
+                    if amount > sizes[src] {
+                        return i;
+                    }
+                    sizes[src] -= amount;
+                    sizes[dest] += amount;
+                }
+                Instruction::Swap { a, b } => sizes.swap(a, b),
+                Instruction::Rotate { .. } | Instruction::Reverse { .. } => {}
+            }
+        }
+        self.instructions.len()
+    }
+}
+
+// Rearrange the stacks with a crane of type `model`, running instructions
+// on separate stacks in parallel, and read the crates left on top.
+pub fn solution_parallel(mut problem: Problem, model: CraneModel) -> Result<String> {
+    problem.execute_parallel(model, DEFAULT_WINDOW)?;
+    problem.tops()
+}
+
+// Compute the answer to part 1 in parallel.
+pub fn part1_parallel(input: &str) -> Result<String> {
+    solution_parallel(input.parse()?, CraneModel::CrateMover9000)
+}
+
+// Compute the answer to part 2 in parallel.
+pub fn part2_parallel(input: &str) -> Result<String> {
+    solution_parallel(input.parse()?, CraneModel::CrateMover9001)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn example() {
+        assert_eq!(part1_parallel(EXAMPLE_INPUT).unwrap(), "CMZ");
+        assert_eq!(part2_parallel(EXAMPLE_INPUT).unwrap(), "MCD");
+    }
+
+    #[test]
+    fn split_into_segments() {
+        let instructions = Problem::parse_extended(
+            "[A] [B] [C] [D] [E]\n 1   2   3   4   5 \n\n\
+             move 1 from 4 to 2\nreverse 5\nswap 2 1\nrotate 3 by 1\nmove 1 from 5 to 3\n",
+        )
+        .unwrap()
+        .instructions;
+        let instructions: Vec<_> = instructions.into();
+        let segments = segments(&instructions, 4);
+        assert_eq!(
+            segments,
+            [
+                vec![
+                    Segment {
+                        stacks: vec![3, 1, 0],
+                        instructions: vec![
+                            Instruction::Move {
+                                src: 0,
+                                dest: 1,
+                                amount: 1
+                            },
+                            Instruction::Swap { a: 1, b: 2 },
+                        ],
+                    },
+                    Segment {
+                        stacks: vec![4],
+                        instructions: vec![Instruction::Reverse { stack: 0 }],
+                    },
+                    Segment {
+                        stacks: vec![2],
+                        instructions: vec![Instruction::Rotate { stack: 0, by: 1 }],
+                    },
+                ],
+                vec![Segment {
+                    stacks: vec![4, 2],
+                    instructions: vec![Instruction::Move {
+                        src: 0,
+                        dest: 1,
+                        amount: 1
+                    }],
+                }],
+            ]
+        );
+    }
+
+    #[test]
+    fn same_as_serial() {
+        for seed in 0..10 {
+            let generated = generate(200, 8, 2000, seed).unwrap();
+            for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+                for window in [1, 16, 300, 5000] {
+                    let mut problem = Problem::parse(&generated.input).unwrap();
+                    problem.execute_parallel(model, window).unwrap();
+                    assert_eq!(problem.stacks(), generated.final_stacks(model));
+                    assert_eq!(problem.executed(), 2000);
+                    assert!(problem.is_finished());
+                }
+            }
+        }
+
+        // With the extended instructions, checked against `execute`.
+        let input = "[A]     [C] [D]\n[E] [F] [G] [H]\n 1   2   3   4 \n\n\
+                     swap 1 2\nrotate 3 by 5\nmove 2 from 4 to 1\nreverse 1\nswap 4 3\n\
+                     move 1 from 1 to 2\nrotate 1 by 2\nreverse 3\nmove 2 from 2 to 4\n";
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            for window in [1, 2, 3, 100] {
+                let mut serial = Problem::parse_extended(input).unwrap();
+                serial.execute(model).unwrap();
+                let mut parallel = Problem::parse_extended(input).unwrap();
+                parallel.execute_parallel(model, window).unwrap();
+                assert_eq!(parallel, serial);
+            }
+        }
+    }
+
+    #[test]
+    fn fails_as_serial() {
+        let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2", "move 3 from 1 to 2");
+        let mut serial: Problem = input.parse().unwrap();
+        let serial_error = serial.execute(CraneModel::CrateMover9001).unwrap_err();
+        let mut parallel: Problem = input.parse().unwrap();
+        let parallel_error = parallel
+            .execute_parallel(CraneModel::CrateMover9001, 2)
+            .unwrap_err();
+        assert_eq!(parallel_error.to_string(), serial_error.to_string());
+        assert_eq!(
+            parallel_error.to_string(),
+            "instruction 4: 'move 3 from 1 to 2' moves 3 crates but the stack only holds 2"
+        );
+        assert_eq!(parallel, serial);
+    }
+}
@@ -0,0 +1,901 @@
+use std::{collections::VecDeque, fmt, mem, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+use aoc_parse::{parse_complete, IResult};
+use log::{debug, log_enabled, Level};
+use nom::{
+    character::complete::{line_ending, multispace0},
+    combinator::{cut, eof, opt},
+    multi::many_till,
+    sequence::{preceded, terminated},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    draw_stacks, parse_stacks, Instruction, InstructionError, InstructionErrorKind, Snapshot, Stack,
+};
+
+// The crane rearranging the stacks.  Part 1 uses the CrateMover 9000 and
+// part 2 the CrateMover 9001.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CraneModel {
+    // Moves one crate at a time.
+    #[serde(rename = "9000")]
+    CrateMover9000,
+    // Moves several crates at once, keeping their order.
+    #[serde(rename = "9001")]
+    CrateMover9001,
+}
+
+impl fmt::Display for CraneModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CrateMover9000 => write!(f, "9000"),
+            Self::CrateMover9001 => write!(f, "9001"),
+        }
+    }
+}
+
+// Parse a model number, e.g. `9001`.
+impl FromStr for CraneModel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "9000" => Ok(Self::CrateMover9000),
+            "9001" => Ok(Self::CrateMover9001),
+            _ => Err(anyhow!("unknown crane model: {}", s)),
+        }
+    }
+}
+
+// How a crane's moves are carried out.  Both leave the same stacks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MoveMethod {
+    // Drain the crates from the source stack straight into the
+    // destination, without allocating.
+    #[default]
+    InPlace,
+    // Pop crates one at a time or, for the CrateMover 9001, split them off
+    // into a new `VecDeque`.  Kept as a baseline for benchmarks.
+    Allocating,
+}
+
+// Number of instructions a problem remembers for `Problem::undo` unless
+// told otherwise.
+pub const DEFAULT_HISTORY_LIMIT: usize = 1000;
+
+// The most recent instructions carried out, oldest first, along with the
+// crane that carried out each.
+#[derive(Clone, Debug)]
+struct History {
+    entries: VecDeque<(Instruction, CraneModel)>,
+    limit: usize,
+}
+
+impl History {
+    fn push(&mut self, instruction: Instruction, model: CraneModel) {
+        if self.limit == 0 {
+            return;
+        }
+        if self.entries.len() == self.limit {
+            self.entries.pop_front();
+        }
+        self.entries.push_back((instruction, model));
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            entries: VecDeque::new(),
+            limit: DEFAULT_HISTORY_LIMIT,
+        }
+    }
+}
+
+// Stacks of crates and the instructions for rearranging them, part way
+// through being carried out.  Deserialized through `Snapshot`, which has the
+// same fields, so the instructions are checked against the stacks as when
+// parsing.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(try_from = "Snapshot")]
+pub struct Problem {
+    pub(crate) stacks: Vec<Stack>,
+    // Instructions still to be carried out.
+    pub(crate) instructions: VecDeque<Instruction>,
+    // Instructions carried out so far.
+    pub(crate) executed: usize,
+    #[serde(skip)]
+    history: History,
+}
+
+// The history only matters to `undo`, so problems with the same stacks and
+// instructions left are equal however they got there.
+impl PartialEq for Problem {
+    fn eq(&self, other: &Self) -> bool {
+        self.stacks == other.stacks
+            && self.instructions == other.instructions
+            && self.executed == other.executed
+    }
+}
+
+impl Eq for Problem {}
+
+type InstructionParser = for<'a> fn(&'a str) -> IResult<'a, Instruction>;
+
+// Parse a drawing of the stacks followed by instructions, each parsed by
+// `instruction`.
+fn parse_problem(i: &str, instruction: InstructionParser) -> IResult<'_, Problem> {
+    let (i, stacks) = parse_stacks(i)?;
+    let (i, _) = line_ending(i)?;
+    // `cut` stops a malformed instruction from being mistaken for the end of
+    // the list.
+    let (i, (instructions, _)) = many_till(
+        terminated(cut(instruction), opt(line_ending)),
+        preceded(multispace0, eof),
+    )(i)?;
+
+    Ok((
+        i,
+        Problem {
+            stacks,
+            instructions: instructions.into(),
+            executed: 0,
+            history: History::default(),
+        },
+    ))
+}
+
+impl Problem {
+    // Stacks and instructions built in code rather than parsed.  Fails if
+    // an instruction names a stack that doesn't exist.
+    pub fn new(stacks: Vec<Stack>, instructions: Vec<Instruction>) -> Result<Self> {
+        Self::from_parts(stacks, instructions.into(), 0)
+    }
+
+    // A problem with `executed` instructions already carried out, checking
+    // the remaining ones as `new` does.
+    pub(crate) fn from_parts(
+        stacks: Vec<Stack>,
+        instructions: VecDeque<Instruction>,
+        executed: usize,
+    ) -> Result<Self> {
+        let problem = Self {
+            stacks,
+            instructions,
+            executed,
+            history: History::default(),
+        };
+        problem.check_stacks()?;
+        Ok(problem)
+    }
+
+    // Parse a puzzle input: a drawing of the stacks, a blank line and a
+    // move on each following line.  Fails with the position of any syntax
+    // error or if an instruction names a stack that doesn't exist.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_with(input, Instruction::parse)
+    }
+
+    // Parse an input which may also use the swap, rotate and reverse
+    // instructions.
+    pub fn parse_extended(input: &str) -> Result<Self> {
+        Self::parse_with(input, Instruction::parse_extended)
+    }
+
+    fn parse_with(input: &str, instruction: InstructionParser) -> Result<Self> {
+        let problem = parse_complete(input, |i| parse_problem(i, instruction))
+            .map_err(|e| anyhow!("Error parsing problem: {}", e))?;
+        problem.check_stacks()?;
+        Ok(problem)
+    }
+
+    // The stacks in their current state, left to right.
+    pub fn stacks(&self) -> &[Stack] {
+        &self.stacks
+    }
+
+    // The instruction `step` will carry out next.
+    pub fn next_instruction(&self) -> Option<&Instruction> {
+        self.instructions.front()
+    }
+
+    // Instructions still to be carried out.
+    pub fn remaining(&self) -> usize {
+        self.instructions.len()
+    }
+
+    // Whether every instruction has been carried out.
+    pub fn is_finished(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    // Number of instructions carried out so far.
+    pub fn executed(&self) -> usize {
+        self.executed
+    }
+
+    // Number of instructions `undo` can currently reverse.
+    pub fn undoable(&self) -> usize {
+        self.history.entries.len()
+    }
+
+    // Remember up to `limit` instructions for `undo`, forgetting the oldest
+    // if more are already remembered.  A limit of 0 turns undo off.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        let excess = self.history.entries.len().saturating_sub(limit);
+        self.history.entries.drain(..excess);
+        self.history.limit = limit;
+    }
+
+    // Check that the instruction numbered `number` names existing stacks
+    // and, if `check_crates`, that its source stack currently holds enough
+    // crates.
+    fn check_instruction(
+        &self,
+        number: usize,
+        instruction: &Instruction,
+        check_crates: bool,
+    ) -> Result<(), InstructionError> {
+        let error = |kind| InstructionError::new(number, instruction, kind);
+        for stack in instruction.stacks() {
+            if stack >= self.stacks.len() {
+                return Err(error(InstructionErrorKind::NoSuchStack {
+                    stack: stack + 1,
+                    stacks: self.stacks.len(),
+                }));
+            }
+        }
+        if let Instruction::Move { src, amount, .. } = *instruction {
+            let available = self.stacks[src].values.len();
+            if check_crates && amount > available {
+                return Err(error(InstructionErrorKind::NotEnoughCrates {
+                    amount,
+                    available,
+                }));
+            }
+        }
+        Ok(())
+    }
+
+    // Check that every remaining instruction names existing stacks.
+    fn check_stacks(&self) -> Result<(), InstructionError> {
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            self.check_instruction(self.executed + i + 1, instruction, false)?;
+        }
+        Ok(())
+    }
+
+    // Carry out the next instruction with a crane of type `model`.  Fails,
+    // leaving the stacks as they were, if the instruction moves more crates
+    // than its source stack holds or there are no instructions left.
+    pub fn step(&mut self, model: CraneModel) -> Result<()> {
+        self.step_with(model, MoveMethod::default())
+    }
+
+    // Carry out the next instruction with a crane of type `model`, moving
+    // crates using `method`.
+    pub fn step_with(&mut self, model: CraneModel, method: MoveMethod) -> Result<()> {
+        let instruction = self
+            .instructions
+            .front()
+            .ok_or_else(|| anyhow!("step called with empty instructions"))?;
+        self.check_instruction(self.executed + 1, instruction, true)?;
+        let instruction = self.instructions.pop_front().unwrap();
+        self.executed += 1;
+        debug!("{}", instruction);
+        self.apply(&instruction, model, method)?;
+        self.history.push(instruction, model);
+        // Checked first so steps don't walk every stack with logging off.
+        if log_enabled!(Level::Debug) {
+            for stack in &self.stacks {
+                debug!("  {}: {:?}", stack.index, stack.values);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Reverse the last instruction carried out, with the crane that carried
+    // it out, and put it back at the front of the remaining instructions.
+    // Fails if no instructions have been carried out or they have all been
+    // undone or forgotten, see `set_history_limit`.
+    pub fn undo(&mut self) -> Result<()> {
+        let (instruction, model) = self
+            .history
+            .entries
+            .pop_back()
+            .ok_or_else(|| anyhow!("nothing to undo"))?;
+        let inverse = match instruction {
+            // Moving the crates back with the same crane puts them back in
+            // their original order: the CrateMover 9000 reverses them again.
+            Instruction::Move { src, dest, amount } => Instruction::Move {
+                src: dest,
+                dest: src,
+                amount,
+            },
+            // Rotating the rest of the way round.
+            Instruction::Rotate { stack, by } => {
+                let len = self.stacks[stack].values.len();
+                Instruction::Rotate {
+                    stack,
+                    by: len - by.checked_rem(len).unwrap_or(0),
+                }
+            }
+            // Swapping and reversing undo themselves.
+            Instruction::Swap { .. } | Instruction::Reverse { .. } => instruction.clone(),
+        };
+        debug!("undo {}", instruction);
+        self.apply(&inverse, model, MoveMethod::default())?;
+        self.instructions.push_front(instruction);
+        self.executed -= 1;
+        Ok(())
+    }
+
+    // Forget the instructions carried out so far, which `undo` can then no
+    // longer reverse.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn clear_history(&mut self) {
+        self.history.entries.clear();
+    }
+
+    // Carry out `instruction`, which has already been checked.
+    pub(crate) fn apply(
+        &mut self,
+        instruction: &Instruction,
+        model: CraneModel,
+        method: MoveMethod,
+    ) -> Result<()> {
+        match *instruction {
+            Instruction::Move { src, dest, amount } => match method {
+                MoveMethod::InPlace => self.move_in_place(src, dest, amount, model)?,
+                MoveMethod::Allocating => self.move_allocating(src, dest, amount, model)?,
+            },
+            Instruction::Swap { a, b } => {
+                // Swapping a stack with itself does nothing.
+                if let Ok([a, b]) = self.stacks.get_disjoint_mut([a, b]) {
+                    mem::swap(&mut a.values, &mut b.values);
+                }
+            }
+            Instruction::Rotate { stack, by } => {
+                let values = &mut self.stacks[stack].values;
+                if !values.is_empty() {
+                    // The top of the stack is the back of `values`.
+                    let by = by % values.len();
+                    values.rotate_right(by);
+                }
+            }
+            Instruction::Reverse { stack } => self.stacks[stack].values.make_contiguous().reverse(),
+        }
+        Ok(())
+    }
+
+    fn move_in_place(
+        &mut self,
+        src: usize,
+        dest: usize,
+        amount: usize,
+        model: CraneModel,
+    ) -> Result<()> {
+        // Moving crates onto the stack they came from leaves it as it was.
+        let Ok([src, dest]) = self.stacks.get_disjoint_mut([src, dest]) else {
+            return Ok(());
+        };
+        let start = src
+            .values
+            .len()
+            .checked_sub(amount)
+            .ok_or_else(|| anyhow!("Can't move {amount} crates from {}", src.index))?;
+        let crates = src.values.drain(start..);
+        match model {
+            // Taken one at a time, the top crate ends up at the bottom.
+            CraneModel::CrateMover9000 => dest.values.extend(crates.rev()),
+            CraneModel::CrateMover9001 => dest.values.extend(crates),
+        }
+        Ok(())
+    }
+
+    fn move_allocating(
+        &mut self,
+        src: usize,
+        dest: usize,
+        amount: usize,
+        model: CraneModel,
+    ) -> Result<()> {
+        match model {
+            CraneModel::CrateMover9000 => {
+                for _ in 0..amount {
+                    let val = self.stacks[src].pop()?;
+                    self.stacks[dest].push(val);
+                }
+            }
+            CraneModel::CrateMover9001 => {
+                let values = self.stacks[src].take(amount)?;
+                for val in values {
+                    self.stacks[dest].push(val);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Carry out every remaining instruction with a crane of type `model`.
+    pub fn execute(&mut self, model: CraneModel) -> Result<()> {
+        self.execute_with(model, MoveMethod::default())
+    }
+
+    // Carry out every remaining instruction, moving crates using `method`.
+    pub fn execute_with(&mut self, model: CraneModel, method: MoveMethod) -> Result<()> {
+        for stack in &self.stacks {
+            debug!("  {}: {:?}", stack.index, stack.values);
+        }
+
+        while !self.instructions.is_empty() {
+            self.step_with(model, method)?;
+        }
+
+        Ok(())
+    }
+
+    // The crate on top of each stack, the puzzle's answer once every
+    // instruction has been carried out.  Fails if a stack is empty.
+    pub fn tops(&self) -> Result<String> {
+        self.stacks.iter().map(|stack| stack.peek()).collect()
+    }
+}
+
+// Draws the stacks as the input does.
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", draw_stacks(&self.stacks))
+    }
+}
+
+impl FromStr for Problem {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{part1, part2, rearrange};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    fn crates(labels: &[&str]) -> VecDeque<String> {
+        labels.iter().map(|&label| label.to_owned()).collect()
+    }
+
+    fn parsed_example() -> Problem {
+        Problem {
+            stacks: vec![
+                Stack {
+                    values: crates(&["Z", "N"]),
+                    index: 1,
+                },
+                Stack {
+                    values: crates(&["M", "C", "D"]),
+                    index: 2,
+                },
+                Stack {
+                    values: crates(&["P"]),
+                    index: 3,
+                },
+            ],
+            instructions: [
+                Instruction::Move {
+                    src: 1,
+                    dest: 0,
+                    amount: 1,
+                },
+                Instruction::Move {
+                    src: 0,
+                    dest: 2,
+                    amount: 3,
+                },
+                Instruction::Move {
+                    src: 1,
+                    dest: 0,
+                    amount: 2,
+                },
+                Instruction::Move {
+                    src: 0,
+                    dest: 1,
+                    amount: 1,
+                },
+            ]
+            .into(),
+            executed: 0,
+            history: History::default(),
+        }
+    }
+
+    #[test]
+    fn test_wide_labels() {
+        let input = "     [🎁]       \n[AB] [CD] [EFG]\n 1    2    3   \n\nmove 1 from 2 to 3\n";
+        let problem = input.parse::<Problem>().unwrap();
+        assert_eq!(
+            problem.stacks[1].values,
+            crates(&["CD", "🎁"]),
+            "{:?}",
+            problem.stacks
+        );
+        assert_eq!(problem.stacks[2].values, crates(&["EFG"]));
+        // Drawing the stacks gives back the input.
+        assert_eq!(
+            draw_stacks(&problem.stacks),
+            input.split("\n\n").next().unwrap().to_owned() + "\n"
+        );
+        assert_eq!(part1(input).unwrap(), "ABCD🎁");
+
+        let error = "[A]      [B]\n 1   2 \n\nmove 1 from 1 to 2\n"
+            .parse::<Problem>()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error parsing problem: line 1, column 10: expected crate above a stack index\n  [A]      [B]\n           ^"
+        );
+    }
+
+    #[test]
+    fn test_parse_problem() {
+        assert_eq!(EXAMPLE_INPUT.parse::<Problem>().unwrap(), parsed_example());
+    }
+
+    #[test]
+    fn test_trimmed_drawing() {
+        // As left by an editor stripping trailing whitespace.  Crates are
+        // placed by their column, so missing padding after the last crate
+        // on a level makes no difference.
+        let trimmed: String = EXAMPLE_INPUT
+            .lines()
+            .map(|line| line.trim_end().to_owned() + "\n")
+            .collect();
+        assert!(trimmed.starts_with("    [D]\n[N] [C]\n"));
+        assert_eq!(trimmed.parse::<Problem>().unwrap(), parsed_example());
+
+        let input = "     [🎁]\n[AB] [CD] [EFG]\n 1    2    3\n\nmove 1 from 2 to 3\n";
+        let problem = input.parse::<Problem>().unwrap();
+        assert_eq!(problem.stacks[1].values, crates(&["CD", "🎁"]));
+        assert_eq!(part1(input).unwrap(), "ABCD🎁");
+
+        // An index line longer than every level of the drawing, naming
+        // stacks no crate stands above.  They are still created, empty.
+        let input = "[A]\n[B] [C]\n 1   2   3   4\n\nmove 1 from 2 to 4\n";
+        let problem = input.parse::<Problem>().unwrap();
+        let heights: Vec<_> = problem.stacks.iter().map(|s| s.values.len()).collect();
+        assert_eq!(heights, [2, 1, 0, 0]);
+        let problem = rearrange(problem, CraneModel::CrateMover9000).unwrap();
+        assert_eq!(problem.stacks[3].values, crates(&["C"]));
+
+        // Even a stack with no crates at all and a trimmed index.
+        let input = "[A]\n 1   2\n\nmove 1 from 1 to 2\n";
+        assert_eq!(part2(input).unwrap_err().to_string(), "stack empty");
+        let problem = input.parse::<Problem>().unwrap();
+        assert_eq!(problem.stacks.len(), 2);
+        assert_eq!(
+            rearrange(problem, CraneModel::CrateMover9001)
+                .unwrap()
+                .stacks[1]
+                .values,
+            crates(&["A"])
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = EXAMPLE_INPUT.replace("move 3 from 1 to 3", "move 3 from one to 3");
+        let error = input.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Error parsing problem: line 7, column 13: expected number while parsing stack number in instruction\n  move 3 from one to 3\n              ^"
+        );
+    }
+
+    #[test]
+    fn test_instruction_errors() {
+        let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2", "move 1 from 1 to 4");
+        let error = input.parse::<Problem>().unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<InstructionError>(),
+            Some(&InstructionError {
+                number: 4,
+                text: "move 1 from 1 to 4".to_owned(),
+                kind: InstructionErrorKind::NoSuchStack {
+                    stack: 4,
+                    stacks: 3
+                },
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "instruction 4: 'move 1 from 1 to 4' references stack 4 but only 3 exist"
+        );
+
+        let input = EXAMPLE_INPUT.replace("move 2 from 2 to 1", "move 4 from 2 to 1");
+        let error = part2(&input).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "instruction 3: 'move 4 from 2 to 1' moves 4 crates but the stack only holds 2"
+        );
+
+        let input = EXAMPLE_INPUT.replace("move 1 from 2 to 1", "move 1 from 0 to 1");
+        assert!(input
+            .parse::<Problem>()
+            .unwrap_err()
+            .to_string()
+            .contains("line 6, column 13: expected stack number while parsing instruction"));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let json = serde_json::to_string(&parsed_example()).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Problem>(&json).unwrap(),
+            parsed_example()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_checks_stacks() {
+        // Written before `executed` was recorded.
+        let json = r#"{"stacks":[{"values":["A"],"index":1},{"values":[],"index":2}],"instructions":[{"Move":{"src":0,"dest":1,"amount":1}}]}"#;
+        let problem = serde_json::from_str::<Problem>(json).unwrap();
+        assert_eq!(problem.executed(), 0);
+
+        let json = json.replace(r#""dest":1"#, r#""dest":5"#);
+        let error = serde_json::from_str::<Problem>(&json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "instruction 1: 'move 1 from 1 to 6' references stack 6 but only 2 exist"
+        );
+    }
+
+    #[test]
+    fn test_extended_instructions() {
+        let input = EXAMPLE_INPUT.split("\n\n").next().unwrap().to_owned()
+            + "\n\nswap 1 2\nrotate 1 by 4\nreverse 3\nswap 3 3\nreverse 2\nmove 1 from 1 to 3\n";
+        assert!(input.parse::<Problem>().is_err());
+
+        let mut problem = Problem::parse_extended(&input).unwrap();
+        problem.execute(CraneModel::CrateMover9000).unwrap();
+        // Swapping gives [M C D] [Z N], rotating the first stack by 4 (once
+        // round plus one crate) gives [D M C] and reversing the second gives
+        // [N Z] before the move.
+        assert_eq!(
+            problem.stacks,
+            [
+                Stack {
+                    values: crates(&["D", "M"]),
+                    index: 1
+                },
+                Stack {
+                    values: crates(&["N", "Z"]),
+                    index: 2
+                },
+                Stack {
+                    values: crates(&["P", "C"]),
+                    index: 3
+                },
+            ]
+        );
+
+        // Rotating an empty stack does nothing.
+        let input = "[A]    \n 1   2 \n\nrotate 2 by 3\n";
+        let mut problem = Problem::parse_extended(input).unwrap();
+        problem.execute(CraneModel::CrateMover9001).unwrap();
+        assert_eq!(problem.stacks[1].values, crates(&[]));
+
+        let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2", "reverse 4");
+        assert_eq!(
+            Problem::parse_extended(&input).unwrap_err().to_string(),
+            "instruction 4: 'reverse 4' references stack 4 but only 3 exist"
+        );
+        let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2", "rotate 1 by two");
+        assert!(Problem::parse_extended(&input)
+            .unwrap_err()
+            .to_string()
+            .contains("line 9, column 13: expected number"));
+    }
+
+    #[test]
+    fn test_execute_9000() {
+        let mut problem = EXAMPLE_INPUT.parse::<Problem>().unwrap();
+        problem.execute(CraneModel::CrateMover9000).unwrap();
+        assert_eq!(
+            problem,
+            Problem {
+                stacks: vec![
+                    Stack {
+                        values: crates(&["C"]),
+                        index: 1
+                    },
+                    Stack {
+                        values: crates(&["M"]),
+                        index: 2
+                    },
+                    Stack {
+                        values: crates(&["P", "D", "N", "Z"]),
+                        index: 3
+                    }
+                ],
+                instructions: [].into(),
+                executed: 4,
+                history: History::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_execute_9001() {
+        let mut problem = EXAMPLE_INPUT.parse::<Problem>().unwrap();
+        problem.execute(CraneModel::CrateMover9001).unwrap();
+        assert_eq!(
+            problem,
+            Problem {
+                stacks: vec![
+                    Stack {
+                        values: crates(&["M"]),
+                        index: 1
+                    },
+                    Stack {
+                        values: crates(&["C"]),
+                        index: 2
+                    },
+                    Stack {
+                        values: crates(&["P", "Z", "N", "D"]),
+                        index: 3
+                    }
+                ],
+                instructions: [].into(),
+                executed: 4,
+                history: History::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_draw_stacks() {
+        let example = parsed_example();
+        // The drawing in the input, without the instructions.
+        let drawing = EXAMPLE_INPUT.split("\n\n").next().unwrap();
+        assert_eq!(draw_stacks(&example.stacks), format!("{}\n", drawing));
+
+        let mut problem = example;
+        problem.execute(CraneModel::CrateMover9000).unwrap();
+        assert_eq!(
+            draw_stacks(&problem.stacks),
+            "        [Z]\n        [N]\n        [D]\n[C] [M] [P]\n 1   2   3 \n"
+        );
+        assert_eq!(draw_stacks(&[]), "\n");
+    }
+
+    #[test]
+    fn test_display_problem() {
+        let problem = rearrange(parsed_example(), CraneModel::CrateMover9001).unwrap();
+        // As drawn at the end of the puzzle's part 2 walkthrough.
+        assert_eq!(
+            problem.to_string(),
+            "        [D]\n        [N]\n        [Z]\n[M] [C] [P]\n 1   2   3 \n"
+        );
+    }
+
+    #[test]
+    fn test_move_methods_agree() {
+        let mut rng = fastrand::Rng::with_seed(6);
+        for _ in 0..100 {
+            let mut problem = parsed_example();
+            let mut sizes: Vec<_> = problem.stacks.iter().map(|s| s.values.len()).collect();
+            // Moves onto the source stack are included.
+            problem.instructions = (0..20)
+                .map(|_| {
+                    let src = rng.usize(0..3);
+                    let dest = rng.usize(0..3);
+                    let amount = rng.usize(0..=sizes[src]);
+                    sizes[src] -= amount;
+                    sizes[dest] += amount;
+                    Instruction::Move { src, dest, amount }
+                })
+                .collect();
+            for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+                let mut in_place = problem.clone();
+                in_place.execute_with(model, MoveMethod::InPlace).unwrap();
+                let mut allocating = problem.clone();
+                allocating
+                    .execute_with(model, MoveMethod::Allocating)
+                    .unwrap();
+                assert_eq!(in_place, allocating, "{:?}", problem.instructions);
+            }
+        }
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut rng = fastrand::Rng::with_seed(7);
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            for _ in 0..50 {
+                let mut problem = parsed_example();
+                let mut sizes: Vec<_> = problem.stacks.iter().map(|s| s.values.len()).collect();
+                problem.instructions = (0..20)
+                    .map(|_| {
+                        let stack = rng.usize(0..3);
+                        let other = rng.usize(0..3);
+                        match rng.u8(0..4) {
+                            0 => {
+                                let amount = rng.usize(0..=sizes[stack]);
+                                sizes[stack] -= amount;
+                                sizes[other] += amount;
+                                Instruction::Move {
+                                    src: stack,
+                                    dest: other,
+                                    amount,
+                                }
+                            }
+                            1 => {
+                                sizes.swap(stack, other);
+                                Instruction::Swap { a: stack, b: other }
+                            }
+                            2 => Instruction::Rotate {
+                                stack,
+                                by: rng.usize(0..10),
+                            },
+                            _ => Instruction::Reverse { stack },
+                        }
+                    })
+                    .collect();
+
+                let mut states = vec![problem.clone()];
+                while !problem.is_finished() {
+                    problem.step(model).unwrap();
+                    states.push(problem.clone());
+                }
+                assert_eq!(problem.undoable(), 20);
+                while let Some(state) = states.pop() {
+                    assert_eq!(problem, state);
+                    assert_eq!(problem.undo().is_ok(), !states.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_history_limit() {
+        let mut problem = parsed_example();
+        problem.step(CraneModel::CrateMover9000).unwrap();
+        problem.step(CraneModel::CrateMover9000).unwrap();
+        let after_one = {
+            let mut problem = parsed_example();
+            problem.step(CraneModel::CrateMover9000).unwrap();
+            problem
+        };
+
+        problem.set_history_limit(1);
+        assert_eq!(problem.undoable(), 1);
+        problem.undo().unwrap();
+        assert_eq!(problem, after_one);
+        assert_eq!(
+            problem.next_instruction(),
+            parsed_example().instructions.get(1)
+        );
+        assert_eq!(problem.undo().unwrap_err().to_string(), "nothing to undo");
+
+        // Undoing can be switched off.
+        problem.set_history_limit(0);
+        problem.execute(CraneModel::CrateMover9000).unwrap();
+        assert_eq!(problem.undoable(), 0);
+        assert!(problem.undo().is_err());
+    }
+
+    #[test]
+    fn test_crane_model_names() {
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            assert_eq!(model.to_string().parse::<CraneModel>().unwrap(), model);
+        }
+        assert!("9002".parse::<CraneModel>().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+// The state of a rearrangement part way through, for saving it and carrying
+// on later or checking it in tests.
+
+use anyhow::{Error, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{Instruction, Problem, Stack};
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Snapshot {
+    // Instructions carried out before the snapshot was taken.
+    #[serde(default)]
+    pub executed: usize,
+    pub stacks: Vec<Stack>,
+    // Instructions still to be carried out.
+    pub instructions: Vec<Instruction>,
+}
+
+impl Problem {
+    // The stacks and remaining instructions as they are now, to save in any
+    // serde format.  The undo history isn't included.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            executed: self.executed,
+            stacks: self.stacks.clone(),
+            instructions: self.instructions.iter().cloned().collect(),
+        }
+    }
+
+    // Carry on from `snapshot`.  Fails, as parsing would, if an instruction
+    // names a stack that doesn't exist.
+    pub fn restore(snapshot: Snapshot) -> Result<Self> {
+        Self::from_parts(
+            snapshot.stacks,
+            snapshot.instructions.into(),
+            snapshot.executed,
+        )
+    }
+}
+
+impl TryFrom<Snapshot> for Problem {
+    type Error = Error;
+
+    fn try_from(snapshot: Snapshot) -> Result<Self> {
+        Self::restore(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate, CraneModel};
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn resume() {
+        let generated = generate(9, 8, 200, 2).unwrap();
+        for model in [CraneModel::CrateMover9000, CraneModel::CrateMover9001] {
+            let mut problem = Problem::parse(&generated.input).unwrap();
+            for _ in 0..120 {
+                problem.step(model).unwrap();
+            }
+            let json = serde_json::to_string(&problem.snapshot()).unwrap();
+
+            let mut resumed = Problem::restore(serde_json::from_str(&json).unwrap()).unwrap();
+            assert_eq!(resumed, problem);
+            assert_eq!(resumed.executed(), 120);
+            assert_eq!(resumed.remaining(), 80);
+            resumed.execute(model).unwrap();
+            assert_eq!(resumed.stacks(), generated.final_stacks(model));
+        }
+    }
+
+    #[test]
+    fn intermediate_state() {
+        let mut problem = Problem::parse(EXAMPLE_INPUT).unwrap();
+        problem.step(CraneModel::CrateMover9000).unwrap();
+        problem.step(CraneModel::CrateMover9000).unwrap();
+        assert_eq!(
+            problem.snapshot(),
+            Snapshot {
+                executed: 2,
+                stacks: vec![
+                    Stack::new(1, [] as [&str; 0]),
+                    Stack::new(2, ["M", "C"]),
+                    Stack::new(3, ["P", "D", "N", "Z"]),
+                ],
+                instructions: vec![
+                    Instruction::Move {
+                        src: 1,
+                        dest: 0,
+                        amount: 2
+                    },
+                    Instruction::Move {
+                        src: 0,
+                        dest: 1,
+                        amount: 1
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_snapshot() {
+        let mut snapshot = Problem::parse(EXAMPLE_INPUT).unwrap().snapshot();
+        snapshot.executed = 10;
+        snapshot.stacks.pop();
+        assert_eq!(
+            Problem::restore(snapshot).unwrap_err().to_string(),
+            "instruction 12: 'move 3 from 1 to 3' references stack 3 but only 2 exist"
+        );
+    }
+}
@@ -0,0 +1,315 @@
+use std::{collections::VecDeque, ops::Range};
+
+use anyhow::{anyhow, Result};
+use aoc_parse::IResult;
+use nom::{
+    bytes::complete::take_till1,
+    character::complete::{char, digit1, line_ending, space0},
+    combinator::{consumed, map_res},
+    error::{context, ErrorKind, VerboseError, VerboseErrorKind},
+    multi::{many1, separated_list1},
+    sequence::pair,
+};
+use serde::{Deserialize, Serialize};
+
+// A crate in a drawing of the stacks, labelled with any text without a
+// closing bracket, e.g. `[A]`, `[AB]` or `[🎁]`.
+#[derive(Debug, Eq, PartialEq)]
+struct DrawnCrate<'a> {
+    // Column, in characters, of the opening bracket.
+    column: usize,
+    label: String,
+    // The crate as written, for reporting errors.
+    text: &'a str,
+}
+
+impl DrawnCrate<'_> {
+    // Columns covered by the crate, including its brackets.
+    fn columns(&self) -> Range<usize> {
+        self.column..self.column + self.text.chars().count()
+    }
+}
+
+fn parse_stack_content(i: &str) -> IResult<'_, String> {
+    let (i, _) = char('[')(i)?;
+    let (i, value) = take_till1(|c| c == ']')(i)?;
+    let (i, _) = char(']')(i)?;
+
+    Ok((i, value.to_owned()))
+}
+
+// Parse one level of crates, each placed by its column since labels may be
+// of any width.
+fn parse_stack_level(i: &str) -> IResult<'_, Vec<DrawnCrate<'_>>> {
+    let (i, crates) = context(
+        "stack level",
+        many1(pair(space0, consumed(parse_stack_content))),
+    )(i)?;
+    let (i, _) = space0(i)?;
+
+    let mut column = 0;
+    let crates = crates
+        .into_iter()
+        .map(|(spaces, (text, label))| {
+            column += spaces.len();
+            let drawn = DrawnCrate {
+                column,
+                label,
+                text,
+            };
+            column += text.chars().count();
+            drawn
+        })
+        .collect();
+    Ok((i, crates))
+}
+
+// Parse the row of stack indices below the crates, along with the column
+// each starts in.
+fn parse_stack_indices(i: &str) -> IResult<'_, Vec<(usize, u32)>> {
+    let (i, indices) = context(
+        "stack indices",
+        many1(pair(
+            space0,
+            map_res(digit1, |value: &str| value.parse::<u32>()),
+        )),
+    )(i)?;
+    let (i, _) = space0(i)?;
+
+    let mut column = 0;
+    let indices = indices
+        .into_iter()
+        .map(|(spaces, index)| {
+            column += spaces.len();
+            let placed = (column, index);
+            column += index.to_string().len();
+            placed
+        })
+        .collect();
+    Ok((i, indices))
+}
+
+// A stack of crates, labelled with the index written below it.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Stack {
+    // Crate labels, bottom first.
+    pub(crate) values: VecDeque<String>,
+    pub(crate) index: u32,
+}
+
+impl Stack {
+    // A stack labelled `index` holding `crates`, bottom first.
+    pub fn new(index: u32, crates: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            values: crates.into_iter().map(Into::into).collect(),
+            index,
+        }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    // Crate labels, bottom first.
+    pub fn crates(&self) -> &VecDeque<String> {
+        &self.values
+    }
+
+    // The label of the crate on top, if any.
+    pub fn top(&self) -> Option<&str> {
+        self.values.back().map(String::as_str)
+    }
+
+    pub(crate) fn pop(&mut self) -> Result<String> {
+        self.values.pop_back().ok_or_else(|| anyhow!("stack empty"))
+    }
+
+    pub(crate) fn push(&mut self, val: String) {
+        self.values.push_back(val)
+    }
+
+    pub(crate) fn take(&mut self, num_elements: usize) -> Result<VecDeque<String>> {
+        if num_elements > self.values.len() {
+            return Err(anyhow!(
+                "Can't pop {num_elements} from stack of length {}",
+                self.values.len()
+            ));
+        }
+        Ok(self.values.split_off(self.values.len() - num_elements))
+    }
+
+    pub(crate) fn peek(&self) -> Result<String> {
+        self.values
+            .back()
+            .cloned()
+            .ok_or_else(|| anyhow!("stack empty"))
+    }
+}
+
+// Parse a drawing of the stacks.  Each crate belongs to the stack whose
+// index is written below it.
+pub(crate) fn parse_stacks(input: &str) -> IResult<'_, Vec<Stack>> {
+    let (input, levels) = separated_list1(line_ending, parse_stack_level)(input)?;
+    let (input, _) = line_ending(input)?;
+    let (input, indices) = parse_stack_indices(input)?;
+    let (input, _) = line_ending(input)?;
+
+    let mut stacks: Vec<_> = indices
+        .iter()
+        .map(|&(_, index)| Stack {
+            values: VecDeque::new(),
+            index,
+        })
+        .collect();
+    for drawn in levels.into_iter().rev().flatten() {
+        // Indices are in column order, so the first one not left of the
+        // crate is the only one that can be below it.
+        let columns = drawn.columns();
+        let stack = indices.partition_point(|&(column, _)| column < columns.start);
+        let Some(stack) = indices
+            .get(stack)
+            .filter(|(column, _)| columns.contains(column))
+            .map(|_| stack)
+        else {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![
+                    (drawn.text, VerboseErrorKind::Nom(ErrorKind::Verify)),
+                    (
+                        drawn.text,
+                        VerboseErrorKind::Context("crate above a stack index"),
+                    ),
+                ],
+            }));
+        };
+        stacks[stack].push(drawn.label);
+    }
+
+    Ok((input, stacks))
+}
+
+// Draw `stacks` the way the input does, crates in brackets above a row of
+// stack indices.  Each stack is as wide as its widest label or index.
+pub fn draw_stacks(stacks: &[Stack]) -> String {
+    let widths: Vec<_> = stacks
+        .iter()
+        .map(|stack| {
+            stack
+                .values
+                .iter()
+                .map(|value| value.chars().count())
+                .chain([stack.index.to_string().len()])
+                .max()
+                .unwrap_or(1)
+                + 2
+        })
+        .collect();
+    let height = stacks.iter().map(|stack| stack.values.len()).max();
+    let mut lines: Vec<String> = (0..height.unwrap_or(0))
+        .rev()
+        .map(|level| {
+            let crates: Vec<_> = stacks
+                .iter()
+                .zip(&widths)
+                .map(|(stack, &width)| {
+                    let value = stack.values.get(level).map(|value| format!("[{}]", value));
+                    format!("{:width$}", value.unwrap_or_default())
+                })
+                .collect();
+            crates.join(" ")
+        })
+        .collect();
+    let indices: Vec<_> = stacks
+        .iter()
+        .zip(&widths)
+        .map(|(stack, &width)| format!(" {:<width$}", stack.index, width = width - 1))
+        .collect();
+    lines.push(indices.join(" "));
+
+    let mut drawing = lines.join("\n");
+    drawing.push('\n');
+    drawing
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crates(labels: &[&str]) -> VecDeque<String> {
+        labels.iter().map(|&label| label.to_owned()).collect()
+    }
+
+    // The column and label of each crate in a level.
+    fn level(input: &str) -> Vec<(usize, String)> {
+        let (remaining, crates) = parse_stack_level(input).unwrap();
+        assert_eq!(remaining, "");
+        crates
+            .into_iter()
+            .map(|drawn| (drawn.column, drawn.label))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_stack_level() {
+        assert_eq!(level("    [D]    "), vec![(4, "D".to_owned())]);
+        assert_eq!(
+            level("[N] [C]    "),
+            vec![(0, "N".to_owned()), (4, "C".to_owned())]
+        );
+        assert_eq!(
+            level("[Z] [M] [P]"),
+            vec![
+                (0, "Z".to_owned()),
+                (4, "M".to_owned()),
+                (8, "P".to_owned())
+            ]
+        );
+        assert_eq!(
+            level("     [🎁] [AB]"),
+            vec![(5, "🎁".to_owned()), (9, "AB".to_owned())]
+        );
+        assert!(parse_stack_level("    ").is_err());
+    }
+
+    #[test]
+    fn test_parse_stacks() {
+        assert_eq!(
+            parse_stacks("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n").unwrap(),
+            (
+                "",
+                vec![
+                    Stack {
+                        values: crates(&["Z", "N"]),
+                        index: 1,
+                    },
+                    Stack {
+                        values: crates(&["M", "C", "D"]),
+                        index: 2,
+                    },
+                    Stack {
+                        values: crates(&["P"]),
+                        index: 3,
+                    },
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_stack_indices() {
+        assert_eq!(
+            parse_stack_indices(" 1   2   3 ").unwrap(),
+            ("", vec![(1, 1), (5, 2), (9, 3)])
+        );
+    }
+
+    #[test]
+    fn test_stack_take() {
+        let mut stack = Stack {
+            values: crates(&["A", "B", "C", "D"]),
+            index: 1,
+        };
+
+        assert_eq!(stack.take(2).unwrap(), ["C", "D"]);
+        assert_eq!(stack.values, ["A", "B"]);
+    }
+}
@@ -0,0 +1,140 @@
+// A record of the stacks after every instruction, for visualizing a
+// rearrangement elsewhere or comparing the crane models.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{CraneModel, Instruction, Problem, Stack};
+
+// The result of carrying out one instruction.  Stacks are numbered from 1,
+// as in the input.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TraceStep {
+    // Number of the instruction, counting from 1.
+    pub number: usize,
+    // The instruction as written in the input.
+    pub instruction: String,
+    // Stacks the instruction touched.
+    pub affected: Vec<usize>,
+    // Crates the instruction moved, bottom first as they ended up.  For a
+    // swap that is the crates of both stacks and for a rotation the crates
+    // taken to the bottom.
+    pub moved: Vec<String>,
+    // Every stack after the instruction.
+    pub stacks: Vec<Stack>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Trace {
+    pub model: CraneModel,
+    // The stacks before the first instruction.
+    pub initial: Vec<Stack>,
+    pub steps: Vec<TraceStep>,
+}
+
+// Carry out the instructions remaining in `problem` with a crane of type
+// `model`, recording the stacks after each.
+pub fn trace(mut problem: Problem, model: CraneModel) -> Result<Trace> {
+    let mut trace = Trace {
+        model,
+        initial: problem.stacks.clone(),
+        steps: Vec::new(),
+    };
+    while let Some(instruction) = problem.instructions.front().cloned() {
+        problem.step(model)?;
+
+        let stacks = &problem.stacks;
+        let moved = match instruction {
+            Instruction::Move { dest, amount, .. } => {
+                let values = &stacks[dest].values;
+                values.range(values.len() - amount..).cloned().collect()
+            }
+            Instruction::Swap { a, b } => {
+                let mut moved: Vec<_> = stacks[a].values.iter().cloned().collect();
+                if a != b {
+                    moved.extend(stacks[b].values.iter().cloned());
+                }
+                moved
+            }
+            Instruction::Rotate { stack, by } => {
+                let values = &stacks[stack].values;
+                values
+                    .iter()
+                    .take(by.checked_rem(values.len()).unwrap_or(0))
+                    .cloned()
+                    .collect()
+            }
+            Instruction::Reverse { stack } => stacks[stack].values.iter().cloned().collect(),
+        };
+        trace.steps.push(TraceStep {
+            number: problem.executed,
+            instruction: instruction.to_string(),
+            affected: instruction.stacks().map(|stack| stack + 1).collect(),
+            moved,
+            stacks: stacks.clone(),
+        });
+    }
+    Ok(trace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    fn crates(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|&label| label.to_owned()).collect()
+    }
+
+    #[test]
+    fn example_trace() {
+        let problem: Problem = EXAMPLE_INPUT.parse().unwrap();
+        let trace_9000 = trace(problem.clone(), CraneModel::CrateMover9000).unwrap();
+        assert_eq!(trace_9000.initial, problem.stacks);
+        assert_eq!(trace_9000.steps.len(), 4);
+        let step = &trace_9000.steps[1];
+        assert_eq!(step.number, 2);
+        assert_eq!(step.instruction, "move 3 from 1 to 3");
+        assert_eq!(step.affected, [1, 3]);
+        assert_eq!(step.moved, crates(&["D", "N", "Z"]));
+        assert_eq!(
+            Vec::from(step.stacks[2].values.clone()),
+            crates(&["P", "D", "N", "Z"])
+        );
+
+        // The CrateMover 9001 keeps the crates in order.
+        let trace_9001 = trace(problem, CraneModel::CrateMover9001).unwrap();
+        assert_eq!(trace_9001.steps[1].moved, crates(&["Z", "N", "D"]));
+        assert_eq!(trace_9001.steps[0], trace_9000.steps[0]);
+    }
+
+    #[test]
+    fn extended_trace() {
+        let input = "[A] [C]\n[B] [D]\n 1   2 \n\nswap 1 2\nrotate 1 by 3\nreverse 2\nswap 2 2\n";
+        let trace = trace(
+            Problem::parse_extended(input).unwrap(),
+            CraneModel::CrateMover9000,
+        )
+        .unwrap();
+        let moved: Vec<_> = trace.steps.iter().map(|step| step.moved.clone()).collect();
+        assert_eq!(
+            moved,
+            [
+                crates(&["D", "C", "B", "A"]),
+                crates(&["C"]),
+                crates(&["A", "B"]),
+                crates(&["A", "B"]),
+            ]
+        );
+        assert_eq!(trace.steps[3].affected, [2]);
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let trace = trace(EXAMPLE_INPUT.parse().unwrap(), CraneModel::CrateMover9001).unwrap();
+        let json = serde_json::to_string(&trace).unwrap();
+        assert!(json.starts_with(r#"{"model":"9001","#), "{}", json);
+        assert_eq!(serde_json::from_str::<Trace>(&json).unwrap(), trace);
+    }
+}
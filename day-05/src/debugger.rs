@@ -0,0 +1,252 @@
+// An interactive mode running the rearrangement one instruction at a time,
+// reading commands a line at a time.
+
+use std::io::{BufRead, Write};
+
+use anyhow::{anyhow, bail, Result};
+
+use day_05_lib::{draw_stacks, CraneModel, Problem};
+
+const HELP: &str = "\
+commands:
+  (empty), s, step       run the next instruction
+  u, undo                undo the last instruction run
+  c, continue            run until a breakpoint or the end
+  b, break N             stop before instruction N
+  b, break empty N       stop when stack N becomes empty
+  p, print               show the stacks and the next instruction
+  h, help                show this help
+  q, quit                stop debugging
+";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Breakpoint {
+    // Stop before running the instruction with this 1 based number.
+    Instruction(usize),
+    // Stop once the stack at this 1 based position becomes empty.
+    EmptyStack(usize),
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Command {
+    Step,
+    Undo,
+    Continue,
+    Break(Breakpoint),
+    Print,
+    Help,
+    Quit,
+}
+
+fn parse_command(line: &str) -> Result<Command> {
+    let words: Vec<_> = line.split_whitespace().collect();
+    let number = |word: &str| {
+        word.parse::<usize>()
+            .ok()
+            .filter(|&n| n > 0)
+            .ok_or_else(|| anyhow!("'{}' is not a positive number", word))
+    };
+    Ok(match words.as_slice() {
+        [] | ["s" | "step"] => Command::Step,
+        ["u" | "undo"] => Command::Undo,
+        ["c" | "continue"] => Command::Continue,
+        ["b" | "break", n] => Command::Break(Breakpoint::Instruction(number(n)?)),
+        ["b" | "break", "empty", n] => Command::Break(Breakpoint::EmptyStack(number(n)?)),
+        ["p" | "print"] => Command::Print,
+        ["h" | "help"] => Command::Help,
+        ["q" | "quit"] => Command::Quit,
+        _ => bail!("unknown command '{}', try 'help'", line.trim()),
+    })
+}
+
+pub struct Debugger {
+    problem: Problem,
+    model: CraneModel,
+    breakpoints: Vec<Breakpoint>,
+}
+
+impl Debugger {
+    pub fn new(problem: Problem, model: CraneModel) -> Self {
+        Self {
+            problem,
+            model,
+            breakpoints: Vec::new(),
+        }
+    }
+
+    fn print(&self, out: &mut impl Write) -> Result<()> {
+        write!(out, "{}", draw_stacks(self.problem.stacks()))?;
+        match self.problem.next_instruction() {
+            Some(instruction) => writeln!(
+                out,
+                "next: {}: {}",
+                self.problem.executed() + 1,
+                instruction
+            )?,
+            None => writeln!(out, "no instructions left")?,
+        }
+        Ok(())
+    }
+
+    fn is_empty(&self, stack: usize) -> bool {
+        self.problem
+            .stacks()
+            .get(stack - 1)
+            .is_some_and(|stack| stack.crates().is_empty())
+    }
+
+    // Run the next instruction, returning the breakpoint it triggered, if
+    // any.
+    fn step(&mut self) -> Result<Option<Breakpoint>> {
+        let was_empty: Vec<_> = self
+            .breakpoints
+            .iter()
+            .map(|&breakpoint| match breakpoint {
+                Breakpoint::EmptyStack(stack) => self.is_empty(stack),
+                Breakpoint::Instruction(_) => false,
+            })
+            .collect();
+        self.problem.step(self.model)?;
+
+        let next = self.problem.executed() + 1;
+        Ok(self
+            .breakpoints
+            .iter()
+            .zip(was_empty)
+            .find(|&(&breakpoint, was_empty)| match breakpoint {
+                Breakpoint::Instruction(n) => n == next,
+                Breakpoint::EmptyStack(stack) => !was_empty && self.is_empty(stack),
+            })
+            .map(|(&breakpoint, _)| breakpoint))
+    }
+
+    // Read and carry out commands from `input` until the instructions run
+    // out or the user quits, writing to `out`.  Returns the crates on top
+    // of the stacks if every instruction was run.
+    pub fn run(&mut self, input: impl BufRead, mut out: impl Write) -> Result<Option<String>> {
+        self.print(&mut out)?;
+        for line in input.lines() {
+            let command = match parse_command(&line?) {
+                Ok(command) => command,
+                Err(e) => {
+                    writeln!(out, "{}", e)?;
+                    continue;
+                }
+            };
+            match command {
+                Command::Step => {
+                    if let Some(breakpoint) = self.step()? {
+                        writeln!(out, "hit {:?}", breakpoint)?;
+                    }
+                    self.print(&mut out)?;
+                }
+                Command::Undo => {
+                    if let Err(e) = self.problem.undo() {
+                        writeln!(out, "{}", e)?;
+                    }
+                    self.print(&mut out)?;
+                }
+                Command::Continue => {
+                    while !self.problem.is_finished() {
+                        if let Some(breakpoint) = self.step()? {
+                            writeln!(out, "hit {:?}", breakpoint)?;
+                            break;
+                        }
+                    }
+                    self.print(&mut out)?;
+                }
+                Command::Break(breakpoint) => {
+                    self.breakpoints.push(breakpoint);
+                    writeln!(out, "added {:?}", breakpoint)?;
+                }
+                Command::Print => self.print(&mut out)?,
+                Command::Help => write!(out, "{}", HELP)?,
+                Command::Quit => return Ok(None),
+            }
+            if self.problem.is_finished() {
+                let top = self.problem.tops()?;
+                writeln!(
+                    out,
+                    "finished after {} instructions, top of stacks: {}",
+                    self.problem.executed(),
+                    top
+                )?;
+                return Ok(Some(top));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EXAMPLE_INPUT;
+
+    // Run a session with `commands`, returning the answer and the output.
+    fn session(commands: &str, model: CraneModel) -> (Option<String>, String) {
+        let mut debugger = Debugger::new(EXAMPLE_INPUT.parse().unwrap(), model);
+        let mut out = Vec::new();
+        let top = debugger.run(commands.as_bytes(), &mut out).unwrap();
+        (top, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse_command("").unwrap(), Command::Step);
+        assert_eq!(
+            parse_command("break 57").unwrap(),
+            Command::Break(Breakpoint::Instruction(57))
+        );
+        assert_eq!(
+            parse_command(" b  empty 3 ").unwrap(),
+            Command::Break(Breakpoint::EmptyStack(3))
+        );
+        assert!(parse_command("break 0").is_err());
+        assert!(parse_command("jump").is_err());
+    }
+
+    #[test]
+    fn stepping() {
+        let (top, out) = session("\n\n\n\n", CraneModel::CrateMover9000);
+        assert_eq!(top.as_deref(), Some("CMZ"));
+        assert!(out.starts_with(
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \nnext: 1: move 1 from 2 to 1\n"
+        ));
+        assert!(out.ends_with("finished after 4 instructions, top of stacks: CMZ\n"));
+    }
+
+    #[test]
+    fn breakpoints() {
+        let (top, out) = session("b 3\nc\nq\n", CraneModel::CrateMover9001);
+        assert_eq!(top, None);
+        assert!(out.contains("hit Instruction(3)\n"));
+        assert!(out.ends_with("next: 3: move 2 from 2 to 1\n"), "{out}");
+
+        // Stack 1 is emptied by the second instruction.
+        let (top, out) = session("b empty 1\nc\nc\n", CraneModel::CrateMover9000);
+        assert_eq!(top.as_deref(), Some("CMZ"));
+        assert!(out.contains("hit EmptyStack(1)\n"), "{out}");
+        assert!(out.contains("next: 3: "));
+    }
+
+    #[test]
+    fn undo() {
+        let (top, out) = session("u\n\n\nundo\nu\nu\nc\n", CraneModel::CrateMover9001);
+        assert_eq!(top.as_deref(), Some("MCD"));
+        let start =
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \nnext: 1: move 1 from 2 to 1\n";
+        let nothing = format!("nothing to undo\n{start}");
+        assert!(out.starts_with(&format!("{start}{nothing}")), "{out}");
+        // Back at the start after undoing both instructions.
+        assert_eq!(out.matches(&nothing).count(), 2, "{out}");
+        assert!(out.contains("next: 2: move 3 from 1 to 3\n"));
+    }
+
+    #[test]
+    fn unknown_command() {
+        let (top, out) = session("jump\nq\n", CraneModel::CrateMover9000);
+        assert_eq!(top, None);
+        assert!(out.contains("unknown command 'jump', try 'help'\n"));
+    }
+}
@@ -1,440 +1,270 @@
-use std::{collections::VecDeque, fs, path::PathBuf, str::FromStr};
-
-use anyhow::{anyhow, Error, Result};
-use clap::Parser;
-use env_logger::Env;
-use log::{debug, info};
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take},
-    character::complete::{char, line_ending, one_of},
-    combinator::{map_res, recognize},
-    multi::{many0, many1, separated_list1},
-    sequence::terminated,
-    Finish, IResult,
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+    path::PathBuf,
+    time::Duration,
 };
 
-#[derive(Debug, Eq, PartialEq)]
-struct Instruction {
-    src: usize,
-    dest: usize,
-    amount: usize,
-}
+use anyhow::{Context, Result};
+use aoc_core::CommonArgs;
+use aoc_viz::Animation;
+use clap::{Args as ClapArgs, Parser, Subcommand};
+use day_05_lib::{
+    draw_stacks, generate, part1, part2, rearrange, solution_optimized, trace, CraneModel, Problem,
+};
+use log::info;
 
-impl Instruction {
-    fn parse(i: &str) -> IResult<&str, Self> {
-        let (i, _) = tag("move ")(i)?;
-        let (i, amount) = decimal_value(i)?;
-        let (i, _) = tag(" from ")(i)?;
-        let (i, src) = decimal_value(i)?;
-        let (i, _) = tag(" to ")(i)?;
-        let (i, dest) = decimal_value(i)?;
-
-        // Convert from 1 based indexing to 0 based.
-        Ok((
-            i,
-            Self {
-                src: src - 1,
-                dest: dest - 1,
-                amount,
-            },
-        ))
-    }
-}
+mod debugger;
 
-// Adapted from https://github.com/Geal/nom/blob/main/doc/nom_recipes.md#integers
-fn decimal_value(input: &str) -> IResult<&str, usize> {
-    map_res(
-        recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
-        |value: &str| value.parse::<usize>(),
-    )(input)
-}
+use debugger::Debugger;
 
-fn parse_empty_stack(i: &str) -> IResult<&str, Option<char>> {
-    let (i, _) = tag("   ")(i)?;
-    Ok((i, None))
+// Play the rearrangement by a crane of type `model` as an animation, one
+// frame per instruction.
+fn animate(mut problem: Problem, model: CraneModel, delay: Duration) -> Result<String> {
+    let mut animation = Animation::new(io::stdout().lock(), delay);
+    animation.frame(&draw_stacks(problem.stacks()))?;
+    while let Some(instruction) = problem.next_instruction() {
+        let caption = instruction.to_string();
+        problem.step(model)?;
+        animation.frame(&format!("{}\n{}\n", draw_stacks(problem.stacks()), caption))?;
+    }
+    problem.tops()
 }
 
-fn parse_stack_content(i: &str) -> IResult<&str, Option<char>> {
-    let (i, _) = char('[')(i)?;
-    let (i, value) = take(1usize)(i)?;
-    let (i, _) = char(']')(i)?;
+const EXAMPLE_INPUT: &str = include_str!("../../day-05-lib/src/example-input.txt");
 
-    Ok((i, value.chars().next()))
+// Command line arguments.
+#[derive(Debug, Parser)]
+#[command(subcommand_negates_reqs = true)]
+struct Args {
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// Rearrange the stacks with only this crane, 9000 or 9001, instead of
+    /// solving each part
+    #[arg(long)]
+    crane: Option<CraneModel>,
+
+    /// Accept the swap, rotate and reverse instructions as well as moves
+    #[arg(long)]
+    extended: bool,
+
+    /// Merge and cancel instructions before running them, checking the
+    /// result against running the original ones
+    #[arg(long)]
+    optimize: bool,
+
+    /// Run instructions touching separate stacks on several threads
+    #[cfg(feature = "parallel")]
+    #[arg(long, conflicts_with = "optimize")]
+    parallel: bool,
+
+    /// Draw the stacks after rearranging them
+    #[arg(long)]
+    show_final: bool,
+
+    /// Run one instruction at a time, with breakpoints, reading commands
+    /// from stdin
+    #[arg(long, conflicts_with = "animate")]
+    step: bool,
+
+    /// Draw the stacks after every instruction, using --crane or else the
+    /// CrateMover 9000
+    #[arg(long)]
+    animate: bool,
+
+    /// Write the stacks after every instruction to a JSON file, using
+    /// --crane or else the CrateMover 9000
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["step", "animate"])]
+    trace: Option<PathBuf>,
+
+    /// Write the state after --snapshot-at instructions to a JSON file,
+    /// using --crane or else the CrateMover 9000, to carry on with --resume
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "snapshot_at",
+        conflicts_with_all = ["step", "animate", "trace"]
+    )]
+    snapshot: Option<PathBuf>,
+
+    /// Number of instructions to run before writing --snapshot
+    #[arg(long, value_name = "N", requires = "snapshot")]
+    snapshot_at: Option<usize>,
+
+    /// Read INPUT as a state written by --snapshot and carry on from it
+    #[arg(long)]
+    resume: bool,
+
+    /// Milliseconds to show each frame of --animate for
+    #[arg(long, requires = "animate", default_value_t = 200)]
+    frame_delay: u64,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn parse_stack_position(i: &str) -> IResult<&str, Option<char>> {
-    alt((parse_empty_stack, parse_stack_content))(i)
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print a random input, and its answers to stderr
+    Gen(GenArgs),
 }
 
-fn parse_stack_level(i: &str) -> IResult<&str, Vec<Option<char>>> {
-    separated_list1(char(' '), parse_stack_position)(i)
-}
+#[derive(Debug, ClapArgs)]
+struct GenArgs {
+    /// Number of stacks
+    #[arg(long, default_value_t = 9)]
+    stacks: usize,
 
-fn parse_stack_index(i: &str) -> IResult<&str, u32> {
-    let (i, _) = char(' ')(i)?;
-    let (i, value) = map_res(take(1usize), |value: &str| value.parse::<u32>())(i)?;
-    let (i, _) = char(' ')(i)?;
+    /// Most crates a stack starts with
+    #[arg(long, default_value_t = 8)]
+    height: usize,
 
-    Ok((i, value))
-}
+    /// Number of moves
+    #[arg(long, default_value_t = 500)]
+    moves: usize,
 
-fn parse_stack_indices(i: &str) -> IResult<&str, Vec<u32>> {
-    separated_list1(char(' '), parse_stack_index)(i)
+    /// Seed for the stacks and moves
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
 }
 
-#[derive(Debug, Eq, PartialEq)]
-struct Stack {
-    values: VecDeque<char>,
-    index: u32,
+// Print a generated input along with the answers it should give.
+fn gen(args: &GenArgs) -> Result<()> {
+    let generated = generate(args.stacks, args.height, args.moves, args.seed)?;
+    print!("{}", generated.input);
+    eprintln!(
+        "[Part: 1] Top of stacks: {}",
+        generated.tops(CraneModel::CrateMover9000)
+    );
+    eprintln!(
+        "[Part: 2] Top of stacks: {}",
+        generated.tops(CraneModel::CrateMover9001)
+    );
+    Ok(())
 }
 
-impl Stack {
-    fn pop(&mut self) -> Result<char> {
-        self.values.pop_back().ok_or_else(|| anyhow!("stack empty"))
-    }
+fn main() -> Result<()> {
+    let args = Args::parse();
+    args.common.init_logging();
 
-    fn push(&mut self, val: char) {
-        self.values.push_back(val)
+    if let Some(Command::Gen(gen_args)) = &args.command {
+        return gen(gen_args);
     }
 
-    fn take(&mut self, num_elements: usize) -> Result<VecDeque<char>> {
-        if num_elements > self.values.len() {
-            return Err(anyhow!(
-                "Can't pop {num_elements} from stack of length {}",
-                self.values.len()
-            ));
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
+
+    let problem = || {
+        if args.resume {
+            Problem::restore(serde_json::from_str(&input).context("Failed to read snapshot")?)
+        } else if args.extended {
+            Problem::parse_extended(&input)
+        } else {
+            Problem::parse(&input)
         }
-        Ok(self.values.split_off(self.values.len() - num_elements))
-    }
+    };
 
-    fn peek(&self) -> Result<char> {
-        self.values
-            .back()
-            .copied()
-            .ok_or_else(|| anyhow!("stack empty"))
+    if args.step {
+        let model = args.crane.unwrap_or(CraneModel::CrateMover9000);
+        let mut debugger = Debugger::new(problem()?, model);
+        debugger.run(io::stdin().lock(), io::stdout().lock())?;
+        return Ok(());
     }
-}
-
-fn parse_stacks(input: &str) -> IResult<&str, Vec<Stack>> {
-    let (input, levels) = separated_list1(line_ending, parse_stack_level)(input)?;
-    let (input, _) = line_ending(input)?;
-    let (input, indices) = parse_stack_indices(input)?;
-    let (input, _) = line_ending(input)?;
 
-    for level in &levels {
-        assert_eq!(level.len(), indices.len())
+    if args.animate {
+        let model = args.crane.unwrap_or(CraneModel::CrateMover9000);
+        let top = animate(problem()?, model, Duration::from_millis(args.frame_delay))?;
+        println!("\nTop of stacks: {}", top);
+        return Ok(());
     }
 
-    let stacks: Vec<_> = indices
-        .into_iter()
-        .enumerate()
-        .map(|(i, index)| {
-            let values: VecDeque<_> = levels.iter().filter_map(|val| val[i]).fold(
-                VecDeque::new(),
-                |mut values, value| {
-                    values.push_front(value);
-                    values
-                },
-            );
-            Stack { values, index }
-        })
-        .collect();
-
-    Ok((input, stacks))
-}
-
-#[derive(Debug, Eq, PartialEq)]
-struct Problem {
-    stacks: Vec<Stack>,
-    instructions: VecDeque<Instruction>,
-}
-
-impl Problem {
-    fn parse(i: &str) -> IResult<&str, Self> {
-        let (i, stacks) = parse_stacks(i)?;
-        let (i, _) = line_ending(i)?;
-        let (i, instructions) = separated_list1(line_ending, Instruction::parse)(i)?;
-        let (i, _) = line_ending(i)?;
-
-        Ok((
-            i,
-            Self {
-                stacks,
-                instructions: instructions.into(),
-            },
-        ))
+    if let Some(path) = &args.trace {
+        let model = args.crane.unwrap_or(CraneModel::CrateMover9000);
+        let trace = trace(problem()?, model)?;
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), &trace)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        info!(
+            "Traced {} instructions to {}",
+            trace.steps.len(),
+            path.display()
+        );
+        return Ok(());
     }
 
-    fn step(&mut self) -> Result<()> {
-        let instruction = &self
-            .instructions
-            .pop_front()
-            .ok_or_else(|| anyhow!("step called with empty instructions"))?;
-        for _ in 0..instruction.amount {
-            let val = self.stacks[instruction.src].pop()?;
-            self.stacks[instruction.dest].push(val);
+    if let (Some(path), Some(at)) = (&args.snapshot, args.snapshot_at) {
+        let model = args.crane.unwrap_or(CraneModel::CrateMover9000);
+        let mut problem = problem()?;
+        while problem.executed() < at && !problem.is_finished() {
+            problem.step(model)?;
         }
-
-        Ok(())
+        let file =
+            File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        serde_json::to_writer(BufWriter::new(file), &problem.snapshot())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        info!(
+            "Wrote the state after {} instructions to {}",
+            problem.executed(),
+            path.display()
+        );
+        return Ok(());
     }
 
-    fn execute(&mut self) -> Result<()> {
-        while !self.instructions.is_empty() {
-            self.step()?;
+    let show_final = |model| -> Result<()> {
+        if args.show_final {
+            print!("{}", rearrange(problem()?, model)?);
         }
-
         Ok(())
-    }
+    };
 
-    fn step2(&mut self) -> Result<()> {
-        let instruction = &self
-            .instructions
-            .pop_front()
-            .ok_or_else(|| anyhow!("step called with empty instructions"))?;
-        debug!(
-            "move {} from {} to {}",
-            instruction.amount,
-            // Convert back to 1 based indexing for printing.
-            instruction.src + 1,
-            instruction.dest + 1
-        );
-        let values = self.stacks[instruction.src].take(instruction.amount)?;
-        for val in values {
-            self.stacks[instruction.dest].push(val);
+    let solve = |model| {
+        #[cfg(feature = "parallel")]
+        if args.parallel {
+            return day_05_lib::solution_parallel(problem()?, model);
         }
-        for stack in &self.stacks {
-            debug!("  {}: {:?}", stack.index, stack.values);
+        if args.optimize {
+            solution_optimized(problem()?, model)
+        } else {
+            rearrange(problem()?, model)?.tops()
         }
+    };
 
-        Ok(())
+    if let Some(model) = args.crane {
+        let top = solve(model)?;
+        info!("Top of stacks: {}", top);
+        return show_final(model);
     }
 
-    fn execute2(&mut self) -> Result<()> {
-        for stack in &self.stacks {
-            debug!("  {}: {:?}", stack.index, stack.values);
-        }
+    // Whether the parts can be solved straight from the input.
+    let plain = !(args.optimize || args.extended || args.resume);
+    #[cfg(feature = "parallel")]
+    let plain = plain && !args.parallel;
 
-        while !self.instructions.is_empty() {
-            self.step2()?;
+    let solve_1 = || {
+        if plain {
+            part1(&input)
+        } else {
+            solve(CraneModel::CrateMover9000)
         }
-
-        Ok(())
-    }
-}
-
-impl FromStr for Problem {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::parse(s)
-            .finish()
-            .map_err(|e| anyhow!("Error parsing pair: {}", e))
-            .map(|val| val.1)
-    }
-}
-
-fn solution_part1(input: &str) -> Result<String> {
-    let mut problem = input.parse::<Problem>()?;
-    problem.execute()?;
-    problem.stacks.iter().map(|stack| stack.peek()).collect()
-}
-
-fn solution_part2(input: &str) -> Result<String> {
-    let mut problem = input.parse::<Problem>()?;
-    problem.execute2()?;
-    problem.stacks.iter().map(|stack| stack.peek()).collect()
-}
-
-// Command line arguments.
-#[derive(Debug, Parser)]
-struct Args {
-    input: PathBuf,
-}
-
-fn main() -> Result<()> {
-    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
-
-    let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
-
-    let top = solution_part1(&input)?;
-    info!("[Part: 1] Top of stacks: {}", top);
-
-    let top = solution_part2(&input)?;
-    info!("[Part: 2] Top of stacks: {}", top);
-
-    Ok(())
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
-
-    fn parsed_example() -> Problem {
-        Problem {
-            stacks: vec![
-                Stack {
-                    values: ['Z', 'N'].into(),
-                    index: 1,
-                },
-                Stack {
-                    values: ['M', 'C', 'D'].into(),
-                    index: 2,
-                },
-                Stack {
-                    values: ['P'].into(),
-                    index: 3,
-                },
-            ],
-            instructions: [
-                Instruction {
-                    src: 1,
-                    dest: 0,
-                    amount: 1,
-                },
-                Instruction {
-                    src: 0,
-                    dest: 2,
-                    amount: 3,
-                },
-                Instruction {
-                    src: 1,
-                    dest: 0,
-                    amount: 2,
-                },
-                Instruction {
-                    src: 0,
-                    dest: 1,
-                    amount: 1,
-                },
-            ]
-            .into(),
+    };
+    let solve_2 = || {
+        if plain {
+            part2(&input)
+        } else {
+            solve(CraneModel::CrateMover9001)
         }
-    }
+    };
 
-    #[test]
-    fn test_parse_stack_level() {
-        assert_eq!(
-            parse_stack_level("    [D]    ").unwrap(),
-            ("", vec![None, Some('D'), None])
-        );
-        assert_eq!(
-            parse_stack_level("[N] [C]    ").unwrap(),
-            ("", vec![Some('N'), Some('C'), None])
-        );
-        assert_eq!(
-            parse_stack_level("[Z] [M] [P]").unwrap(),
-            ("", vec![Some('Z'), Some('M'), Some('P')])
-        );
-    }
-
-    #[test]
-    fn test_parse_stacks() {
-        assert_eq!(
-            parse_stacks("    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n").unwrap(),
-            ("", parsed_example().stacks)
-        );
-    }
-
-    #[test]
-    fn test_parse_problem() {
-        assert_eq!(EXAMPLE_INPUT.parse::<Problem>().unwrap(), parsed_example());
-    }
-
-    #[test]
-    fn test_parse_stack_indices() {
-        assert_eq!(
-            parse_stack_indices(" 1   2   3 ").unwrap(),
-            ("", vec![1, 2, 3])
-        );
-    }
-
-    #[test]
-    fn test_parse_instruction() {
-        assert_eq!(
-            Instruction::parse("move 1 from 2 to 3").unwrap(),
-            (
-                "",
-                Instruction {
-                    src: 1,
-                    dest: 2,
-                    amount: 1
-                }
-            )
-        );
-    }
-    #[test]
-    fn test_stack_take() {
-        let mut stack = Stack {
-            values: ['A', 'B', 'C', 'D'].into(),
-            index: 1,
-        };
-
-        assert_eq!(stack.take(2).unwrap(), ['C', 'D']);
-        assert_eq!(stack.values, ['A', 'B']);
-    }
-
-    #[test]
-    fn test_execute() {
-        let mut problem = EXAMPLE_INPUT.parse::<Problem>().unwrap();
-        problem.execute().unwrap();
-        assert_eq!(
-            problem,
-            Problem {
-                stacks: vec![
-                    Stack {
-                        values: ['C'].into(),
-                        index: 1
-                    },
-                    Stack {
-                        values: ['M'].into(),
-                        index: 2
-                    },
-                    Stack {
-                        values: ['P', 'D', 'N', 'Z'].into(),
-                        index: 3
-                    }
-                ],
-                instructions: [].into()
-            }
-        );
-    }
-    #[test]
-    fn test_execute2() {
-        let mut problem = EXAMPLE_INPUT.parse::<Problem>().unwrap();
-        problem.execute2().unwrap();
-        assert_eq!(
-            problem,
-            Problem {
-                stacks: vec![
-                    Stack {
-                        values: ['M'].into(),
-                        index: 1
-                    },
-                    Stack {
-                        values: ['C'].into(),
-                        index: 2
-                    },
-                    Stack {
-                        values: ['P', 'Z', 'N', 'D'].into(),
-                        index: 3
-                    }
-                ],
-                instructions: [].into()
-            }
-        );
+    if let Some(top) = args.common.run_part(1, solve_1)? {
+        info!("[Part: 1] Top of stacks: {}", top);
+        show_final(CraneModel::CrateMover9000)?;
     }
 
-    #[test]
-    fn test_part1() {
-        assert_eq!(solution_part1(EXAMPLE_INPUT).unwrap(), "CMZ".to_string());
+    if let Some(top) = args.common.run_part(2, solve_2)? {
+        info!("[Part: 2] Top of stacks: {}", top);
+        show_final(CraneModel::CrateMover9001)?;
     }
 
-    #[test]
-    fn test_part2() {
-        assert_eq!(solution_part2(EXAMPLE_INPUT).unwrap(), "MCD".to_string());
-    }
+    Ok(())
 }
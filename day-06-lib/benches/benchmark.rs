@@ -0,0 +1,97 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use day_06_lib::{find_markers, MarkerSearch, Method};
+
+const INPUT: &str = include_str!("../../day-06/input.txt");
+
+fn criterion_benchmark(c: &mut Criterion) {
+    c.bench_function("part1", |b| b.iter(|| day_06_lib::part1(INPUT).unwrap()));
+    c.bench_function("part2", |b| b.iter(|| day_06_lib::part2(INPUT).unwrap()));
+}
+
+// Find both parts' markers in two passes over the input or in one.  On the
+// puzzle input, on a machine with a single core:
+//
+//     two_passes  one_pass
+//        11.9 µs   10.5 µs
+fn both_parts_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("both_parts");
+    group.bench_function("two_passes", |b| {
+        b.iter(|| {
+            (
+                day_06_lib::part1(INPUT).unwrap(),
+                day_06_lib::part2(INPUT).unwrap(),
+            )
+        })
+    });
+    group.bench_function("one_pass", |b| b.iter(|| find_markers(INPUT, [4, 14])));
+    group.finish();
+}
+
+// `len` random letters with the only marker of `window` different letters at
+// the end, so every search reads the whole input.  The rest uses only
+// `window - 1` letters.
+fn synthetic_input(len: usize, window: usize) -> String {
+    let mut rng = fastrand::Rng::with_seed(6);
+    let letters: Vec<char> = ('a'..='z').take(window).collect();
+    let mut input: String = (0..len - window)
+        .map(|_| letters[rng.usize(..window - 1)])
+        .collect();
+    input.extend(&letters);
+    input
+}
+
+// Compare the searches on generated inputs of increasing size.  On a machine
+// with a single core, in MiB/s:
+//
+//     window  bytes   hash_set  counting  bitmask
+//     4       4 KB         8.6       253      305
+//     4       256 KB       9.7       305      225
+//     4       4 MB         7.9       231      230
+//     4       64 MB          -       235      252
+//     14      4 KB         2.9       247      255
+//     14      256 KB       2.6       330      248
+//     14      4 MB         2.8       270      248
+//     14      64 MB          -       272      232
+//
+// Building a `HashSet` for every window costs time in the window's length,
+// so it falls further behind with longer markers and takes too long to run
+// at 64 MB.  Counting bytes and sliding a bitmask run at the same speed
+// within the noise between runs, whatever the window or input size.
+fn method_benchmark(c: &mut Criterion) {
+    let methods = [
+        (Method::HashSet, "hash_set"),
+        (Method::Counting, "counting"),
+        (Method::Bitmask, "bitmask"),
+    ];
+    for window in [4, 14] {
+        let mut group = c.benchmark_group(format!("window_{}", window));
+        group.sample_size(10);
+        for (len, name) in [
+            (4 << 10, "4KB"),
+            (256 << 10, "256KB"),
+            (4 << 20, "4MB"),
+            (64 << 20, "64MB"),
+        ] {
+            let input = synthetic_input(len, window);
+            group.throughput(Throughput::Bytes(len as u64));
+            for (method, method_name) in methods {
+                if method == Method::HashSet && len > 4 << 20 {
+                    continue;
+                }
+                let search = |input: &str| -> MarkerSearch { method.find_marker_n(input, window) };
+                group.bench_with_input(BenchmarkId::new(method_name, name), &input, |b, input| {
+                    b.iter(|| assert_eq!(search(input).unwrap().byte, len))
+                });
+            }
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(
+    benches,
+    criterion_benchmark,
+    both_parts_benchmark,
+    method_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+// Feed arbitrary bytes and window sizes to every day 6 marker search,
+// checking that none of them panic and that each finds the same marker as a
+// naive search.  Run with `cargo +nightly fuzz run markers` from day-06-lib.
+
+#![no_main]
+
+use std::io::BufReader;
+
+use day_06_lib::{
+    find_marker_bitmask, find_marker_bytes, find_marker_hash_set, find_marker_n,
+    find_marker_stream, find_markers, Marker, MarkerError, Method,
+};
+use libfuzzer_sys::fuzz_target;
+
+// Compare every pair of bytes in every window of `n`.
+fn naive(bytes: &[u8], n: usize) -> Result<usize, MarkerError> {
+    if n == 0 {
+        return Err(MarkerError::EmptyWindow);
+    }
+    (n..=bytes.len())
+        .find(|&end| {
+            let window = &bytes[end - n..end];
+            (0..n).all(|i| !window[i + 1..].contains(&window[i]))
+        })
+        .ok_or(MarkerError::NotFound {
+            len: bytes.len(),
+            window: n,
+        })
+}
+
+fuzz_target!(|data: &[u8]| {
+    // The first two bytes pick the window sizes, including the invalid 0
+    // and sizes no input could hold.  The third picks the reader's buffer
+    // size for the streaming search.
+    let [a, b, capacity, data @ ..] = data else {
+        return;
+    };
+    let (a, b) = (usize::from(a % 32), usize::from(b % 32));
+
+    let expected = naive(data, a);
+    assert_eq!(find_marker_bytes(data, a), expected);
+    let streamed = find_marker_stream(
+        BufReader::with_capacity(usize::from(*capacity).max(1), data),
+        a,
+    );
+    match (&streamed, &expected) {
+        (Ok(end), Ok(expected)) => assert_eq!(end, expected),
+        (Err(error), Err(expected)) => {
+            assert_eq!(error.downcast_ref::<MarkerError>(), Some(expected))
+        }
+        _ => panic!("streamed {:?}, expected {:?}", streamed, expected),
+    }
+
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let expected = expected.map(|byte| Marker::new(input, byte));
+    assert_eq!(find_marker_n(input, a), expected);
+    assert_eq!(find_marker_hash_set(input, a), expected);
+    for method in [Method::HashSet, Method::Counting] {
+        assert_eq!(method.find_marker_n(input, a), expected);
+    }
+    assert_eq!(
+        find_markers(input, [a, b]),
+        [expected, find_marker_n(input, b)]
+    );
+
+    // The bitmask search fails at the first byte that isn't a lowercase
+    // letter, unless it finds the marker first.
+    let end = expected.map_or(data.len(), |marker| marker.byte);
+    let expected = match data[..end]
+        .iter()
+        .position(|byte| !byte.is_ascii_lowercase())
+    {
+        Some(position) if a > 0 => Err(MarkerError::NotLowercase {
+            byte: data[position],
+            position,
+        }),
+        _ => expected,
+    };
+    assert_eq!(find_marker_bitmask(input, a), expected);
+    assert_eq!(Method::Bitmask.find_marker_n(input, a), expected);
+});
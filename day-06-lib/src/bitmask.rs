@@ -0,0 +1,59 @@
+use crate::{Marker, MarkerError, MarkerSearch};
+
+// Find the end of the first `n` different lowercase letters, sliding a
+// bitmask with a bit for each letter along the input.  A letter is XORed in
+// as it enters the window and again as it leaves, so a bit is set while the
+// window holds its letter an odd number of times.  With `n` bits set for
+// `n` letters each must appear once.  Fails on reaching anything but a
+// lowercase letter.
+pub fn find_marker_bitmask(input: &str, n: usize) -> MarkerSearch {
+    if n == 0 {
+        return Err(MarkerError::EmptyWindow);
+    }
+    let bytes = input.as_bytes();
+    let bit = |byte: u8| 1u32 << (byte - b'a');
+    let mut mask = 0;
+    for (i, &byte) in bytes.iter().enumerate() {
+        if !byte.is_ascii_lowercase() {
+            return Err(MarkerError::NotLowercase { byte, position: i });
+        }
+        mask ^= bit(byte);
+        if i >= n {
+            mask ^= bit(bytes[i - n]);
+        }
+        if mask.count_ones() as usize == n {
+            // Only ASCII gets this far, so bytes are characters.
+            return Ok(Marker {
+                byte: i + 1,
+                char: i + 1,
+            });
+        }
+    }
+
+    Err(MarkerError::NotFound {
+        len: bytes.len(),
+        window: n,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmask() {
+        // A letter seen three times sets its bit again.
+        assert_eq!(find_marker_bitmask("aaabcd", 4).unwrap().byte, 6);
+        assert_eq!(find_marker_bitmask("aaab", 2).unwrap().byte, 4);
+        assert!(find_marker_bitmask("abcdefghijklmnopqrstuvwxyz", 27).is_err());
+        assert_eq!(
+            find_marker_bitmask("abcd\n", 4).unwrap().byte,
+            4,
+            "stops before the newline"
+        );
+        assert_eq!(
+            find_marker_bitmask("aabB", 3).unwrap_err().to_string(),
+            "the bitmask method only handles lowercase letters, found 'B' at 3"
+        );
+    }
+}
@@ -0,0 +1,186 @@
+use std::array;
+
+use crate::{Marker, MarkerError, MarkerSearch};
+
+// Find the end of the first N different bytes in O(n), sliding a window
+// along the input while counting each byte in it and the bytes it holds more
+// than once.
+pub fn find_marker_fast<const N: usize>(input: &str) -> MarkerSearch {
+    find_marker_n(input, N)
+}
+
+// As `find_marker_fast`, with the marker length `n` chosen at runtime.
+pub fn find_marker_n(input: &str, n: usize) -> MarkerSearch {
+    find_marker_bytes(input.as_bytes(), n).map(|byte| Marker::new(input, byte))
+}
+
+// Find the end of the first `n` different bytes, of any value, so binary
+// data can be searched as well as text.  Text is searched as UTF-8 bytes:
+// each byte of a multi-byte character counts separately, so a marker may
+// start or end part way through a character, and the position returned is a
+// byte offset.
+pub fn find_marker_bytes(bytes: &[u8], n: usize) -> Result<usize, MarkerError> {
+    if n == 0 {
+        return Err(MarkerError::EmptyWindow);
+    }
+    let mut window = Window::default();
+    for (i, &byte) in bytes.iter().enumerate() {
+        window.add(byte);
+        if i >= n {
+            window.remove(bytes[i - n]);
+        }
+        if i + 1 >= n && window.is_distinct() {
+            return Ok(i + 1);
+        }
+    }
+
+    Err(MarkerError::NotFound {
+        len: bytes.len(),
+        window: n,
+    })
+}
+
+// Find the ends of the first markers of each length in `windows` in a single
+// pass over the input.  Rather than a window for each length, this tracks
+// the longest run of different bytes ending at each position, which is a
+// marker of every length up to its own, so each byte is handled once
+// however many lengths are wanted.  Stops once every marker has been found.
+pub fn find_markers<const K: usize>(input: &str, windows: [usize; K]) -> [MarkerSearch; K] {
+    let bytes = input.as_bytes();
+    // One past the position each byte was last seen at, or 0 if it hasn't
+    // been.
+    let mut last_seen = [0; 256];
+    // Start of the longest run of different bytes ending at the current one.
+    let mut start = 0;
+    let mut found = [None; K];
+    let mut searching = windows.iter().filter(|&&n| n > 0).count();
+    for (i, &byte) in bytes.iter().enumerate() {
+        if searching == 0 {
+            break;
+        }
+        start = start.max(last_seen[byte as usize]);
+        last_seen[byte as usize] = i + 1;
+        let run = i + 1 - start;
+        for (&n, found) in windows.iter().zip(&mut found) {
+            if n > 0 && n <= run && found.is_none() {
+                *found = Some(i + 1);
+                searching -= 1;
+            }
+        }
+    }
+
+    array::from_fn(|k| match (windows[k], found[k]) {
+        (0, _) => Err(MarkerError::EmptyWindow),
+        (_, Some(byte)) => Ok(Marker::new(input, byte)),
+        (n, None) => Err(MarkerError::NotFound {
+            len: bytes.len(),
+            window: n,
+        }),
+    })
+}
+
+// Counts of the bytes in a window, along with the number of bytes it holds
+// more than once.
+pub(crate) struct Window {
+    counts: [u32; 256],
+    duplicated: usize,
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Self {
+            counts: [0; 256],
+            duplicated: 0,
+        }
+    }
+}
+
+impl Window {
+    pub(crate) fn add(&mut self, byte: u8) {
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 2 {
+            self.duplicated += 1;
+        }
+    }
+
+    pub(crate) fn remove(&mut self, byte: u8) {
+        self.counts[byte as usize] -= 1;
+        if self.counts[byte as usize] == 1 {
+            self.duplicated -= 1;
+        }
+    }
+
+    pub(crate) fn is_distinct(&self) -> bool {
+        self.duplicated == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_examples() {
+        for (input, frame, message) in [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ] {
+            assert_eq!(find_marker_fast::<4>(input).unwrap().byte, frame);
+            assert_eq!(find_marker_fast::<14>(input).unwrap().byte, message);
+        }
+        // A marker ending the input.
+        assert_eq!(find_marker_fast::<4>("aaabcd").unwrap().byte, 6);
+        assert!(find_marker_fast::<4>("abc").is_err());
+    }
+
+    #[test]
+    fn bytes() {
+        // Every byte value, then the first again.
+        let all: Vec<u8> = (0..=255).chain([0]).collect();
+        assert_eq!(find_marker_bytes(&all, 256).unwrap(), 256);
+        assert_eq!(find_marker_bytes(&all[1..], 256).unwrap(), 256);
+        assert!(find_marker_bytes(&all, 257).is_err());
+        assert_eq!(find_marker_bytes(&[0xff, 0xff, 0x00, 0x80], 3).unwrap(), 4);
+
+        // 'é' is the two bytes C3 A9, each counted on its own, so a marker
+        // can end part way through it.
+        assert_eq!(
+            find_marker_n("ééa", 2).unwrap(),
+            Marker { byte: 2, char: 1 }
+        );
+        assert_eq!(
+            find_marker_n("aaé", 2).unwrap(),
+            Marker { byte: 3, char: 3 }
+        );
+    }
+
+    #[test]
+    fn single_pass() {
+        for (input, frame, message) in [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+            ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+        ] {
+            let [start_of_frame, start_of_message] = find_markers(input, [4, 14]);
+            assert_eq!(start_of_frame.unwrap().byte, frame);
+            assert_eq!(start_of_message.unwrap().byte, message);
+        }
+
+        // Each marker is found or not on its own, in any order.
+        assert_eq!(
+            find_markers("abcabcd", [4, 0, 3, 5]),
+            [
+                find_marker_n("abcabcd", 4),
+                Err(MarkerError::EmptyWindow),
+                find_marker_n("abcabcd", 3),
+                Err(MarkerError::NotFound { len: 7, window: 5 }),
+            ]
+        );
+        assert_eq!(find_markers("", []), []);
+    }
+}
@@ -0,0 +1,32 @@
+use std::{error::Error, fmt};
+
+// Why a search found no marker.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MarkerError {
+    // No `window` different bytes in a row in the `len` bytes searched.
+    NotFound { len: usize, window: usize },
+    // Markers need at least one byte.
+    EmptyWindow,
+    // The bitmask search reached a byte other than a lowercase letter.
+    NotLowercase { byte: u8, position: usize },
+}
+
+impl fmt::Display for MarkerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::NotFound { len, window } => write!(
+                f,
+                "no marker of {} different characters in {} bytes of input",
+                window, len
+            ),
+            Self::EmptyWindow => write!(f, "markers must be at least one character long"),
+            Self::NotLowercase { byte, position } => write!(
+                f,
+                "the bitmask method only handles lowercase letters, found {:?} at {}",
+                byte as char, position
+            ),
+        }
+    }
+}
+
+impl Error for MarkerError {}
@@ -0,0 +1,100 @@
+use std::collections::HashSet;
+
+use crate::{Marker, MarkerError, MarkerSearch};
+
+// Find the end of the first N different bytes, collecting each window of the
+// input into a `HashSet`.
+pub fn find_marker<const N: usize>(input: &str) -> MarkerSearch {
+    find_marker_hash_set(input, N)
+}
+
+// As `find_marker`, with the marker length chosen at runtime.  Like the
+// other searches, this looks at bytes rather than characters.
+pub fn find_marker_hash_set(input: &str, n: usize) -> MarkerSearch {
+    if n == 0 {
+        return Err(MarkerError::EmptyWindow);
+    }
+    // Inputs shorter than a marker have no windows at all.
+    input
+        .as_bytes()
+        .windows(n)
+        .position(|window| window.iter().collect::<HashSet<_>>().len() == n)
+        .map(|i| Marker::new(input, i + n))
+        .ok_or(MarkerError::NotFound {
+            len: input.len(),
+            window: n,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_of_frame() {
+        assert_eq!(
+            find_marker::<4>("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .unwrap()
+                .byte,
+            7
+        );
+        assert_eq!(
+            find_marker::<4>("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .unwrap()
+                .byte,
+            5
+        );
+        assert_eq!(
+            find_marker::<4>("nppdvjthqldpwncqszvftbrmjlhg")
+                .unwrap()
+                .byte,
+            6
+        );
+        assert_eq!(
+            find_marker::<4>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .unwrap()
+                .byte,
+            10
+        );
+        assert_eq!(
+            find_marker::<4>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .unwrap()
+                .byte,
+            11
+        );
+    }
+
+    #[test]
+    fn start_of_message() {
+        assert_eq!(
+            find_marker::<14>("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+                .unwrap()
+                .byte,
+            19
+        );
+        assert_eq!(
+            find_marker::<14>("bvwbjplbgvbhsrlpgdmjqwftvncz")
+                .unwrap()
+                .byte,
+            23
+        );
+        assert_eq!(
+            find_marker::<14>("nppdvjthqldpwncqszvftbrmjlhg")
+                .unwrap()
+                .byte,
+            23
+        );
+        assert_eq!(
+            find_marker::<14>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+                .unwrap()
+                .byte,
+            29
+        );
+        assert_eq!(
+            find_marker::<14>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+                .unwrap()
+                .byte,
+            26
+        );
+    }
+}
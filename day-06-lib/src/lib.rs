@@ -0,0 +1,43 @@
+mod bitmask;
+mod counting;
+mod error;
+mod hash_set;
+mod marker;
+mod method;
+mod stream;
+
+pub use bitmask::find_marker_bitmask;
+pub use counting::{find_marker_bytes, find_marker_fast, find_marker_n, find_markers};
+pub use error::MarkerError;
+pub use hash_set::{find_marker, find_marker_hash_set};
+pub use marker::{Marker, MarkerSearch};
+pub use method::Method;
+pub use stream::find_marker_stream;
+
+// Compute the answer to part 1, the end of the first start-of-packet marker.
+pub fn part1(input: &str) -> MarkerSearch {
+    find_marker_fast::<4>(input)
+}
+
+// Compute the answer to part 2, the end of the first start-of-message
+// marker.
+pub fn part2(input: &str) -> MarkerSearch {
+    find_marker_fast::<14>(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT).unwrap().byte, 7);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT).unwrap().byte, 19);
+    }
+}
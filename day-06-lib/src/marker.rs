@@ -0,0 +1,42 @@
+use std::fmt;
+
+use crate::MarkerError;
+
+// The end of a marker found in text, as offsets just past its last byte.
+// Searches work on bytes, so a marker can end part way through a multi-byte
+// character.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Marker {
+    // Bytes before the end of the marker.
+    pub byte: usize,
+    // Characters before the end of the marker, counting one it ends part way
+    // through.  The same as `byte` for ASCII.
+    pub char: usize,
+}
+
+impl Marker {
+    // The marker ending `byte` bytes into `input`.
+    pub fn new(input: &str, byte: usize) -> Self {
+        // Count the bytes starting characters, which aren't continuation
+        // bytes of the form 0b10xxxxxx.
+        let char = input.as_bytes()[..byte]
+            .iter()
+            .filter(|&&b| b & 0xc0 != 0x80)
+            .count();
+        Self { byte, char }
+    }
+}
+
+// Shows the byte offset, along with the character offset if it differs.
+impl fmt::Display for Marker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.byte == self.char {
+            write!(f, "{}", self.byte)
+        } else {
+            write!(f, "{} (character {})", self.byte, self.char)
+        }
+    }
+}
+
+// The first marker in some text, or why there isn't one.
+pub type MarkerSearch = Result<Marker, MarkerError>;
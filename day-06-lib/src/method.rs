@@ -0,0 +1,177 @@
+use std::{fmt, str::FromStr};
+
+use anyhow::{anyhow, Error, Result};
+
+use crate::{
+    find_marker, find_marker_bitmask, find_marker_fast, find_marker_hash_set, find_marker_n,
+    MarkerSearch,
+};
+
+// How to look for markers, named on the command line as `hash-set`,
+// `counting` or `bitmask`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Method {
+    // Collect each window into a `HashSet`.
+    HashSet,
+    // Slide a window of byte counts along the input.
+    Counting,
+    // Slide a bitmask of the letters in the window along the input.  Only
+    // handles lowercase letters.
+    Bitmask,
+}
+
+impl Method {
+    // Find the end of the first N different bytes this way.
+    pub fn find_marker<const N: usize>(self, input: &str) -> MarkerSearch {
+        match self {
+            Self::HashSet => find_marker::<N>(input),
+            Self::Counting => find_marker_fast::<N>(input),
+            Self::Bitmask => find_marker_bitmask(input, N),
+        }
+    }
+
+    // As `find_marker`, with the marker length `n` chosen at runtime.
+    pub fn find_marker_n(self, input: &str, n: usize) -> MarkerSearch {
+        match self {
+            Self::HashSet => find_marker_hash_set(input, n),
+            Self::Counting => find_marker_n(input, n),
+            Self::Bitmask => find_marker_bitmask(input, n),
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HashSet => write!(f, "hash-set"),
+            Self::Counting => write!(f, "counting"),
+            Self::Bitmask => write!(f, "bitmask"),
+        }
+    }
+}
+
+impl FromStr for Method {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hash-set" => Ok(Self::HashSet),
+            "counting" => Ok(Self::Counting),
+            "bitmask" => Ok(Self::Bitmask),
+            _ => Err(anyhow!(
+                "unknown method: {}, expected hash-set, counting or bitmask",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{find_marker_stream, Marker, MarkerError};
+
+    #[test]
+    fn names() {
+        for method in [Method::HashSet, Method::Counting, Method::Bitmask] {
+            assert_eq!(method.to_string().parse::<Method>().unwrap(), method);
+        }
+        assert!("simd".parse::<Method>().is_err());
+    }
+
+    #[test]
+    fn runtime_window() {
+        let input = "mjqjpqmgbljsphdztnvjfqwrcgsmlb";
+        for method in [Method::HashSet, Method::Counting, Method::Bitmask] {
+            assert_eq!(method.find_marker_n(input, 4).unwrap().byte, 7);
+            assert_eq!(method.find_marker_n(input, 14).unwrap().byte, 19);
+            assert_eq!(method.find_marker_n(input, 1).unwrap().byte, 1);
+            assert_eq!(method.find_marker_n(input, 3).unwrap().byte, 3);
+            assert!(method.find_marker_n(input, 0).is_err());
+        }
+        assert_eq!(find_marker_n(input, 8).unwrap().byte, 12);
+    }
+
+    #[test]
+    fn window_bounds() {
+        for method in [Method::HashSet, Method::Counting, Method::Bitmask] {
+            // The marker ends the input, or is all of it.
+            let byte = |input, n| method.find_marker_n(input, n).map(|marker| marker.byte);
+            assert_eq!(byte("aaabcd", 4), Ok(6));
+            assert_eq!(byte("abcd", 4), Ok(4));
+            assert_eq!(
+                method.find_marker_n("abc", 4),
+                Err(MarkerError::NotFound { len: 3, window: 4 })
+            );
+            assert_eq!(
+                method.find_marker_n("", 1),
+                Err(MarkerError::NotFound { len: 0, window: 1 })
+            );
+            assert_eq!(
+                method.find_marker_n("abcc", 4),
+                Err(MarkerError::NotFound { len: 4, window: 4 })
+            );
+            assert_eq!(
+                method.find_marker_n("abc", 0),
+                Err(MarkerError::EmptyWindow)
+            );
+        }
+        assert_eq!(
+            find_marker_stream("abc".as_bytes(), 4)
+                .unwrap_err()
+                .downcast::<MarkerError>()
+                .unwrap(),
+            MarkerError::NotFound { len: 3, window: 4 }
+        );
+        assert_eq!(
+            MarkerError::NotFound { len: 3, window: 4 }.to_string(),
+            "no marker of 4 different characters in 3 bytes of input"
+        );
+    }
+
+    #[test]
+    fn char_offsets() {
+        for method in [Method::HashSet, Method::Counting] {
+            let marker = method.find_marker_n("ééabcd", 4).unwrap();
+            assert_eq!(marker, Marker { byte: 6, char: 4 });
+            assert_eq!(marker.to_string(), "6 (character 4)");
+        }
+        assert_eq!(find_marker_fast::<4>("abcd").unwrap().to_string(), "4");
+    }
+
+    #[test]
+    fn fast_matches_hash_set() {
+        let mut rng = fastrand::Rng::with_seed(6);
+        for _ in 0..1000 {
+            // Few letters, so markers are rare.
+            let input: String = (0..rng.usize(20..200))
+                .map(|_| rng.char('a'..='o'))
+                .collect();
+            assert_eq!(
+                find_marker_fast::<4>(&input).ok(),
+                find_marker::<4>(&input).ok(),
+                "{input}"
+            );
+            assert_eq!(
+                find_marker_fast::<14>(&input).ok(),
+                find_marker::<14>(&input).ok(),
+                "{input}"
+            );
+            assert_eq!(
+                find_marker_bitmask(&input, 4).ok(),
+                find_marker_n(&input, 4).ok(),
+                "{input}"
+            );
+            assert_eq!(
+                find_marker_bitmask(&input, 14).ok(),
+                find_marker_n(&input, 14).ok(),
+                "{input}"
+            );
+            assert_eq!(
+                find_marker_stream(input.as_bytes(), 14).ok(),
+                find_marker_n(&input, 14).ok().map(|marker| marker.byte),
+                "{input}"
+            );
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::io::BufRead;
+
+use anyhow::Result;
+
+use crate::{counting::Window, MarkerError};
+
+// Find the end of the first `n` different bytes read from `reader`, keeping
+// only the last `n` bytes in memory.  Fails with a `MarkerError` or an
+// error reading.
+pub fn find_marker_stream(mut reader: impl BufRead, n: usize) -> Result<usize> {
+    if n == 0 {
+        return Err(MarkerError::EmptyWindow.into());
+    }
+    // The last `n` bytes read, the oldest at `position % n`.
+    let mut last = vec![0; n];
+    let mut window = Window::default();
+    let mut position = 0;
+    loop {
+        let buf = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        let len = buf.len();
+        for &byte in buf {
+            let slot = position % n;
+            if position >= n {
+                window.remove(last[slot]);
+            }
+            last[slot] = byte;
+            window.add(byte);
+            position += 1;
+            if position >= n && window.is_distinct() {
+                return Ok(position);
+            }
+        }
+        reader.consume(len);
+    }
+
+    Err(MarkerError::NotFound {
+        len: position,
+        window: n,
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io;
+
+    // Reads `data` a few bytes at a time.
+    struct Trickle<'a> {
+        data: &'a [u8],
+    }
+
+    impl io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = buf.len().min(self.data.len()).min(3);
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn stream() {
+        let input = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg";
+        assert_eq!(find_marker_stream(input.as_bytes(), 4).unwrap(), 10);
+        // Markers spanning several reads.
+        let reader = io::BufReader::with_capacity(
+            2,
+            Trickle {
+                data: input.as_bytes(),
+            },
+        );
+        assert_eq!(find_marker_stream(reader, 14).unwrap(), 29);
+        assert_eq!(find_marker_stream("aaabcd".as_bytes(), 4).unwrap(), 6);
+        assert!(find_marker_stream("aaabcc".as_bytes(), 4).is_err());
+        assert!(find_marker_stream(input.as_bytes(), 0).is_err());
+    }
+}
@@ -1,81 +1,95 @@
-use std::{collections::HashSet, fs, path::PathBuf};
+use std::{fs, io};
 
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
+use aoc_core::CommonArgs;
 use clap::Parser;
+use day_06_lib::{find_marker_bytes, find_marker_stream, find_markers, Method};
 
-fn find_marker<const N: usize>(input: &str) -> Result<usize> {
-    for i in 0..input.len() - N {
-        let chars: HashSet<_> = input[i..i + N].chars().collect();
-        if chars.len() == N {
-            return Ok(i + N);
-        }
-    }
-
-    Err(anyhow!("unable to find start of frame sequence"))
-}
+const EXAMPLE_INPUT: &str = include_str!("../../day-06-lib/src/example-input.txt");
 
 // Command line arguments.
 #[derive(Debug, Parser)]
+#[command(mut_arg("input", |arg| arg.required_unless_present_any(["example", "stdin"])))]
 struct Args {
-    input: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
+
+    /// How to look for markers: hash-set, counting or bitmask
+    #[arg(long, default_value_t = Method::Counting)]
+    method: Method,
+
+    /// Find the first marker of N different characters instead of solving
+    /// the parts
+    #[arg(long, value_name = "N")]
+    window: Option<usize>,
+
+    /// Read a stream from stdin, without holding it in memory, instead of
+    /// INPUT and look for the --window marker in it
+    #[arg(long, requires = "window", conflicts_with_all = ["input", "example"])]
+    stdin: bool,
+
+    /// Read INPUT as raw bytes, which need not be UTF-8, and look for the
+    /// --window marker in them
+    #[arg(long, requires = "window", conflicts_with_all = ["stdin", "method"])]
+    binary: bool,
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
+    args.common.init_logging();
 
-    let start_of_frame = find_marker::<4>(&input)?;
-    println!("[Part 1] Start of frame: {}", start_of_frame);
+    if args.stdin {
+        // `--stdin` and `--binary` require `--window`.
+        let n = args.window.unwrap();
+        println!(
+            "Marker of {}: {}",
+            n,
+            find_marker_stream(io::stdin().lock(), n)?
+        );
+        return Ok(());
+    }
 
-    let start_of_message = find_marker::<14>(&input)?;
-    println!("[Part 2] Start of message: {}", start_of_message);
+    if args.binary {
+        let n = args.window.unwrap();
+        let bytes = match (&args.common.input, args.common.example) {
+            (Some(path), false) => {
+                fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?
+            }
+            _ => EXAMPLE_INPUT.as_bytes().to_vec(),
+        };
+        println!("Marker of {}: {}", n, find_marker_bytes(&bytes, n)?);
+        return Ok(());
+    }
 
-    Ok(())
-}
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    if let Some(n) = args.window {
+        println!("Marker of {}: {}", n, args.method.find_marker_n(&input, n)?);
+        return Ok(());
+    }
 
-    #[test]
-    fn start_of_frame() {
-        assert_eq!(
-            find_marker::<4>("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(),
-            7
-        );
-        assert_eq!(find_marker::<4>("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), 5);
-        assert_eq!(find_marker::<4>("nppdvjthqldpwncqszvftbrmjlhg").unwrap(), 6);
-        assert_eq!(
-            find_marker::<4>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(),
-            10
-        );
-        assert_eq!(
-            find_marker::<4>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(),
-            11
-        );
+    // Counting finds both markers in one pass over the input, unless each
+    // part is wanted on its own or timed.
+    if args.method == Method::Counting && args.common.part.is_none() && !args.common.time {
+        let [start_of_frame, start_of_message] = find_markers(&input, [4, 14]);
+        println!("[Part 1] Start of frame: {}", start_of_frame?);
+        println!("[Part 2] Start of message: {}", start_of_message?);
+        return Ok(());
     }
 
-    #[test]
-    fn start_of_message() {
-        assert_eq!(
-            find_marker::<14>("mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(),
-            19
-        );
-        assert_eq!(
-            find_marker::<14>("bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(),
-            23
-        );
-        assert_eq!(
-            find_marker::<14>("nppdvjthqldpwncqszvftbrmjlhg").unwrap(),
-            23
-        );
-        assert_eq!(
-            find_marker::<14>("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(),
-            29
-        );
-        assert_eq!(
-            find_marker::<14>("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(),
-            26
-        );
+    if let Some(start_of_frame) = args
+        .common
+        .run_part(1, || Ok(args.method.find_marker::<4>(&input)?))?
+    {
+        println!("[Part 1] Start of frame: {}", start_of_frame);
+    }
+
+    if let Some(start_of_message) = args
+        .common
+        .run_part(2, || Ok(args.method.find_marker::<14>(&input)?))?
+    {
+        println!("[Part 2] Start of message: {}", start_of_message);
     }
+
+    Ok(())
 }
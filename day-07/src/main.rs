@@ -1,41 +1,43 @@
-use std::{fmt, fs, path::PathBuf};
+use std::fmt;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use aoc_core::CommonArgs;
+use aoc_parse::{finish, IResult, ParseError};
+use aoc_tree::{Aggregates, Edge, Named, NodeId, Tree};
 use clap::Parser;
-use env_logger::Env;
-use indextree::{Arena, NodeEdge, NodeId};
-use log::{debug, error, info};
+use log::{debug, info};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, alphanumeric1, char, line_ending, one_of, space1},
     combinator::{map_res, recognize},
+    error::context,
     multi::{many0, many0_count, many1, separated_list1},
     sequence::{pair, terminated},
-    IResult,
 };
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 
 // Adapted from https://github.com/Geal/nom/blob/main/doc/nom_recipes.md#integers
-fn decimal_value(input: &str) -> IResult<&str, u64> {
+fn decimal_value(input: &str) -> IResult<'_, u64> {
     map_res(
         recognize(many1(terminated(one_of("0123456789"), many0(char('_'))))),
         |value: &str| value.parse::<u64>(),
     )(input)
 }
 
-fn separator(input: &str) -> IResult<&str, &str> {
+fn separator(input: &str) -> IResult<'_, &str> {
     alt((tag("_"), tag("-"), tag(".")))(input)
 }
 
 // Adapted from https://docs.rs/nom/latest/nom/recipes/index.html#rust-style-identifiers
-fn file_name(input: &str) -> IResult<&str, &str> {
+fn file_name(input: &str) -> IResult<'_, &str> {
     recognize(pair(
         alt((alpha1, separator)),
         many0_count(alt((alphanumeric1, separator))),
     ))(input)
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 enum Directory {
     Root,
     Parent,
@@ -43,34 +45,34 @@ enum Directory {
 }
 
 impl Directory {
-    fn parse_root(input: &str) -> IResult<&str, Self> {
+    fn parse_root(input: &str) -> IResult<'_, Self> {
         let (input, _) = tag("/")(input)?;
         Ok((input, Self::Root))
     }
 
-    fn parse_parent(input: &str) -> IResult<&str, Self> {
+    fn parse_parent(input: &str) -> IResult<'_, Self> {
         let (input, _) = tag("..")(input)?;
         Ok((input, Self::Parent))
     }
 
-    fn parse_child(input: &str) -> IResult<&str, Self> {
+    fn parse_child(input: &str) -> IResult<'_, Self> {
         let (input, name) = file_name(input)?;
         Ok((input, Self::Child(name.into())))
     }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
+    fn parse(input: &str) -> IResult<'_, Self> {
         alt((Self::parse_root, Self::parse_parent, Self::parse_child))(input)
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 enum DirectoryEntry {
     File { name: String, size: u64 },
     Directory { name: String },
 }
 
 impl DirectoryEntry {
-    fn parse_file(input: &str) -> IResult<&str, Self> {
+    fn parse_file(input: &str) -> IResult<'_, Self> {
         let (input, size) = decimal_value(input)?;
         let (input, _) = space1(input)?;
         let (input, name) = file_name(input)?;
@@ -83,17 +85,22 @@ impl DirectoryEntry {
         ))
     }
 
-    fn parse_directory(input: &str) -> IResult<&str, Self> {
+    fn parse_directory(input: &str) -> IResult<'_, Self> {
         let (input, _) = tag("dir")(input)?;
         let (input, _) = space1(input)?;
         let (input, name) = file_name(input)?;
         Ok((input, Self::Directory { name: name.into() }))
     }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        alt((Self::parse_file, Self::parse_directory))(input)
+    fn parse(input: &str) -> IResult<'_, Self> {
+        context(
+            "directory entry",
+            alt((Self::parse_file, Self::parse_directory)),
+        )(input)
     }
+}
 
+impl Named for DirectoryEntry {
     fn name(&self) -> &str {
         match self {
             Self::Directory { name } => name,
@@ -111,39 +118,42 @@ impl fmt::Display for DirectoryEntry {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 enum Command {
     Cd(Directory),
     Ls(Vec<DirectoryEntry>),
 }
 
 struct CommandIterator<'a> {
+    // The full input, kept so parse errors can report their line.
+    source: &'a str,
     input: &'a str,
 }
 
 impl Iterator for CommandIterator<'_> {
-    type Item = Command;
+    type Item = Result<Command, ParseError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.input.is_empty() {
             return None;
         }
-        match Command::parse(self.input) {
+        match finish(self.source, Command::parse(self.input)) {
             Ok((input, command)) => {
                 self.input = input;
                 debug!("parsed {:?}", command);
-                Some(command)
+                Some(Ok(command))
             }
             Err(e) => {
-                error!("parse error: {}", e);
-                None
+                // Nothing after an error can be parsed reliably.
+                self.input = "";
+                Some(Err(e))
             }
         }
     }
 }
 
 impl Command {
-    fn parse_cd(input: &str) -> IResult<&str, Self> {
+    fn parse_cd(input: &str) -> IResult<'_, Self> {
         let (input, _) = tag("cd")(input)?;
         let (input, _) = space1(input)?;
         let (input, directory) = Directory::parse(input)?;
@@ -152,7 +162,7 @@ impl Command {
         Ok((input, Self::Cd(directory)))
     }
 
-    fn parse_ls(input: &str) -> IResult<&str, Self> {
+    fn parse_ls(input: &str) -> IResult<'_, Self> {
         let (input, _) = tag("ls")(input)?;
         let (input, _) = line_ending(input)?;
         let (input, entries) = separated_list1(line_ending, DirectoryEntry::parse)(input)?;
@@ -161,96 +171,91 @@ impl Command {
         Ok((input, Self::Ls(entries)))
     }
 
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let (input, _) = tag("$")(input)?;
-        let (input, _) = space1(input)?;
-        alt((Self::parse_cd, Self::parse_ls))(input)
+    fn parse(input: &str) -> IResult<'_, Self> {
+        context("command", |input| {
+            let (input, _) = tag("$")(input)?;
+            let (input, _) = space1(input)?;
+            alt((Self::parse_cd, Self::parse_ls))(input)
+        })(input)
     }
 
-    fn parse_multiple(input: &str) -> CommandIterator {
-        CommandIterator { input }
+    fn parse_multiple(input: &str) -> CommandIterator<'_> {
+        CommandIterator {
+            source: input,
+            input,
+        }
     }
 }
 
 struct Filesystem {
-    root: NodeId,
-    arena: Arena<DirectoryEntry>,
+    tree: Tree<DirectoryEntry>,
+    // The size of every entry, including everything under a directory,
+    // computed once the tree is built.
+    sizes: Aggregates<DirectoryEntry, u64>,
 }
 
 impl Filesystem {
-    fn parse(input: &str) -> Self {
-        let mut arena = Arena::new();
-        let root = arena.new_node(DirectoryEntry::Directory { name: "/".into() });
-        let mut current_dir = root;
+    fn new(tree: Tree<DirectoryEntry>) -> Self {
+        let sizes = tree.aggregate(|entry, children| match entry {
+            DirectoryEntry::File { name: _, size } => *size,
+            DirectoryEntry::Directory { name: _ } => children.iter().copied().sum(),
+        });
+        Self { tree, sizes }
+    }
+
+    fn parse(input: &str) -> Result<Self> {
+        let mut tree = Tree::new(DirectoryEntry::Directory { name: "/".into() });
+        let mut current_dir = tree.root();
 
         for command in Command::parse_multiple(input) {
-            match command {
+            match command? {
                 // Assume this only occurs at the start of the input and ignore
                 Command::Cd(Directory::Root) => (),
                 Command::Cd(Directory::Parent) => {
                     // Assume input is valid ("cd .." only occurs in directories
                     // with parents.
-                    current_dir = arena.get(current_dir).unwrap().parent().unwrap();
+                    current_dir = tree.parent(current_dir).unwrap();
                 }
                 Command::Cd(Directory::Child(name)) => {
-                    // Linear search through directory entries.
-                    for entry in current_dir.children(&arena) {
-                        if arena.get(entry).unwrap().get()
-                            == (&DirectoryEntry::Directory { name: name.clone() })
-                        {
-                            current_dir = entry;
-                            break;
+                    let dir = tree.find_child(current_dir, |entry| {
+                        matches!(entry, DirectoryEntry::Directory { name: dir } if *dir == name)
+                    });
+                    match dir {
+                        Some(dir) => current_dir = dir,
+                        None if tree.child_by_name(current_dir, &name).is_some() => {
+                            bail!("Can't cd into {name}: it is a file")
                         }
+                        None => bail!("Can't cd into {name}: no such directory"),
                     }
                 }
                 Command::Ls(entries) => {
                     for entry in entries {
-                        current_dir.append(arena.new_node(entry), &mut arena);
+                        tree.append(current_dir, entry);
                     }
                 }
             }
         }
 
-        Self { root, arena }
+        Ok(Self::new(tree))
     }
 
-    fn filter_subdirs_by_size(
-        &self,
-        filter: &impl Fn(u64) -> bool,
-        dir: NodeId,
-        dirs: &mut Vec<(String, u64)>,
-    ) -> u64 {
-        let mut size = 0;
-        for child in dir.children(&self.arena) {
-            match self.arena.get(child).unwrap().get() {
-                DirectoryEntry::File {
-                    name: _,
-                    size: file_size,
-                } => {
-                    size += file_size;
+    // Directories whose total size passes `filter`, each after those inside
+    // it.
+    fn filter_dirs_by_size(&self, filter: impl Fn(u64) -> bool) -> Vec<(String, u64)> {
+        self.tree
+            .post_order(self.tree.root())
+            .filter_map(|id| match self.tree.get(id) {
+                DirectoryEntry::Directory { name } => {
+                    let size = *self.sizes.get(id);
+                    filter(size).then(|| (name.clone(), size))
                 }
-                DirectoryEntry::Directory { name: _ } => {
-                    let dir_size = self.filter_subdirs_by_size(filter, child, dirs);
-                    size += dir_size;
-                }
-            }
-        }
-        if filter(size) {
-            dirs.push((self.arena.get(dir).unwrap().get().name().to_owned(), size))
-        }
-
-        size
-    }
-
-    fn filter_dirs_by_size(&self, filter: impl Fn(u64) -> bool + 'static) -> Vec<(String, u64)> {
-        let mut dirs = Vec::new();
-        self.filter_subdirs_by_size(&filter, self.root, &mut dirs);
-        dirs
+                DirectoryEntry::File { .. } => None,
+            })
+            .collect()
     }
 
     fn total_size(&self) -> u64 {
-        let mut dirs = Vec::new();
-        self.filter_subdirs_by_size(&|_| false, self.root, &mut dirs)
+        *self.sizes.get(self.tree.root())
     }
 }
 
@@ -259,15 +264,15 @@ impl fmt::Display for Filesystem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut indent = String::new();
 
-        for edge in self.root.traverse(&self.arena) {
+        for edge in self.tree.traverse(self.tree.root()) {
             match edge {
-                NodeEdge::Start(id) => {
-                    let node = self.arena.get(id).unwrap().get();
+                Edge::Enter(id) => {
+                    let node = self.tree.get(id);
                     writeln!(f, "{}- {}", indent, node)?;
 
                     indent.push_str("  ")
                 }
-                NodeEdge::End(_) => {
+                Edge::Exit(_) => {
                     indent.truncate(indent.len() - 2);
                 }
             }
@@ -277,6 +282,71 @@ impl fmt::Display for Filesystem {
     }
 }
 
+// `Filesystem` is backed by an arena, so it is serialized as nested nodes
+// instead: each entry followed by its children.
+#[derive(Deserialize)]
+struct SerializedNode {
+    entry: DirectoryEntry,
+    #[serde(default)]
+    children: Vec<SerializedNode>,
+}
+
+// Borrowed counterpart of `SerializedNode` which avoids copying the tree.
+struct NodeRef<'a> {
+    tree: &'a Tree<DirectoryEntry>,
+    id: NodeId<DirectoryEntry>,
+}
+
+impl Serialize for NodeRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let children: Vec<_> = self
+            .tree
+            .children(self.id)
+            .map(|id| NodeRef {
+                tree: self.tree,
+                id,
+            })
+            .collect();
+
+        let mut node = serializer.serialize_struct("SerializedNode", 2)?;
+        node.serialize_field("entry", self.tree.get(self.id))?;
+        node.serialize_field("children", &children)?;
+        node.end()
+    }
+}
+
+impl Serialize for Filesystem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NodeRef {
+            tree: &self.tree,
+            id: self.tree.root(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Filesystem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn append(
+            tree: &mut Tree<DirectoryEntry>,
+            parent: NodeId<DirectoryEntry>,
+            children: Vec<SerializedNode>,
+        ) {
+            for child in children {
+                let id = tree.append(parent, child.entry);
+                append(tree, id, child.children);
+            }
+        }
+
+        let root = SerializedNode::deserialize(deserializer)?;
+        let mut tree = Tree::new(root.entry);
+        let root_id = tree.root();
+        append(&mut tree, root_id, root.children);
+
+        Ok(Self::new(tree))
+    }
+}
+
 fn solution_part1(fs: &Filesystem) -> u64 {
     fs.filter_dirs_by_size(|size| size <= 100000)
         .iter()
@@ -285,9 +355,6 @@ fn solution_part1(fs: &Filesystem) -> u64 {
 }
 
 fn solution_part2(fs: &Filesystem) -> u64 {
-    // Calling `fs.total_size()` here causes fs to be traversed twice.  This
-    // could be optimized by calculating directory sizes at Filesystem
-    // creation time.
     let size_to_free = 30000000 - (70000000 - fs.total_size());
     let filter = move |size| size >= size_to_free;
     *fs.filter_dirs_by_size(filter)
@@ -297,25 +364,29 @@ fn solution_part2(fs: &Filesystem) -> u64 {
         .unwrap()
 }
 
+const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
+
 // Command line arguments.
 #[derive(Debug, Parser)]
 struct Args {
-    input: PathBuf,
+    #[command(flatten)]
+    common: CommonArgs,
 }
 
 fn main() -> Result<()> {
-    env_logger::Builder::from_env(Env::default().default_filter_or("info")).init();
-
     let args = Args::parse();
-    let input = fs::read_to_string(&args.input)?;
+    args.common.init_logging();
+    let input = args.common.read_input(EXAMPLE_INPUT)?;
 
-    let fs = Filesystem::parse(&input);
+    let fs = Filesystem::parse(&input)?;
 
-    let total = solution_part1(&fs);
-    info!("[Part 1] Sum of directory sizes under 100000: {total}");
+    if let Some(total) = args.common.run_part(1, || Ok(solution_part1(&fs)))? {
+        info!("[Part 1] Sum of directory sizes under 100000: {total}");
+    }
 
-    let size = solution_part2(&fs);
-    info!("[Part 2] Size of directory to free: {size}");
+    if let Some(size) = args.common.run_part(2, || Ok(solution_part2(&fs)))? {
+        info!("[Part 2] Size of directory to free: {size}");
+    }
 
     Ok(())
 }
@@ -326,8 +397,6 @@ mod tests {
 
     use super::*;
 
-    const EXAMPLE_INPUT: &str = include_str!("example-input.txt");
-
     #[test]
     fn parse_directory() {
         assert_eq!(Directory::parse("/").unwrap(), ("", Directory::Root));
@@ -414,7 +483,8 @@ mod tests {
                 8504156 c.dat
                 dir d
             "#})
-            .collect::<Vec<_>>(),
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap(),
             vec![
                 Command::Cd(Directory::Root),
                 Command::Ls(vec![
@@ -433,9 +503,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_error() {
+        let input = "$ cd /\n$ ls\ndir a\nbogus line\n$ cd a\n";
+        let mut commands = Command::parse_multiple(input);
+        assert!(commands.next().unwrap().is_ok());
+        assert!(commands.next().unwrap().is_ok());
+        let error = commands.next().unwrap().unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.line_text, "bogus line");
+        assert!(commands.next().is_none());
+
+        let error = Filesystem::parse(input).err().unwrap();
+        assert_eq!(error.downcast_ref::<ParseError>().map(|e| e.line), Some(4));
+    }
+
+    #[test]
+    fn cd_into_file() {
+        let input = "$ cd /\n$ ls\n10 a\ndir b\n$ cd a\n$ ls\n20 c\n";
+        let error = Filesystem::parse(input).err().unwrap();
+        assert_eq!(error.to_string(), "Can't cd into a: it is a file");
+
+        let input = "$ cd /\n$ ls\ndir b\n$ cd c\n";
+        let error = Filesystem::parse(input).err().unwrap();
+        assert_eq!(error.to_string(), "Can't cd into c: no such directory");
+
+        // Only the directory matches when a file shares its name.
+        let input = "$ cd /\n$ ls\n10 a\ndir a\n$ cd a\n$ ls\n20 c\n";
+        let fs = Filesystem::parse(input).unwrap();
+        assert_eq!(fs.total_size(), 30);
+        let dir = fs.tree.children(fs.tree.root()).nth(1).unwrap();
+        assert_eq!(*fs.sizes.get(dir), 20);
+    }
+
     #[test]
     fn parse_fs() {
-        let text = format!("{}", Filesystem::parse(EXAMPLE_INPUT));
+        let text = format!("{}", Filesystem::parse(EXAMPLE_INPUT).unwrap());
         println!("{text}");
         assert_eq!(
             text,
@@ -458,9 +561,18 @@ mod tests {
         )
     }
 
+    #[test]
+    fn serde_round_trip() {
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
+        let json = serde_json::to_string(&fs).unwrap();
+        let restored: Filesystem = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.to_string(), fs.to_string());
+        assert_eq!(restored.total_size(), 48381165);
+    }
+
     #[test]
     fn filter_dirs() {
-        let fs = Filesystem::parse(EXAMPLE_INPUT);
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(
             fs.filter_dirs_by_size(|size| size <= 100000),
             vec![("e".to_string(), 584), ("a".to_string(), 94853)]
@@ -469,19 +581,35 @@ mod tests {
 
     #[test]
     fn fs_size() {
-        let fs = Filesystem::parse(EXAMPLE_INPUT);
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(fs.total_size(), 48381165);
     }
 
+    #[test]
+    fn cached_sizes() {
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
+        let root = fs.tree.root();
+        let a = fs.tree.child_by_name(root, "a").unwrap();
+        let e = fs.tree.child_by_name(a, "e").unwrap();
+        let d = fs.tree.child_by_name(root, "d").unwrap();
+        assert_eq!(*fs.sizes.get(e), 584);
+        assert_eq!(*fs.sizes.get(a), 94853);
+        assert_eq!(*fs.sizes.get(d), 24933642);
+        assert_eq!(
+            *fs.sizes.get(fs.tree.child_by_name(d, "k").unwrap()),
+            7214296
+        );
+    }
+
     #[test]
     fn part1() {
-        let fs = Filesystem::parse(EXAMPLE_INPUT);
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(solution_part1(&fs), 95437);
     }
 
     #[test]
     fn part2() {
-        let fs = Filesystem::parse(EXAMPLE_INPUT);
+        let fs = Filesystem::parse(EXAMPLE_INPUT).unwrap();
         assert_eq!(solution_part2(&fs), 24933642);
     }
 }